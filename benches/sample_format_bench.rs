@@ -1,5 +1,6 @@
 use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
 use libbladerf_rs::bladerf1::SampleFormat;
+use num_complex::Complex;
 
 fn bench_pack(c: &mut Criterion) {
     let mut group = c.benchmark_group("pack_sc16q11_packed");
@@ -37,5 +38,40 @@ fn bench_unpack(c: &mut Criterion) {
     }
 }
 
-criterion_group!(benches, bench_pack, bench_unpack,);
+fn bench_to_complex(c: &mut Criterion) {
+    let mut group = c.benchmark_group("to_complex_sc16q11");
+    let sizes: &[usize] = &[256, 4_096, 65_536];
+
+    for &num_samples in sizes {
+        group.throughput(Throughput::Elements(num_samples as u64));
+        group.bench_with_input(
+            BenchmarkId::new("i16", num_samples),
+            &num_samples,
+            |b, &n| {
+                let src = vec![0u8; 4 * n];
+                let mut dst = vec![Complex::new(0i16, 0); n];
+                b.iter(|| {
+                    SampleFormat::Sc16Q11
+                        .to_complex_i16(&src, &mut dst)
+                        .unwrap()
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("f32", num_samples),
+            &num_samples,
+            |b, &n| {
+                let src = vec![0u8; 4 * n];
+                let mut dst = vec![Complex::new(0f32, 0.0); n];
+                b.iter(|| {
+                    SampleFormat::Sc16Q11
+                        .to_complex_f32(&src, &mut dst)
+                        .unwrap()
+                })
+            },
+        );
+    }
+}
+
+criterion_group!(benches, bench_pack, bench_unpack, bench_to_complex,);
 criterion_main!(benches);
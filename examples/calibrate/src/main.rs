@@ -1,6 +1,5 @@
 use anyhow::Result;
 use libbladerf_rs::bladerf1::BladeRf1;
-use libbladerf_rs::bladerf1::hardware::lms6002d::dc_calibration::DcCalModule;
 
 fn main() -> Result<()> {
     env_logger::builder()
@@ -15,17 +14,11 @@ fn main() -> Result<()> {
     let dc_cals = rf.get_dc_cals()?;
     log::debug!("{dc_cals}");
 
-    log::debug!("Calibrating: {:?}", DcCalModule::RxVga2);
-    rf.calibrate_dc(DcCalModule::RxVga2)?;
+    let dc_cals = rf.calibrate_dc_all_rx()?;
+    log::debug!("After RX calibration: {dc_cals}");
 
-    log::debug!("Calibrating: {:?}", DcCalModule::RxLpf);
-    rf.calibrate_dc(DcCalModule::RxLpf)?;
-
-    log::debug!("Calibrating: {:?}", DcCalModule::TxLpf);
-    rf.cal_tx_lpf()?;
-
-    log::debug!("Calibrating: {:?}", DcCalModule::LpfTuning);
-    rf.calibrate_dc(DcCalModule::LpfTuning)?;
+    let dc_cals = rf.calibrate_dc_all_tx()?;
+    log::debug!("After TX calibration: {dc_cals}");
 
     Ok(())
 }
@@ -25,7 +25,7 @@ fn main() -> Result<()> {
     // }
 
     let frequency_range = rf.get_frequency_range()?;
-    log::debug!("Frequency Range: {frequency_range:?}");
+    log::debug!("Frequency Range: {frequency_range}");
 
     // Set Frequency to minimum frequency
     rf.set_frequency(
@@ -45,7 +45,7 @@ fn main() -> Result<()> {
     log::debug!("Frequency TX: {}", frequency_tx);
 
     let sample_rate_range = RfLinkSession::get_sample_rate_range();
-    log::debug!("Sample Rate: {sample_rate_range:?}");
+    log::debug!("Sample Rate: {sample_rate_range}");
 
     // Set Sample Rate to minimum Sample Rate
     rf.set_sample_rate(Channel::Rx, sample_rate_range.min().unwrap() as u32)?;
@@ -57,7 +57,7 @@ fn main() -> Result<()> {
     log::debug!("Sample Rate TX: {}", sample_rate_tx);
 
     let bandwidth_range = RfLinkSession::get_bandwidth_range();
-    log::debug!("Bandwidth: {bandwidth_range:?}");
+    log::debug!("Bandwidth: {bandwidth_range}");
 
     // Set Sample Rate to minimum Sample Rate
     rf.set_bandwidth(Channel::Rx, bandwidth_range.min().unwrap() as u32)?;
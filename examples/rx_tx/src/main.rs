@@ -30,12 +30,6 @@ fn do_rx(rf: &mut RfLinkSession) -> Result<()> {
 fn _do_tx(rf: &mut RfLinkSession) -> Result<()> {
     println!("called do_tx()");
     sleep(Duration::from_millis(5_000));
-    rf.perform_format_config(SampleFormat::Sc16Q11)?;
-    println!("called perform_format_config(SampleFormat::Sc16Q11)");
-    sleep(Duration::from_millis(5_000));
-    rf.enable_module(Channel::Tx, true)?;
-    println!("called enable_module(Channel::Tx, true)");
-    sleep(Duration::from_millis(5_000));
 
     let mut streamer = TxStream::builder(rf)
         .buffer_size(32_768)
@@ -56,8 +50,8 @@ fn _do_tx(rf: &mut RfLinkSession) -> Result<()> {
 
     sleep(Duration::from_millis(5_000));
 
-    println!("called enable_module(Channel::Tx, false)");
     let _ = streamer.close(rf);
+    println!("closed TX stream");
 
     Ok(())
 }
@@ -5,25 +5,47 @@
 //! builders, hardware abstraction types, and DC calibration table management.
 
 pub mod board;
+pub mod boot;
 pub mod calibration;
 pub mod hardware;
 pub mod protocol;
 pub use crate::nios_client::NiosCore;
 pub use crate::usb::BladeRf1UsbInterfaceCommands;
 pub use board::QuickTune;
+#[cfg(feature = "dsp")]
+pub use board::psd_dbfs;
 pub use board::rf_port::RfPort;
-pub use board::xb::ExpansionBoard;
 #[cfg(feature = "xb200")]
 pub use board::xb::xb200::{Xb200Filter, Xb200Path};
-pub use board::{BLADERF1_USB_PID, BLADERF1_USB_VID};
-pub use board::{BladeRf1, ConfigSession, FlashSession, RfLinkSession, RxStream, TxStream};
+#[cfg(feature = "xb300")]
+pub use board::xb::xb300::{Xb300Amplifier, Xb300Trx};
+pub use board::xb::{ExpansionBoard, xb_gpio};
+pub use board::{BLADERF1_USB_PID, BLADERF1_USB_VID, REENUMERATION_POLL_INTERVAL, have_cap};
 pub use board::{
-    Correction, FpgaSource, GainMode, METADATA_HEADER_SIZE, MetadataHeader, RxMux, RxStreamBuilder,
-    SampleFormat, TuningMode, TxStreamBuilder,
+    BladeRf1, BladeRf1Builder, BladeRfDevInfo, ConfigSession, FlashSession, RfLinkSession,
+    RxStream, TxStream,
 };
+pub use board::{
+    BladeRfMetaFlags, BoardState, Capability, ChannelConfig, Correction, DeviceConfig, FpgaSource,
+    GainMode, GainStageInfo, METADATA_HEADER_SIZE, MetadataHeader, ModuleEnableState, Packet,
+    RxMux, RxStreamBuilder, SampleFormat, StreamFormats, TriggerRole, TriggerState, TuningMode,
+    TxGap, TxStreamBuilder, VctcxoTamerMode,
+};
+pub use board::{Capture, SIGNAL_PEAK_SAMPLES, SignalPeak};
+pub use board::{FX3_BOOTLOADER_USB_PID, FX3_BOOTLOADER_USB_VID};
+pub use board::{FwLogEntry, FwLogFile};
+pub use board::{MAX_BUFFER_COUNT, align_buffer_size, check_buffer_count, stream_block_size};
+pub use board::{POWER_FULL_SCALE, power_dbfs};
+pub use board::{
+    SELF_TEST_MIN_CORRELATION, SELF_TEST_MODES, SELF_TEST_TONE_PERIOD, SelfTestReport,
+    SelfTestResult, ToneMeasurement, self_test_tone,
+};
+pub use board::{SWEEP_MAX_STEPS, Sweep, SweepPoint, sweep_frequencies};
+pub use board::{TONE_FULL_SCALE, check_tone, tone_samples};
+pub use boot::{BladeRfBoot, Fx3Image, Fx3Section, fx3_checksum};
 pub use calibration::{DcCalEntry, DcCalTable};
 pub use hardware::lms6002d::dc_calibration::{AgcDcCorrection, DcPair};
-pub use hardware::lms6002d::gain::GainDb;
+pub use hardware::lms6002d::gain::{GainDb, GainStage};
 pub use hardware::lms6002d::{Band, LpfMode, Tune};
 pub use hardware::si5338::{RationalRate, SmbMode};
 pub use protocol::RetuneResult;
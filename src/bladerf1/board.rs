@@ -14,9 +14,18 @@
 //! that serializes all register I/O. Session types borrow `&mut NiosCore`, so the
 //! Rust borrow checker guarantees that at most one session is active at a time.
 //! Users never access `NiosCore` directly; they call methods on the session.
+//!
+//! [`BladeRf1`] is deliberately not `Clone`: the FX3 interface can be claimed
+//! only once, and a clone would bypass the one-session guarantee. It is
+//! `Send`, so threads share a device through `Arc<Mutex<BladeRf1>>` and open
+//! a session while holding the lock.
 
 mod bandwidth;
+mod builder;
 mod calibration;
+mod capabilities;
+mod capture;
+mod config;
 pub(crate) mod corrections;
 mod dac_trim;
 pub(crate) mod firmware;
@@ -26,16 +35,24 @@ mod frequency;
 mod gain;
 mod loopback;
 mod lpf_mode;
-pub use loopback::Loopback;
+mod power;
+pub use loopback::{BLADERF1_FW_LOOPBACK_VERSION, Loopback};
+#[cfg(feature = "direct-register-access")]
+mod registers;
 pub(crate) mod rf_port;
 pub(crate) mod rx_mux;
 mod sample_rate;
+mod self_test;
 mod smb;
 pub mod stream;
+mod sweep;
 mod timestamp;
+mod tone;
 mod trigger;
 mod vctcxo_tamer;
 pub mod xb;
+#[cfg(feature = "xb200")]
+use crate::bladerf1::board::xb::xb200::Xb200Filter;
 use crate::bladerf1::calibration::DcCalTable;
 use crate::bladerf1::hardware::dac161s055::Dac161s055;
 use crate::bladerf1::hardware::lms6002d::dc_calibration::DcCals;
@@ -45,15 +62,30 @@ use crate::bladerf1::hardware::spi_flash::FlashMeta;
 use crate::channel::Channel;
 use crate::error::Error;
 use crate::flash::decode_flash_size;
+use crate::log_target;
 use crate::nios_client::NiosCore;
 use crate::usb::{
     BladeRf1DeviceCommands, BladeRf1UsbInterfaceCommands, DeviceCommands, UsbAltSetting,
     UsbInterfaceCommands, UsbTransport,
 };
-pub use corrections::Correction;
+use crate::version::SemanticVersion;
+pub use builder::BladeRf1Builder;
+pub use capabilities::{Capability, have_cap};
+pub use capture::{Capture, SIGNAL_PEAK_SAMPLES, SignalPeak};
+pub use config::{ChannelConfig, DeviceConfig};
+pub use corrections::{
+    BLADERF_CORR_DCOFF_MAX, BLADERF_CORR_DCOFF_MIN, BLADERF_CORR_GAIN_MAX, BLADERF_CORR_GAIN_MIN,
+    BLADERF_CORR_PHASE_MAX, BLADERF_CORR_PHASE_MIN, Correction,
+};
+pub use fpga::{FwLogEntry, FwLogFile};
 pub use frequency::QuickTune;
-pub use frequency::TuningMode;
+pub use frequency::{BLADERF_DEFAULT_TUNING_MODE_ENV, TuningMode};
+#[cfg(feature = "dsp")]
+pub use power::psd_dbfs;
+pub use power::{POWER_FULL_SCALE, power_dbfs};
 use std::path::Path;
+pub use sweep::{SWEEP_MAX_STEPS, Sweep, SweepPoint, sweep_frequencies};
+pub use tone::{TONE_FULL_SCALE, check_tone, tone_samples};
 pub use trigger::{TriggerRole, TriggerState};
 pub use vctcxo_tamer::VctcxoTamerMode;
 
@@ -79,15 +111,47 @@ impl TryFrom<u8> for FpgaSource {
         }
     }
 }
-pub use gain::GainMode;
+
+/// Lifecycle state of a BladeRF1 board.
+///
+/// Derived from the hardware on demand rather than cached, so it stays
+/// correct when the FPGA is reloaded or another process initializes the
+/// device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BoardState {
+    /// The FPGA is not configured; only FX3 control requests are available.
+    Uninitialized,
+    /// The FPGA is configured but `initialize()` has not been run.
+    FpgaLoaded,
+    /// The board has been initialized and is ready for RF operation.
+    Initialized,
+}
+
+impl std::fmt::Display for BoardState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            BoardState::Uninitialized => "uninitialized",
+            BoardState::FpgaLoaded => "FPGA loaded",
+            BoardState::Initialized => "initialized",
+        };
+        f.write_str(name)
+    }
+}
+pub use gain::{GainMode, GainStageInfo};
 #[cfg(not(target_os = "android"))]
 use nusb::DeviceInfo;
 use nusb::{Device, MaybeFuture, Speed};
 pub use rx_mux::RxMux;
+pub use self_test::{
+    SELF_TEST_MIN_CORRELATION, SELF_TEST_MODES, SELF_TEST_TONE_PERIOD, SelfTestReport,
+    SelfTestResult, ToneMeasurement, self_test_tone,
+};
 pub use stream::{
     BLADERF_GPIO_8BIT_MODE, BLADERF_GPIO_HIGHLY_PACKED_MODE, BLADERF_GPIO_PACKET,
-    BLADERF_GPIO_TIMESTAMP, BLADERF_GPIO_TIMESTAMP_DIV2, METADATA_HEADER_SIZE, MetadataHeader,
-    RxStream, RxStreamBuilder, SampleFormat, TxStream, TxStreamBuilder,
+    BLADERF_GPIO_TIMESTAMP, BLADERF_GPIO_TIMESTAMP_DIV2, BladeRfMetaFlags, MAX_BUFFER_COUNT,
+    METADATA_HEADER_SIZE, MetadataHeader, Packet, RxStream, RxStreamBuilder, SampleFormat,
+    StreamFormats, TxGap, TxStream, TxStreamBuilder, align_buffer_size, check_buffer_count,
+    stream_block_size,
 };
 
 /// Nuand BladeRF1 USB Vendor ID.
@@ -96,9 +160,92 @@ pub const BLADERF1_USB_VID: u16 = 0x2CF0;
 /// Nuand BladeRF1 USB Product ID.
 pub const BLADERF1_USB_PID: u16 = 0x5246;
 
+/// USB Vendor ID of the Cypress FX3 ROM bootloader.
+pub const FX3_BOOTLOADER_USB_VID: u16 = 0x04B4;
+
+/// USB Product ID of the Cypress FX3 ROM bootloader.
+pub const FX3_BOOTLOADER_USB_PID: u16 = 0x00F3;
+
 /// GPIO bit that enables small DMA transfers on Hi-Speed USB.
 pub const BLADERF_GPIO_FEATURE_SMALL_DMA_XFER: u16 = 1 << 7;
 
+/// Identification of a connected BladeRF1, read from its USB descriptors.
+///
+/// Produced by [`BladeRf1::enumerate`] without opening the device or
+/// claiming its interface. `bus_id` and `address` can be passed to
+/// [`BladeRf1::from_bus_addr`], `serial` to [`BladeRf1::from_serial`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BladeRfDevInfo {
+    /// Serial number string, if the device reports one.
+    pub serial: Option<String>,
+    /// Platform-specific USB bus identifier.
+    pub bus_id: String,
+    /// Device address on the bus.
+    pub address: u8,
+    /// Negotiated USB speed, if known.
+    pub speed: Option<Speed>,
+    /// Manufacturer string, if the device reports one.
+    pub manufacturer: Option<String>,
+    /// Product string, if the device reports one.
+    pub product: Option<String>,
+}
+
+#[cfg(not(target_os = "android"))]
+impl From<&DeviceInfo> for BladeRfDevInfo {
+    fn from(dev: &DeviceInfo) -> Self {
+        Self {
+            serial: dev.serial_number().map(str::to_owned),
+            bus_id: dev.bus_id().to_owned(),
+            address: dev.device_address(),
+            speed: dev.speed(),
+            manufacturer: dev.manufacturer_string().map(str::to_owned),
+            product: dev.product_string().map(str::to_owned),
+        }
+    }
+}
+
+/// Last known enable state of the RX and TX modules, indexed by
+/// `Channel as usize`.
+///
+/// Used by [`RfLinkSession::enable_module`] to skip the LMS6002D and FX3
+/// transfers when a module is already in the requested state. `None` means
+/// the state is unknown, in which case the transfers are always issued.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ModuleEnableState([Option<bool>; 2]);
+
+impl ModuleEnableState {
+    /// Returns the last known state of `channel`'s module, if any.
+    pub fn get(&self, channel: Channel) -> Option<bool> {
+        self.0[channel as usize]
+    }
+
+    /// Forgets the state of both modules.
+    pub fn invalidate(&mut self) {
+        self.0 = [None; 2];
+    }
+
+    /// Runs `transfer` to put `channel`'s module into the `enable` state,
+    /// unless it is already known to be there and `force` is `false`.
+    ///
+    /// Returns whether `transfer` ran. On success the new state is recorded;
+    /// if `transfer` fails the state becomes unknown and the error is returned.
+    pub fn apply(
+        &mut self,
+        channel: Channel,
+        enable: bool,
+        force: bool,
+        transfer: impl FnOnce() -> crate::Result<()>,
+    ) -> crate::Result<bool> {
+        if !force && self.get(channel) == Some(enable) {
+            return Ok(false);
+        }
+        self.0[channel as usize] = None;
+        transfer()?;
+        self.0[channel as usize] = Some(enable);
+        Ok(true)
+    }
+}
+
 /// Primary device handle for the BladeRF1.
 ///
 /// Owns the USB device and the internal [`NiosCore`].
@@ -112,7 +259,12 @@ pub const BLADERF_GPIO_FEATURE_SMALL_DMA_XFER: u16 = 1 << 7;
 /// `<serial>_dc_tx.json`. The directory searched defaults to the current
 /// directory; use [`load_dc_cal_tables_from_dir`](BladeRf1::load_dc_cal_tables_from_dir)
 /// to load from an explicit path, which is required on platforms without a
-/// meaningful working directory such as Android.
+/// meaningful working directory such as Android. The factory VCTCXO DAC
+/// trim is also read from the flash calibration region so that
+/// [`initialize`](RfLinkSession::initialize) can apply it.
+///
+/// Not `Clone`; see the [module documentation](crate::bladerf1::board) for sharing a device
+/// between threads.
 ///
 /// On drop, RX and TX modules are disabled (best-effort).
 pub struct BladeRf1 {
@@ -120,6 +272,14 @@ pub struct BladeRf1 {
     nios: NiosCore,
     dc_rx_table: Option<DcCalTable>,
     dc_tx_table: Option<DcCalTable>,
+    tuning_mode: TuningMode,
+    dac_trim: u16,
+    fx3_version: Option<SemanticVersion>,
+    commanded_frequency: [Option<u64>; 2],
+    stream_format: StreamFormats,
+    module_enabled: ModuleEnableState,
+    #[cfg(feature = "xb200")]
+    xb200_auto_filter: [Option<Xb200Filter>; 2],
 }
 impl BladeRf1 {
     /// Lists all BladeRF1 devices currently connected to the host.
@@ -132,15 +292,28 @@ impl BladeRf1 {
             dev.vendor_id() == BLADERF1_USB_VID && dev.product_id() == BLADERF1_USB_PID
         }))
     }
+    /// Returns the serial, bus address, speed and descriptor strings of every
+    /// connected BladeRF1, without opening any of them.
+    ///
+    /// Not available on Android, where USB enumeration is not permitted.
+    #[cfg(not(target_os = "android"))]
+    pub fn enumerate() -> crate::Result<Vec<BladeRfDevInfo>> {
+        Ok(Self::list_bladerf1()?
+            .map(|dev| BladeRfDevInfo::from(&dev))
+            .collect())
+    }
     fn build(device: Device, cal_table_dir: Option<&Path>) -> crate::Result<Self> {
-        log::debug!("Manufacturer: {}", device.manufacturer()?);
-        log::debug!("Product: {}", device.product()?);
-        log::debug!("Serial: {}", device.serial()?);
-        log::debug!("Speed: {:?}", device.speed());
-        log::debug!("Languages: {:x?}", device.get_supported_languages()?);
+        log::debug!(target: log_target::BOARD, "Manufacturer: {}", device.manufacturer()?);
+        log::debug!(target: log_target::BOARD, "Product: {}", device.product()?);
+        log::debug!(target: log_target::BOARD, "Serial: {}", device.serial()?);
+        log::debug!(target: log_target::BOARD, "Speed: {:?}", device.speed());
+        log::debug!(
+            target: log_target::BOARD,
+            "Languages: {:x?}", device.get_supported_languages()?
+        );
         let speed = device.speed().ok_or(Error::UnsupportedSpeed)?;
         if speed < Speed::High {
-            log::error!("BladeRF requires High/Super/SuperPlus speeds");
+            log::error!(target: log_target::BOARD, "BladeRF requires High/Super/SuperPlus speeds");
             return Err(Error::UnsupportedSpeed);
         }
         let nios = NiosCore::new(UsbTransport::new(
@@ -152,9 +325,27 @@ impl BladeRf1 {
             nios,
             dc_rx_table: None,
             dc_tx_table: None,
+            tuning_mode: TuningMode::from_env(),
+            dac_trim: dac_trim::DEFAULT_DAC_TRIM,
+            fx3_version: None,
+            commanded_frequency: [None; 2],
+            stream_format: StreamFormats::default(),
+            module_enabled: ModuleEnableState::default(),
+            #[cfg(feature = "xb200")]
+            xb200_auto_filter: [None; 2],
         };
         result.wait_until_ready()?;
         Self::auto_load_tables(&mut result, cal_table_dir);
+        Self::load_dac_trim(&mut result);
+        result.fx3_version = result
+            .fx3_version()
+            .inspect_err(|e| {
+                log::warn!(
+                    target: log_target::BOARD,
+                    "Failed to determine FX3 firmware version, skipping version checks: {e}"
+                )
+            })
+            .ok();
         Ok(result)
     }
     fn wait_until_ready(&self) -> crate::Result<()> {
@@ -164,14 +355,18 @@ impl BladeRf1 {
                 Ok(true) => return Ok(()),
                 Ok(false) => {
                     if i == 0 {
-                        log::info!("Waiting for device to become ready...");
+                        log::info!(
+                            target: log_target::BOARD,
+                            "Waiting for device to become ready..."
+                        );
                     } else {
-                        log::debug!("Retry {}/{}.", i + 1, MAX_RETRIES);
+                        log::debug!(target: log_target::BOARD, "Retry {}/{}.", i + 1, MAX_RETRIES);
                     }
                     std::thread::sleep(std::time::Duration::from_secs(1));
                 }
                 Err(e) => {
                     log::warn!(
+                        target: log_target::BOARD,
                         "Firmware does not support device ready query ({e:#}). \
                          Ensure flash-autoloading completes before opening the device."
                     );
@@ -179,14 +374,17 @@ impl BladeRf1 {
                 }
             }
         }
-        log::debug!("Timed out while waiting for device.");
+        log::debug!(target: log_target::BOARD, "Timed out while waiting for device.");
         Err(Error::Timeout)
     }
     fn auto_load_tables(result: &mut Self, dir: Option<&Path>) {
         let serial = match result.device.serial() {
             Ok(s) => s,
             Err(e) => {
-                log::warn!("Failed to read serial number, skipping DC cal table auto-load: {e}");
+                log::warn!(
+                    target: log_target::BOARD,
+                    "Failed to read serial number, skipping DC cal table auto-load: {e}"
+                );
                 return;
             }
         };
@@ -196,20 +394,55 @@ impl BladeRf1 {
         if rx_path.exists() {
             match DcCalTable::load(&rx_path) {
                 Ok(tbl) => {
-                    log::debug!("Loaded RX DC cal table from {}", rx_path.display());
+                    log::debug!(
+                        target: log_target::BOARD,
+                        "Loaded RX DC cal table from {}", rx_path.display()
+                    );
                     result.dc_rx_table = Some(tbl);
                 }
-                Err(e) => log::warn!("Failed to parse RX DC cal table {}: {e}", rx_path.display()),
+                Err(e) => {
+                    log::warn!(
+                        target: log_target::BOARD,
+                        "Failed to parse RX DC cal table {}: {e}", rx_path.display()
+                    )
+                }
             }
         }
         if tx_path.exists() {
             match DcCalTable::load(&tx_path) {
                 Ok(tbl) => {
-                    log::debug!("Loaded TX DC cal table from {}", tx_path.display());
+                    log::debug!(
+                        target: log_target::BOARD,
+                        "Loaded TX DC cal table from {}", tx_path.display()
+                    );
                     result.dc_tx_table = Some(tbl);
                 }
-                Err(e) => log::warn!("Failed to parse TX DC cal table {}: {e}", tx_path.display()),
+                Err(e) => {
+                    log::warn!(
+                        target: log_target::BOARD,
+                        "Failed to parse TX DC cal table {}: {e}", tx_path.display()
+                    )
+                }
+            }
+        }
+    }
+    fn load_dac_trim(result: &mut Self) {
+        match result
+            .flash_session()
+            .and_then(|mut flash| flash.read_flash_dac_trim())
+        {
+            Ok(trim) => {
+                log::debug!(
+                    target: log_target::BOARD,
+                    "Loaded VCTCXO DAC trim {trim:#06x} from flash"
+                );
+                result.dac_trim = trim;
             }
+            Err(e) => log::warn!(
+                target: log_target::BOARD,
+                "Failed to read VCTCXO DAC trim from flash, defaulting to {:#06x}: {e}",
+                dac_trim::DEFAULT_DAC_TRIM
+            ),
         }
     }
     /// Opens the first BladeRF1 device found.
@@ -240,6 +473,22 @@ impl BladeRf1 {
             .wait()?;
         Self::build(device, None)
     }
+    /// Opens a BladeRF1 device by serial number like
+    /// [`from_serial`](BladeRf1::from_serial), retrying transient failures.
+    ///
+    /// Up to `attempts` opens are tried, sleeping `delay` before the first
+    /// retry and doubling it after each, which covers a device that is still
+    /// re-enumerating after [`device_reset`](BladeRf1::device_reset). Only
+    /// errors for which [`Error::is_transient`] holds are retried;
+    /// `Error::NotFound` and other permanent errors return immediately.
+    #[cfg(not(target_os = "android"))]
+    pub fn from_serial_with_retry(
+        serial: &str,
+        attempts: u32,
+        delay: std::time::Duration,
+    ) -> crate::Result<Self> {
+        crate::error::retry_transient(attempts, delay, || Self::from_serial(serial))
+    }
     /// Opens a BladeRF1 device at the given USB bus number and address.
     ///
     /// DC calibration tables are auto-loaded from the current directory. Not
@@ -290,13 +539,23 @@ impl BladeRf1 {
         if rx_path.exists() {
             match DcCalTable::load(&rx_path) {
                 Ok(tbl) => self.dc_rx_table = Some(tbl),
-                Err(e) => log::warn!("Failed to parse RX DC cal table {}: {e}", rx_path.display()),
+                Err(e) => {
+                    log::warn!(
+                        target: log_target::BOARD,
+                        "Failed to parse RX DC cal table {}: {e}", rx_path.display()
+                    )
+                }
             }
         }
         if tx_path.exists() {
             match DcCalTable::load(&tx_path) {
                 Ok(tbl) => self.dc_tx_table = Some(tbl),
-                Err(e) => log::warn!("Failed to parse TX DC cal table {}: {e}", tx_path.display()),
+                Err(e) => {
+                    log::warn!(
+                        target: log_target::BOARD,
+                        "Failed to parse TX DC cal table {}: {e}", tx_path.display()
+                    )
+                }
             }
         }
         Ok(())
@@ -306,9 +565,17 @@ impl BladeRf1 {
         self.device.serial()
     }
 
-    /// Loads a DC calibration table from a JSON file for the given channel.
+    /// Loads a DC calibration table from a file for the given channel.
+    ///
+    /// Files with a `.tbl` extension are parsed as libbladeRF binary tables;
+    /// anything else is read as JSON. The loaded entry for the current
+    /// frequency is applied on the next `set_frequency()`.
     pub fn load_dc_cal_table(&mut self, channel: Channel, path: &Path) -> crate::Result<()> {
-        let table = DcCalTable::load(path)?;
+        let table = if path.extension().is_some_and(|e| e == "tbl") {
+            DcCalTable::load_tbl(path)?
+        } else {
+            DcCalTable::load(path)?
+        };
         match channel {
             Channel::Rx => self.dc_rx_table = Some(table),
             Channel::Tx => self.dc_tx_table = Some(table),
@@ -342,6 +609,47 @@ impl BladeRf1 {
         self.device.fx3_firmware_version()
     }
 
+    /// Returns the FX3 firmware version parsed from the USB string descriptor.
+    ///
+    /// Returns `Error::Argument` if the descriptor is not a version string.
+    pub fn fx3_version(&self) -> crate::Result<SemanticVersion> {
+        self.device.fx3_firmware_version()?.parse()
+    }
+
+    /// Returns the factory VCTCXO DAC trim applied by `initialize()`.
+    ///
+    /// Read from the flash calibration region when the device is opened.
+    /// Falls back to mid-scale (0x8000) if the calibration data is missing
+    /// or unreadable.
+    pub fn factory_dac_trim(&self) -> u16 {
+        self.dac_trim
+    }
+
+    /// Returns the timeout applied to each NIOS packet transfer.
+    ///
+    /// Defaults to [`DEFAULT_CONTROL_TIMEOUT`](crate::usb::DEFAULT_CONTROL_TIMEOUT).
+    pub fn control_timeout(&self) -> std::time::Duration {
+        self.nios.transport().control_timeout()
+    }
+
+    /// Sets the timeout applied to each NIOS packet transfer, i.e. to every
+    /// register access made through a session.
+    ///
+    /// A transfer that exceeds it fails with `Error::Timeout`; the request
+    /// is cancelled, so the next access starts cleanly. FX3 vendor requests
+    /// keep `DEFAULT_CONTROL_TIMEOUT`.
+    ///
+    /// Returns `Error::Argument` if `timeout` is zero.
+    pub fn set_control_timeout(&mut self, timeout: std::time::Duration) -> crate::Result<()> {
+        log::debug!(target: log_target::BOARD, "Control timeout: {timeout:?}");
+        self.nios.transport_mut().set_control_timeout(timeout)
+    }
+
+    /// Returns the USB alternate setting currently selected on the interface.
+    pub fn usb_mode(&self) -> UsbAltSetting {
+        self.nios.get_alt_setting()
+    }
+
     /// Creates an [`RfLinkSession`] for normal RF operation.
     ///
     /// Switches the USB alt setting to RfLink if not already there.
@@ -349,12 +657,21 @@ impl BladeRf1 {
     /// no redundant USB switch is performed.
     pub fn rf_link_session(&mut self) -> crate::Result<RfLinkSession<'_>> {
         if self.nios.transport().current_alt_setting() != UsbAltSetting::RfLink {
+            self.module_enabled.invalidate();
             self.nios.usb_change_setting(UsbAltSetting::RfLink)?;
         }
         Ok(RfLinkSession {
             nios: &mut self.nios,
             dc_rx_table: self.dc_rx_table.as_ref(),
             dc_tx_table: self.dc_tx_table.as_ref(),
+            tuning_mode: &mut self.tuning_mode,
+            dac_trim: self.dac_trim,
+            fx3_version: self.fx3_version,
+            commanded_frequency: &mut self.commanded_frequency,
+            stream_format: &mut self.stream_format,
+            module_enabled: &mut self.module_enabled,
+            #[cfg(feature = "xb200")]
+            xb200_auto_filter: &mut self.xb200_auto_filter,
         })
     }
 
@@ -367,6 +684,7 @@ impl BladeRf1 {
             return Err(Error::StreamsActive);
         }
         if self.nios.transport().current_alt_setting() != UsbAltSetting::SpiFlash {
+            self.module_enabled.invalidate();
             self.nios.usb_change_setting(UsbAltSetting::SpiFlash)?;
         }
         let result = self
@@ -390,6 +708,10 @@ impl BladeRf1 {
 
     /// Creates a [`ConfigSession`] for FPGA loading and device configuration.
     ///
+    /// Loading an FPGA resets the LMS6002D, so the host-side record of
+    /// commanded frequencies, stream formats and module enable state is
+    /// cleared.
+    ///
     /// Returns [`Error::StreamsActive`] if any stream is currently running.
     pub fn config_session(&mut self) -> crate::Result<ConfigSession<'_>> {
         if self.nios.active_streams() > 0 {
@@ -398,16 +720,136 @@ impl BladeRf1 {
         if self.nios.transport().current_alt_setting() != UsbAltSetting::Config {
             self.nios.usb_change_setting(UsbAltSetting::Config)?;
         }
+        self.commanded_frequency = [None; 2];
+        self.stream_format = StreamFormats::default();
+        self.module_enabled.invalidate();
         Ok(ConfigSession {
             nios: &mut self.nios,
         })
     }
 
+    /// Returns the RF frontend to an idle state: clears any loopback mode and
+    /// disables the RX and TX modules.
+    ///
+    /// Unlike the best-effort disable on drop, errors are reported. Loopback
+    /// is only cleared if the board has been initialized, since it is LMS6002D
+    /// state that does not exist before. The handle remains usable afterwards.
+    ///
+    /// Returns [`Error::StreamsActive`] if any stream is still running; close
+    /// streams with their own `close()` first.
+    pub fn close(&mut self) -> crate::Result<()> {
+        if self.nios.active_streams() > 0 {
+            return Err(Error::StreamsActive);
+        }
+        let mut rf = self.rf_link_session()?;
+        if rf.board_state()? == BoardState::Initialized {
+            rf.set_loopback(Loopback::None)?;
+        }
+        self.module_enabled.invalidate();
+        self.nios.usb_enable_module(Channel::Rx, false)?;
+        self.nios.usb_enable_module(Channel::Tx, false)
+    }
+
     /// Resets the device, causing it to re-enumerate on the USB bus.
     pub fn device_reset(&mut self) -> crate::Result<()> {
         self.nios.usb_device_reset()
     }
 
+    /// Makes the device re-enumerate as the Cypress FX3 ROM bootloader,
+    /// e.g. to recover from or replace broken FX3 firmware.
+    ///
+    /// Consumes the handle: after the request the BladeRF1 leaves the bus
+    /// and the handle would no longer refer to a live device. The bootloader
+    /// appears as a new device with `FX3_BOOTLOADER_USB_VID` and
+    /// `FX3_BOOTLOADER_USB_PID`, which the caller must enumerate and open
+    /// itself; it does not speak the BladeRF1 protocol, so `BladeRf1` cannot
+    /// open it. A transient error from the request is ignored, since the
+    /// device may leave the bus before acknowledging it.
+    pub fn jump_to_bootloader(self) -> crate::Result<()> {
+        match self.nios.usb_jump_to_bootloader() {
+            Err(err) if !err.is_transient() => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    /// Resets the device and opens it again once it has re-enumerated.
+    ///
+    /// Consumes the handle, since it no longer refers to a live device after
+    /// the reset. A transient error from the reset request is ignored, since
+    /// the device may leave the bus before acknowledging it. The bus is then
+    /// polled every `REENUMERATION_POLL_INTERVAL`, first until the old device
+    /// has disappeared and then until a BladeRF1 with the same serial number
+    /// is present, which is opened like
+    /// [`from_serial`](BladeRf1::from_serial). The FPGA
+    /// is only present again if it is autoloaded from flash, so the returned
+    /// handle has not been through
+    /// [`initialize`](RfLinkSession::initialize).
+    ///
+    /// The old device is waited for at most half of `timeout`. If it is
+    /// still listed by then, it may have re-enumerated between two polls
+    /// under the same ID, so the search by serial number starts anyway.
+    ///
+    /// Returns `Error::NotFound` if the device does not come back and open
+    /// successfully within `timeout`. Not available on Android.
+    #[cfg(not(target_os = "android"))]
+    pub fn reset_and_reopen(mut self, timeout: std::time::Duration) -> crate::Result<Self> {
+        let serial = self.serial()?;
+        let old_id = Self::list_bladerf1()?
+            .find(|dev| dev.serial_number() == Some(serial.as_str()))
+            .ok_or(Error::NotFound)?
+            .id();
+        if let Err(err) = self.device_reset()
+            && !err.is_transient()
+        {
+            return Err(err);
+        }
+        drop(self);
+
+        let start = std::time::Instant::now();
+        let deadline = start + timeout;
+        let expired = || {
+            let expired = std::time::Instant::now() >= deadline;
+            if expired {
+                log::error!(
+                    target: log_target::BOARD,
+                    "BladeRF1 {serial} did not re-enumerate within {timeout:?}"
+                );
+            }
+            expired
+        };
+        let vanish_deadline = start + timeout / 2;
+        while Self::list_bladerf1()?.any(|dev| dev.id() == old_id) {
+            if std::time::Instant::now() >= vanish_deadline {
+                log::debug!(
+                    target: log_target::BOARD,
+                    "BladeRF1 {serial} still listed after {:?}, matching by serial",
+                    timeout / 2
+                );
+                break;
+            }
+            std::thread::sleep(REENUMERATION_POLL_INTERVAL);
+        }
+        loop {
+            let reappeared =
+                Self::list_bladerf1()?.find(|dev| dev.serial_number() == Some(serial.as_str()));
+            if let Some(info) = reappeared {
+                match info.open().wait().map_err(Error::from) {
+                    Ok(device) => match Self::build(device, None) {
+                        Ok(sdr) => return Ok(sdr),
+                        Err(err) if err.is_transient() => {}
+                        Err(err) => return Err(err),
+                    },
+                    Err(err) if err.is_transient() => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            if expired() {
+                return Err(Error::NotFound);
+            }
+            std::thread::sleep(REENUMERATION_POLL_INTERVAL);
+        }
+    }
+
     /// Returns `true` if the FPGA has been configured (loaded and ready).
     pub fn is_fpga_configured(&self) -> crate::Result<bool> {
         self.nios.usb_is_fpga_configured()
@@ -416,12 +858,15 @@ impl BladeRf1 {
 
 impl Drop for BladeRf1 {
     fn drop(&mut self) {
-        log::debug!("BladeRf1::drop — shutting down device");
+        log::debug!(target: log_target::BOARD, "BladeRf1::drop — shutting down device");
         let _ = self.nios.usb_enable_module(Channel::Rx, false);
         let _ = self.nios.usb_enable_module(Channel::Tx, false);
     }
 }
 
+/// Interval at which [`BladeRf1::reset_and_reopen`] polls the bus.
+pub const REENUMERATION_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
 /// Session for normal RF operation (tuning, gain, streaming, initialization, etc.).
 ///
 /// Borrows `&mut NiosCore` from [`BladeRf1`], so the borrow checker prevents
@@ -432,6 +877,22 @@ pub struct RfLinkSession<'a> {
     pub(crate) nios: &'a mut NiosCore,
     pub(crate) dc_rx_table: Option<&'a DcCalTable>,
     pub(crate) dc_tx_table: Option<&'a DcCalTable>,
+    pub(crate) tuning_mode: &'a mut TuningMode,
+    pub(crate) dac_trim: u16,
+    pub(crate) fx3_version: Option<SemanticVersion>,
+    /// Frequency last passed to `set_frequency()` per channel, indexed by
+    /// `Channel as usize`. Only used to cross-check `get_frequency()`.
+    pub(crate) commanded_frequency: &'a mut [Option<u64>; 2],
+    /// Sample format claimed by the stream of each direction. The format GPIO
+    /// bits are shared by both directions, so this is what conflicting
+    /// formats are checked against.
+    pub(crate) stream_format: &'a mut StreamFormats,
+    /// Last known RX and TX module enable state, see `enable_module()`.
+    pub(crate) module_enabled: &'a mut ModuleEnableState,
+    /// Automatic XB-200 filter mode per channel, indexed by `Channel as usize`.
+    /// The filter switch only holds the selected bank, so the mode is kept here.
+    #[cfg(feature = "xb200")]
+    pub(crate) xb200_auto_filter: &'a mut [Option<Xb200Filter>; 2],
 }
 
 /// Session for SPI flash read/write/erase operations.
@@ -467,20 +928,63 @@ impl RfLinkSession<'_> {
 
     /// Checks that the device has been initialized by reading the config GPIO.
     ///
-    /// Returns [`Error::BoardState`] if the lower 7 bits of GPIO are zero,
+    /// Returns [`Error::WrongState`] if the lower 7 bits of GPIO are zero,
     /// meaning [`initialize`](RfLinkSession::initialize) has not yet been
     /// called (or the FPGA was just reloaded, resetting NIOS).
     fn require_initialized(&mut self) -> crate::Result<()> {
+        if self.config_gpio_read().is_ok_and(|cfg| (cfg & 0x7f) != 0) {
+            return Ok(());
+        }
+        Err(Error::WrongState {
+            required: BoardState::Initialized,
+            actual: self.board_state()?,
+        })
+    }
+
+    /// Returns `Error::NoApi` if the FX3 firmware is older than `min`.
+    ///
+    /// Passes if the firmware version could not be determined at open.
+    pub(crate) fn require_fx3_version(&self, min: SemanticVersion) -> crate::Result<()> {
+        match self.fx3_version {
+            Some(device) if device < min => Err(Error::NoApi { device, min }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the current lifecycle state of the board.
+    ///
+    /// Queries the FX3 for the FPGA configuration status and, if the FPGA is
+    /// loaded, reads the config GPIO to determine whether
+    /// [`initialize`](RfLinkSession::initialize) has been run.
+    pub fn board_state(&mut self) -> crate::Result<BoardState> {
+        if !self.nios.usb_is_fpga_configured()? {
+            return Ok(BoardState::Uninitialized);
+        }
         let cfg = self.config_gpio_read()?;
         if (cfg & 0x7f) == 0 {
-            return Err(Error::BoardState("device not initialized"));
+            Ok(BoardState::FpgaLoaded)
+        } else {
+            Ok(BoardState::Initialized)
+        }
+    }
+
+    /// Selects the RfLink USB alternate setting if the interface is in any other mode.
+    pub fn ensure_rf_link(&mut self) -> crate::Result<()> {
+        let alt_setting = self.nios.get_alt_setting();
+        if alt_setting != UsbAltSetting::RfLink {
+            log::debug!(
+                target: log_target::BOARD,
+                "Switching USB alt setting from {alt_setting:?} to RfLink"
+            );
+            self.module_enabled.invalidate();
+            self.nios.usb_change_setting(UsbAltSetting::RfLink)?;
         }
         Ok(())
     }
 
     /// Returns the FPGA version as a string.
     pub fn fpga_version(&mut self) -> crate::Result<String> {
-        let version = self.nios.nios_get_fpga_version()?;
+        let version = self.fpga_version_struct()?;
         Ok(format!("{version}"))
     }
 
@@ -492,14 +996,17 @@ impl RfLinkSession<'_> {
     /// Writes the config GPIO register, automatically setting the small DMA
     /// transfer bit when connected at Hi-Speed USB.
     pub fn config_gpio_write(&mut self, mut data: u32) -> crate::Result<()> {
-        log::trace!("[config_gpio_write] data: {data}");
+        log::trace!(target: log_target::BOARD, "[config_gpio_write] data: {data}");
         let speed = self.nios.transport().speed();
         if speed == Speed::High {
             data |= BLADERF_GPIO_FEATURE_SMALL_DMA_XFER as u32;
         } else {
             data &= !(BLADERF_GPIO_FEATURE_SMALL_DMA_XFER as u32);
         }
-        log::trace!("[config_gpio_write] data after speed check: {data}");
+        log::trace!(
+            target: log_target::BOARD,
+            "[config_gpio_write] data after speed check: {data}"
+        );
         self.nios.nios_config_write(data)
     }
 
@@ -523,24 +1030,35 @@ impl RfLinkSession<'_> {
     /// When `force` is `true`, initialization is performed regardless.
     ///
     /// The init sequence configures the LMS6002D transceiver, sets default
-    /// sample rates (1 MHz), DAC trim (0), frequencies (TX 2.447 GHz,
-    /// RX 2.484 GHz), and gain mode (MGC). After the standard init sequence,
+    /// sample rates (1 MHz), the factory DAC trim read from flash, frequencies
+    /// (TX 2.447 GHz, RX 2.484 GHz), and gain mode (MGC). After the standard init sequence,
     /// any loaded DC calibration tables are applied to the LMS6002D registers
     /// and the current frequencies are re-tuned to activate the corrections.
+    ///
+    /// Returns `Error::WrongState` if the FPGA is not configured; load a
+    /// bitstream with [`ConfigSession::load_fpga`] first.
     pub fn initialize(&mut self, force: bool) -> crate::Result<()> {
-        let alt_setting = self.nios.get_alt_setting();
-        log::trace!("[*] Init - Default Alt Setting {alt_setting:?}");
-        if alt_setting != UsbAltSetting::RfLink {
-            self.nios.usb_change_setting(UsbAltSetting::RfLink)?;
-            log::trace!("[*] Init - Set Alt Setting to 0x01");
+        log::trace!(
+            target: log_target::BOARD,
+            "[*] Init - Default Alt Setting {:?}",
+            self.nios.get_alt_setting()
+        );
+        if !self.nios.usb_is_fpga_configured()? {
+            return Err(Error::WrongState {
+                required: BoardState::FpgaLoaded,
+                actual: BoardState::Uninitialized,
+            });
         }
+        self.ensure_rf_link()?;
         let cfg = self.config_gpio_read()?;
         if force || (cfg & 0x7f) == 0 {
             log::trace!(
+                target: log_target::BOARD,
                 "[*] Init - {}initializing device (GPIO={cfg:#04x})",
                 if force { "Force " } else { "" }
             );
             self.config_gpio_write(0x57)?;
+            self.module_enabled.invalidate();
             self.lms().enable_rffe(Channel::Tx, false)?;
             self.lms().enable_rffe(Channel::Rx, false)?;
             self.lms().write(0x05, 0x3e)?;
@@ -556,13 +1074,17 @@ impl RfLinkSession<'_> {
             {
                 let _actual_tx = self.si().set_sample_rate(Channel::Tx, 1_000_000)?;
                 let _actual_rx = self.si().set_sample_rate(Channel::Rx, 1_000_000)?;
-                self.dac().write(0)?;
+                let dac_trim = self.dac_trim;
+                self.dac().write(dac_trim)?;
             }
-            self.set_frequency(Channel::Tx, 2_447_000_000, TuningMode::Fpga)?;
-            self.set_frequency(Channel::Rx, 2_484_000_000, TuningMode::Fpga)?;
+            self.set_frequency_force(Channel::Tx, 2_447_000_000, TuningMode::Fpga)?;
+            self.set_frequency_force(Channel::Rx, 2_484_000_000, TuningMode::Fpga)?;
             self.set_gain_mode(Channel::Rx, GainMode::Mgc)?;
         } else {
-            log::trace!("[*] Init - Device already initialized: {cfg:#04x}");
+            log::trace!(
+                target: log_target::BOARD,
+                "[*] Init - Device already initialized: {cfg:#04x}"
+            );
         }
         self.apply_dc_cal_tables()?;
         Ok(())
@@ -632,15 +1154,67 @@ impl RfLinkSession<'_> {
     /// Enables or disables the RF front-end and USB streaming module for the
     /// given channel.
     ///
+    /// The USB side is the FX3 `RfRx`/`RfTx` vendor request, which starts or
+    /// stops the firmware's RF data path. Streams issue it themselves:
+    /// `start()` enables the module and `stop()`/`close()` disable it, so
+    /// streaming code does not need to call this directly.
+    ///
+    /// The last state set per channel is tracked, and both transfers are
+    /// skipped if the module is already in the requested state. Use
+    /// [`enable_module_forced`](RfLinkSession::enable_module_forced) to issue
+    /// them regardless, e.g. after changing the RFFE bits behind the driver's
+    /// back.
+    ///
     /// Requires the device to be initialized (see [`initialize`](RfLinkSession::initialize)).
     pub fn enable_module(&mut self, channel: Channel, enable: bool) -> crate::Result<()> {
+        self.apply_module_enable(channel, enable, false)
+    }
+
+    /// Like [`enable_module`](RfLinkSession::enable_module), but always
+    /// issues the LMS6002D and FX3 transfers.
+    pub fn enable_module_forced(&mut self, channel: Channel, enable: bool) -> crate::Result<()> {
+        self.apply_module_enable(channel, enable, true)
+    }
+
+    /// Returns the module state last set by `enable_module()`, or `None` if
+    /// it is unknown, e.g. after an FPGA load or a failed transfer.
+    pub fn module_enabled(&self, channel: Channel) -> Option<bool> {
+        self.module_enabled.get(channel)
+    }
+
+    /// Reads back whether the LMS6002D RF frontend of `channel` is powered.
+    ///
+    /// Requires the device to be initialized (see [`initialize`](RfLinkSession::initialize)).
+    pub fn rffe_enabled(&mut self, channel: Channel) -> crate::Result<bool> {
         self.require_initialized()?;
-        self.lms().enable_rffe(channel, enable)?;
-        self.nios.usb_enable_module(channel, enable)
+        self.lms().is_rffe_enabled(channel)
+    }
+
+    fn apply_module_enable(
+        &mut self,
+        channel: Channel,
+        enable: bool,
+        force: bool,
+    ) -> crate::Result<()> {
+        self.require_initialized()?;
+        let mut state = *self.module_enabled;
+        let result = state.apply(channel, enable, force, || {
+            self.lms().enable_rffe(channel, enable)?;
+            self.nios.usb_enable_module(channel, enable)
+        });
+        *self.module_enabled = state;
+        if !result? {
+            log::trace!(
+                target: log_target::BOARD,
+                "{channel:?} module already {}",
+                if enable { "enabled" } else { "disabled" }
+            );
+        }
+        Ok(())
     }
 
     /// Tears down a stream: cancels pending transfers, disables the module,
-    /// drains cancelled transfers, clears halt, and deconfigures format GPIO bits.
+    /// drains cancelled transfers, clears halt, and releases the stream format.
     pub(crate) fn close_stream<Dir: nusb::transfer::EndpointDirection>(
         &mut self,
         channel: Channel,
@@ -650,16 +1224,21 @@ impl RfLinkSession<'_> {
         self.enable_module(channel, false)?;
         pool.drain_cancelled();
         pool.clear_halt()?;
-        self.perform_format_deconfig()
+        self.release_stream_format(channel)
     }
 
     /// Queries whether the currently loaded FPGA came from flash or was loaded
     /// by the host.
+    ///
+    /// Returns `FpgaSource::Unknown` if the firmware cannot determine the
+    /// source, and `Error::Unsupported` if it reports an unrecognized value.
     pub fn get_fpga_source(&mut self) -> crate::Result<FpgaSource> {
         let result = self
             .nios
             .usb_vendor_cmd_int(crate::usb::VendorRequest::QueryFpgaSource)?;
-        FpgaSource::try_from(result as u8)
+        let raw =
+            u8::try_from(result).map_err(|_| Error::Unsupported("unknown FPGA source value"))?;
+        FpgaSource::try_from(raw)
     }
 
     /// Selects the LNA/PA band on the LMS6002D and updates the config GPIO
@@ -668,24 +1247,8 @@ impl RfLinkSession<'_> {
     /// Low band (< 1.5 GHz) uses LNA1/PA1; high band (>= 1.5 GHz) uses
     /// LNA2/PA2.
     pub fn band_select(&mut self, channel: Channel, band: Band) -> crate::Result<()> {
-        let band_value = match band {
-            Band::Low => 2,
-            Band::High => 1,
-        };
-        log::trace!("Selecting {band:?} band");
+        log::trace!(target: log_target::BOARD, "Selecting {band:?} band");
         self.lms().select_band(channel, band)?;
-        self.config_gpio_modify(|gpio| {
-            let clear_mask = if channel == Channel::Tx {
-                3 << 3
-            } else {
-                3 << 5
-            };
-            let shift = if channel == Channel::Tx {
-                band_value << 3
-            } else {
-                band_value << 5
-            };
-            (gpio & !clear_mask) | shift
-        })
+        self.config_gpio_modify(|gpio| band.apply_to_config_gpio(channel, gpio))
     }
 }
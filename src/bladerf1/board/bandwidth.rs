@@ -7,45 +7,88 @@
 
 use crate::bladerf1::board::RfLinkSession;
 use crate::bladerf1::hardware::lms6002d;
-use crate::bladerf1::hardware::lms6002d::bandwidth::LmsBandwidth;
+use crate::bladerf1::hardware::lms6002d::bandwidth::{LmsBandwidth, UINT_BANDWIDTHS};
 use crate::channel::Channel;
-use crate::error::{Error, Result};
+use crate::error::Result;
+use crate::log_target;
 use crate::range::Range;
 impl RfLinkSession<'_> {
     /// Sets the LPF bandwidth for the given channel in Hz.
     ///
-    /// Clamps the requested bandwidth to the hardware-supported range,
-    /// enables the LPF, and programs the LMS6002D. The chip selects the
-    /// closest calibrated filter response to the requested value.
+    /// Enables the LPF and programs the narrowest of the 16 LMS6002D filters
+    /// that is at least as wide as the request, e.g. 4 MHz selects 5 MHz.
+    /// Returns the bandwidth of the selected filter, which `get_bandwidth()`
+    /// reports afterwards.
     ///
-    /// Returns the actual bandwidth applied by the hardware, which may
-    /// differ from the requested value due to the discrete set of calibrated
-    /// filter settings.
+    /// Returns `Error::Argument` if `bandwidth` is outside
+    /// `get_bandwidth_range()` (1.5 MHz to 28 MHz).
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
-    pub fn set_bandwidth(&mut self, channel: Channel, mut bandwidth: u32) -> Result<u32> {
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn set_bandwidth(&mut self, channel: Channel, bandwidth: u32) -> Result<u32> {
+        let actual = Self::snap_bandwidth(bandwidth)?;
         self.require_initialized()?;
-        let bandwidth_range = lms6002d::bandwidth::get_bandwidth_range();
-        bandwidth = bandwidth.clamp(
-            bandwidth_range
-                .min()
-                .ok_or(Error::BoardState("bandwidth range has no minimum"))? as u32,
-            bandwidth_range
-                .max()
-                .ok_or(Error::BoardState("bandwidth range has no maximum"))? as u32,
+        log::trace!(
+            target: log_target::BOARD,
+            "Bandwidth {bandwidth} selects the {actual} filter"
         );
-        log::trace!("Clamped bandwidth to {bandwidth}");
-        let bw: LmsBandwidth = bandwidth.into();
         self.lms().lpf_enable(channel, true)?;
-        self.lms().set_bandwidth(channel, bw)?;
-        let actual: u32 = bw.into();
+        self.lms().set_bandwidth(channel, actual.into())?;
         Ok(actual)
     }
+    /// Returns the bandwidth of the LMS6002D filter that `set_bandwidth()`
+    /// selects for `bandwidth`, without touching hardware.
+    ///
+    /// Returns `Error::Argument` if `bandwidth` is outside
+    /// `get_bandwidth_range()`.
+    pub fn snap_bandwidth(bandwidth: u32) -> Result<u32> {
+        Self::get_bandwidth_range()
+            .check(bandwidth as f64, "bandwidth")
+            .inspect_err(|e| log::error!(target: log_target::BOARD, "{e}"))?;
+        let bw: LmsBandwidth = bandwidth.into();
+        Ok(bw.into())
+    }
+    /// Sets the sample rate of `channel` and a matching LPF bandwidth.
+    ///
+    /// With `bandwidth` set to `None` the filter is chosen with
+    /// `auto_bandwidth()`. Both values are validated before any register is
+    /// written, then the sample rate is programmed followed by the
+    /// bandwidth. Returns the bandwidth of the selected filter.
+    ///
+    /// Returns `Error::Argument` if `rate` is outside
+    /// `get_sample_rate_range()` or `bandwidth` is outside
+    /// `get_bandwidth_range()`.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn set_sample_rate_and_bandwidth(
+        &mut self,
+        channel: Channel,
+        rate: u32,
+        bandwidth: Option<u32>,
+    ) -> Result<u32> {
+        Self::check_sample_rate(rate)?;
+        let bandwidth = match bandwidth {
+            Some(bandwidth) => Self::snap_bandwidth(bandwidth)?,
+            None => Self::auto_bandwidth(rate),
+        };
+        self.set_sample_rate(channel, rate)?;
+        self.set_bandwidth(channel, bandwidth)
+    }
+    /// Returns the widest LMS6002D filter that is no wider than 80 % of
+    /// `rate`, leaving a guard band against aliasing. Rates below 1.875 Msps
+    /// get the narrowest (1.5 MHz) filter.
+    pub fn auto_bandwidth(rate: u32) -> u32 {
+        let limit = rate as u64 * 4 / 5;
+        UINT_BANDWIDTHS
+            .iter()
+            .copied()
+            .find(|&bw| bw as u64 <= limit)
+            .unwrap_or(UINT_BANDWIDTHS[UINT_BANDWIDTHS.len() - 1])
+    }
     /// Returns the current LPF bandwidth for the given channel in Hz.
     ///
     /// Reads the calibrated bandwidth value from the LMS6002D registers.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn get_bandwidth(&mut self, channel: Channel) -> Result<u32> {
         self.require_initialized()?;
         let bw: LmsBandwidth = self.lms().get_bandwidth(channel)?;
@@ -55,4 +98,11 @@ impl RfLinkSession<'_> {
     pub fn get_bandwidth_range() -> Range {
         lms6002d::bandwidth::get_bandwidth_range()
     }
+    /// Returns the LPF bandwidth range in Hz supported by this device.
+    ///
+    /// See `get_bandwidth_range()`. The LMS6002D filter bank is fixed, so
+    /// the range does not depend on any attached expansion board.
+    pub fn bandwidth_range(&self) -> Range {
+        Self::get_bandwidth_range()
+    }
 }
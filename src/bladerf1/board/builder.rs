@@ -0,0 +1,168 @@
+//! One-shot open-and-configure builder for BladeRF1.
+//!
+//! [`BladeRf1Builder`] collects a device selector and an RF operating point,
+//! checks every value against the board's ranges, and only then opens the
+//! device, runs [`initialize`](RfLinkSession::initialize) and applies the
+//! settings.
+
+use crate::bladerf1::board::{BladeRf1, RfLinkSession};
+use crate::bladerf1::hardware::lms6002d::gain::GainDb;
+use crate::bladerf1::hardware::lms6002d::loopback::Loopback;
+use crate::channel::Channel;
+use crate::error::Result;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ChannelSettings {
+    frequency: Option<u64>,
+    sample_rate: Option<u32>,
+    bandwidth: Option<u32>,
+    gain: Option<GainDb>,
+}
+
+/// Builder that opens a BladeRF1 and applies an initial configuration.
+///
+/// Created with [`BladeRf1::builder`]. Settings left unset keep the values
+/// programmed by [`initialize`](RfLinkSession::initialize).
+///
+/// ```no_run
+/// use libbladerf_rs::Channel;
+/// use libbladerf_rs::bladerf1::BladeRf1;
+///
+/// let sdr = BladeRf1::builder()
+///     .frequency(Channel::Rx, 915_000_000)
+///     .sample_rate(Channel::Rx, 2_000_000)
+///     .bandwidth(Channel::Rx, 1_500_000)
+///     .gain(Channel::Rx, 30.into())
+///     .open()?;
+/// # Ok::<(), libbladerf_rs::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BladeRf1Builder {
+    serial: Option<String>,
+    channels: [ChannelSettings; 2],
+    loopback: Option<Loopback>,
+}
+
+impl BladeRf1 {
+    /// Returns a [`BladeRf1Builder`] for opening and configuring a device.
+    pub fn builder() -> BladeRf1Builder {
+        BladeRf1Builder::default()
+    }
+}
+
+impl BladeRf1Builder {
+    /// Opens the device with this serial number instead of the first one found.
+    pub fn serial(mut self, serial: impl Into<String>) -> Self {
+        self.serial = Some(serial.into());
+        self
+    }
+
+    /// Sets the RF frequency of `channel` in Hz.
+    pub fn frequency(mut self, channel: Channel, frequency: u64) -> Self {
+        self.channels[channel as usize].frequency = Some(frequency);
+        self
+    }
+
+    /// Sets the sample rate of `channel` in samples per second.
+    pub fn sample_rate(mut self, channel: Channel, rate: u32) -> Self {
+        self.channels[channel as usize].sample_rate = Some(rate);
+        self
+    }
+
+    /// Sets the LPF bandwidth of `channel` in Hz.
+    pub fn bandwidth(mut self, channel: Channel, bandwidth: u32) -> Self {
+        self.channels[channel as usize].bandwidth = Some(bandwidth);
+        self
+    }
+
+    /// Sets the aggregate gain of `channel`.
+    pub fn gain(mut self, channel: Channel, gain: GainDb) -> Self {
+        self.channels[channel as usize].gain = Some(gain);
+        self
+    }
+
+    /// Sets the loopback mode.
+    pub fn loopback(mut self, loopback: Loopback) -> Self {
+        self.loopback = Some(loopback);
+        self
+    }
+
+    /// Checks every configured value against the board's ranges without
+    /// touching hardware.
+    ///
+    /// The frequency range is the LMS6002D's native range, since the builder
+    /// does not attach expansion boards. Bandwidths are snapped to the nearest
+    /// LPF setting when applied, so only their bounds are checked.
+    ///
+    /// Returns `Error::Argument` for the first out-of-range value.
+    pub fn validate(&self) -> Result<()> {
+        let frequency_range = RfLinkSession::frequency_range(false);
+        for channel in [Channel::Rx, Channel::Tx] {
+            let ch = &self.channels[channel as usize];
+            if let Some(frequency) = ch.frequency {
+                frequency_range.check(frequency as f64, &format!("{channel:?} frequency"))?;
+            }
+            if let Some(rate) = ch.sample_rate {
+                let range = RfLinkSession::get_sample_rate_range();
+                range.check(rate as f64, &format!("{channel:?} sample rate"))?;
+            }
+            if let Some(bandwidth) = ch.bandwidth {
+                let range = RfLinkSession::get_bandwidth_range();
+                range.check(bandwidth as f64, &format!("{channel:?} bandwidth"))?;
+            }
+            if let Some(gain) = ch.gain {
+                let range = RfLinkSession::get_gain_range(channel);
+                range.check(gain.db() as f64, &format!("{channel:?} gain"))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates the settings, opens the device, initializes it and applies
+    /// the settings.
+    ///
+    /// Per channel the sample rate is programmed first, then the bandwidth,
+    /// the frequency (using the current tuning mode) and the gain, as in
+    /// [`apply_config`](RfLinkSession::apply_config). The loopback mode is
+    /// applied last.
+    ///
+    /// Returns `Error::Argument` before any USB access if a value is out of
+    /// range, and `Error::NotFound` if no matching device is connected.
+    #[cfg(not(target_os = "android"))]
+    pub fn open(self) -> Result<BladeRf1> {
+        self.validate()?;
+        let mut sdr = match &self.serial {
+            Some(serial) => BladeRf1::from_serial(serial)?,
+            None => BladeRf1::from_first()?,
+        };
+        {
+            let mut rf = sdr.rf_link_session()?;
+            rf.initialize(false)?;
+            self.apply(&mut rf)?;
+        }
+        Ok(sdr)
+    }
+
+    fn apply(&self, rf: &mut RfLinkSession<'_>) -> Result<()> {
+        for channel in [Channel::Rx, Channel::Tx] {
+            let ch = &self.channels[channel as usize];
+            if let Some(rate) = ch.sample_rate {
+                rf.set_sample_rate(channel, rate)?;
+            }
+            if let Some(bandwidth) = ch.bandwidth {
+                rf.set_bandwidth(channel, bandwidth)?;
+            }
+            if let Some(frequency) = ch.frequency {
+                let mode = rf.get_tuning_mode();
+                rf.set_frequency(channel, frequency, mode)?;
+            }
+            if let Some(gain) = ch.gain {
+                rf.set_gain(channel, gain)?;
+            }
+        }
+        if let Some(loopback) = self.loopback {
+            rf.set_loopback(loopback)?;
+        }
+        Ok(())
+    }
+}
@@ -10,7 +10,7 @@ use crate::bladerf1::board::fpga::{BLADERF_FLASH_FPGA_SIZE_40KLE, BLADERF_FLASH_
 use crate::bladerf1::hardware::spi_flash::{BLADERF_FLASH_ADDR_CAL, BLADERF_FLASH_PAGE_SIZE};
 use crate::error::Error;
 use crate::error::Result;
-use crate::flash::{FpgaSize, binkv_decode_field, make_cal_region};
+use crate::flash::{FpgaSize, binkv_decode_field, decode_dac_trim, make_cal_region};
 
 impl FlashSession<'_> {
     /// Reads the factory DAC trim value from the flash calibration region.
@@ -20,10 +20,7 @@ impl FlashSession<'_> {
     pub fn read_flash_dac_trim(&mut self) -> Result<u16> {
         let mut buf = [0u8; BLADERF_FLASH_PAGE_SIZE];
         self.read_cal_cache(&mut buf)?;
-        let dac_str = binkv_decode_field(&buf, "DAC")?;
-        dac_str
-            .parse::<u16>()
-            .map_err(|_| Error::BoardState("failed to parse DAC trim from flash"))
+        decode_dac_trim(&buf)
     }
 
     /// Reads the FPGA package size indicator from the flash calibration region.
@@ -0,0 +1,82 @@
+//! FPGA capability gating for BladeRF1.
+//!
+//! Several features depend on logic that was added to the FPGA image over
+//! time. [`Capability`] names those features and maps each one to the first
+//! FPGA version that implements it, mirroring the capability table in
+//! libbladeRF's `bladerf1/capabilities.c`.
+
+use crate::bladerf1::board::RfLinkSession;
+use crate::error::{Error, Result};
+use crate::version::SemanticVersion;
+
+/// A feature whose availability depends on the loaded FPGA version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// Updated VCTCXO trim DAC address (FPGA v0.0.4).
+    UpdatedDacAddr,
+    /// XB-200 transverter support (FPGA v0.0.5).
+    Xb200,
+    /// Sample timestamps (FPGA v0.1.0).
+    Timestamps,
+    /// FPGA-based tuning and scheduled retunes (FPGA v0.2.0).
+    FpgaTuning,
+    /// VCTCXO trim DAC read-back (FPGA v0.3.2).
+    VctcxoTrimDacRead,
+    /// Atomic NINT/NFRAC register writes (FPGA v0.4.0).
+    AtomicNintNfracWrite,
+    /// Masked expansion GPIO writes (FPGA v0.4.1).
+    MaskedXbioWrite,
+    /// VCTCXO taming via 1PPS or 10 MHz reference (FPGA v0.5.0).
+    VctcxoTamingMode,
+    /// Synchronization triggers (FPGA v0.6.0).
+    TrxSyncTrig,
+    /// AGC DC-offset lookup table (FPGA v0.7.0).
+    AgcDcLut,
+}
+
+impl Capability {
+    /// Returns the first FPGA version that implements this capability.
+    pub const fn min_fpga_version(self) -> SemanticVersion {
+        match self {
+            Capability::UpdatedDacAddr => SemanticVersion::new(0, 0, 4),
+            Capability::Xb200 => SemanticVersion::new(0, 0, 5),
+            Capability::Timestamps => SemanticVersion::new(0, 1, 0),
+            Capability::FpgaTuning => SemanticVersion::new(0, 2, 0),
+            Capability::VctcxoTrimDacRead => SemanticVersion::new(0, 3, 2),
+            Capability::AtomicNintNfracWrite => SemanticVersion::new(0, 4, 0),
+            Capability::MaskedXbioWrite => SemanticVersion::new(0, 4, 1),
+            Capability::VctcxoTamingMode => SemanticVersion::new(0, 5, 0),
+            Capability::TrxSyncTrig => SemanticVersion::new(0, 6, 0),
+            Capability::AgcDcLut => SemanticVersion::new(0, 7, 0),
+        }
+    }
+}
+
+/// Returns `true` if an FPGA of version `fpga` provides `cap`.
+pub fn have_cap(fpga: SemanticVersion, cap: Capability) -> bool {
+    fpga >= cap.min_fpga_version()
+}
+
+impl RfLinkSession<'_> {
+    /// Reads the FPGA version from the NIOS as a [`SemanticVersion`].
+    pub fn fpga_version_struct(&mut self) -> Result<SemanticVersion> {
+        self.nios.nios_get_fpga_version()
+    }
+
+    /// Returns `true` if the loaded FPGA provides `cap`.
+    pub fn fpga_has_cap(&mut self, cap: Capability) -> Result<bool> {
+        Ok(have_cap(self.fpga_version_struct()?, cap))
+    }
+
+    /// Returns `Error::NoApi` if the loaded FPGA does not provide `cap`.
+    pub(crate) fn require_fpga_cap(&mut self, cap: Capability) -> Result<()> {
+        let device = self.fpga_version_struct()?;
+        if have_cap(device, cap) {
+            return Ok(());
+        }
+        Err(Error::NoApi {
+            device,
+            min: cap.min_fpga_version(),
+        })
+    }
+}
@@ -0,0 +1,137 @@
+//! One-shot RX capture for BladeRF1.
+//!
+//! Wraps the RX stream lifecycle (build, start, read, close) for quick
+//! experiments that only need a fixed number of samples in memory, and
+//! builds a signal peak measurement for host-side gain loops on top of it.
+
+use crate::bladerf1::board::RfLinkSession;
+use crate::bladerf1::board::stream::{RxStream, SampleFormat};
+use crate::channel::Channel;
+use crate::error::{Error, Result};
+use crate::log_target;
+use num_complex::Complex;
+use std::time::{Duration, Instant};
+
+/// Samples collected by `capture()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Capture {
+    /// Received samples, scaled to `[-1.0, 1.0)`.
+    pub samples: Vec<Complex<f32>>,
+    /// `true` if the timeout expired before all requested samples arrived,
+    /// in which case `samples` holds what was received until then.
+    pub timed_out: bool,
+}
+
+/// Number of samples captured by `measure_signal_peak()`.
+pub const SIGNAL_PEAK_SAMPLES: usize = 4096;
+
+const SIGNAL_PEAK_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Largest I and Q magnitudes seen in a block of samples, as a fraction of
+/// ADC full scale.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SignalPeak {
+    /// Peak `|I|`, from 0.0 to 1.0.
+    pub i: f32,
+    /// Peak `|Q|`, from 0.0 to 1.0.
+    pub q: f32,
+}
+
+impl SignalPeak {
+    /// Returns the peak `|I|` and `|Q|` of `samples`, scaled as by
+    /// `SampleFormat::to_complex_f32`. An empty slice measures zero.
+    pub fn measure(samples: &[Complex<f32>]) -> Self {
+        samples.iter().fold(Self::default(), |peak, s| Self {
+            i: peak.i.max(s.re.abs()),
+            q: peak.q.max(s.im.abs()),
+        })
+    }
+
+    /// Returns the larger of the I and Q peaks in dB relative to full scale.
+    pub fn dbfs(&self) -> f32 {
+        20.0 * self.i.max(self.q).log10()
+    }
+}
+
+impl RfLinkSession<'_> {
+    /// Captures `SIGNAL_PEAK_SAMPLES` RX samples with `capture()` and returns
+    /// their peak I and Q magnitudes, e.g. to drive a manual gain loop.
+    ///
+    /// The LMS6002D peak detector has no digital readout; its output can
+    /// only be observed through the RX ADC. The peak is therefore measured
+    /// on the received samples, at the ADC after all RX gain stages.
+    ///
+    /// Returns `Error::Unsupported` for `Channel::Tx`, `Error::Timeout` if no
+    /// samples arrive within one second, and `Error::WrongState` if the
+    /// board has not been initialized.
+    pub fn measure_signal_peak(&mut self, channel: Channel) -> Result<SignalPeak> {
+        if channel.is_tx() {
+            return Err(Error::Unsupported("signal peak measurement on TX"));
+        }
+        let capture = self.capture(SIGNAL_PEAK_SAMPLES, SIGNAL_PEAK_TIMEOUT)?;
+        if capture.samples.is_empty() {
+            return Err(Error::Timeout);
+        }
+        Ok(SignalPeak::measure(&capture.samples))
+    }
+
+    /// Receives `num_samples` RX samples with a temporary `Sc16Q11` stream.
+    ///
+    /// Buffers are read and converted until `num_samples` have been
+    /// collected; a final partial buffer is truncated. `timeout` bounds the
+    /// whole capture, not each buffer. If it expires the samples received so
+    /// far are returned with `Capture::timed_out` set. The stream is closed
+    /// and the RX module disabled before returning, also on error.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn capture(&mut self, num_samples: usize, timeout: Duration) -> Result<Capture> {
+        let mut rx = RxStream::builder(self)
+            .format(SampleFormat::Sc16Q11)
+            .build()?;
+        let captured = self.capture_from(&mut rx, num_samples, timeout);
+        let closed = rx.close(self);
+        let capture = captured?;
+        closed?;
+        Ok(capture)
+    }
+
+    fn capture_from(
+        &mut self,
+        rx: &mut RxStream,
+        num_samples: usize,
+        timeout: Duration,
+    ) -> Result<Capture> {
+        let deadline = Instant::now() + timeout;
+        let speed = self.nios.transport().speed();
+        let per_buffer = SampleFormat::Sc16Q11.samples_per_buffer(rx.buffer_size()?, speed)?;
+        let mut scratch = vec![Complex::default(); per_buffer];
+        let mut samples = Vec::with_capacity(num_samples);
+
+        rx.start(self)?;
+        while samples.len() < num_samples {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match rx.read_complex_f32(&mut scratch, Some(remaining)) {
+                Ok(n) => {
+                    let take = n.min(num_samples - samples.len());
+                    samples.extend_from_slice(&scratch[..take]);
+                }
+                Err(Error::Timeout) => {
+                    log::warn!(
+                        target: log_target::STREAM,
+                        "capture timed out after {} of {num_samples} samples",
+                        samples.len()
+                    );
+                    return Ok(Capture {
+                        samples,
+                        timed_out: true,
+                    });
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(Capture {
+            samples,
+            timed_out: false,
+        })
+    }
+}
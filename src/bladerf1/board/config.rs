@@ -0,0 +1,98 @@
+//! Device configuration snapshots for BladeRF1.
+//!
+//! Captures the operating point of both channels (frequency, bandwidth,
+//! sample rate and per-stage gains) together with the loopback mode, so that
+//! it can be saved, serialized and re-applied later. All values are read back
+//! from the hardware rather than from cached state.
+
+use crate::bladerf1::board::RfLinkSession;
+use crate::bladerf1::hardware::lms6002d::gain::{GainDb, GainStage};
+use crate::bladerf1::hardware::lms6002d::loopback::Loopback;
+use crate::bladerf1::hardware::si5338::RationalRate;
+use crate::channel::Channel;
+use crate::error::Result;
+
+/// Operating point of a single channel.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ChannelConfig {
+    /// RF frequency in Hz.
+    pub frequency: u64,
+    /// LPF bandwidth in Hz.
+    pub bandwidth: u32,
+    /// Exact sample rate as programmed into the Si5338.
+    pub sample_rate: RationalRate,
+    /// Gain of each amplifier stage, in the order of `get_gain_stages()`.
+    pub gains: Vec<(GainStage, GainDb)>,
+}
+
+/// Snapshot of the RF configuration of both channels and the loopback mode.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DeviceConfig {
+    /// RX channel configuration.
+    pub rx: ChannelConfig,
+    /// TX channel configuration.
+    pub tx: ChannelConfig,
+    /// Active loopback mode.
+    pub loopback: Loopback,
+}
+
+impl DeviceConfig {
+    /// Returns the configuration of the given channel.
+    pub fn channel(&self, channel: Channel) -> &ChannelConfig {
+        match channel {
+            Channel::Rx => &self.rx,
+            Channel::Tx => &self.tx,
+        }
+    }
+}
+
+impl RfLinkSession<'_> {
+    /// Reads the current configuration of both channels from the hardware.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn capture_config(&mut self) -> Result<DeviceConfig> {
+        self.require_initialized()?;
+        Ok(DeviceConfig {
+            rx: self.capture_channel_config(Channel::Rx)?,
+            tx: self.capture_channel_config(Channel::Tx)?,
+            loopback: self.get_loopback()?,
+        })
+    }
+
+    fn capture_channel_config(&mut self, channel: Channel) -> Result<ChannelConfig> {
+        let gains = Self::get_gain_stages(channel)
+            .iter()
+            .map(|&stage| Ok((stage, self.get_gain_stage(stage)?)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(ChannelConfig {
+            frequency: self.get_frequency(channel)?,
+            bandwidth: self.get_bandwidth(channel)?,
+            sample_rate: self.get_rational_sample_rate(channel)?,
+            gains,
+        })
+    }
+
+    /// Applies a configuration previously obtained from `capture_config()`.
+    ///
+    /// For each channel the sample rate is programmed first, then the
+    /// bandwidth, then the frequency (using the current tuning mode) and
+    /// finally the stage gains. The loopback mode is applied last, since
+    /// retuning reselects the LNA and PA paths it depends on.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn apply_config(&mut self, cfg: &DeviceConfig) -> Result<()> {
+        self.require_initialized()?;
+        for channel in [Channel::Rx, Channel::Tx] {
+            let ch = cfg.channel(channel);
+            let mut rate = ch.sample_rate;
+            self.set_rational_sample_rate(channel, &mut rate)?;
+            self.set_bandwidth(channel, ch.bandwidth)?;
+            let mode = self.get_tuning_mode();
+            self.set_frequency(channel, ch.frequency, mode)?;
+            for &(stage, gain) in &ch.gains {
+                self.set_gain_stage(stage, gain)?;
+            }
+        }
+        self.set_loopback(cfg.loopback)
+    }
+}
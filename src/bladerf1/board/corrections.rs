@@ -14,7 +14,9 @@ use crate::bladerf1::board::TuningMode;
 use crate::bladerf1::hardware::lms6002d;
 use crate::bladerf1::hardware::lms6002d::dc_calibration::{DcCalModule, DcCals};
 use crate::channel::Channel;
-use crate::error::Result;
+use crate::error::{Error, Result};
+use crate::log_target;
+use std::ops::RangeInclusive;
 /// Converts a duration in milliseconds to a sample count at the given sample rate.
 #[macro_export]
 macro_rules! ms_to_samples {
@@ -34,13 +36,39 @@ pub enum Correction {
     /// Gain imbalance correction between I and Q channels in ppm.
     Gain,
 }
+
+/// Minimum LMS6002D DC offset correction value.
+pub const BLADERF_CORR_DCOFF_MIN: i16 = -2048;
+/// Maximum LMS6002D DC offset correction value.
+pub const BLADERF_CORR_DCOFF_MAX: i16 = 2048;
+/// Minimum FPGA phase correction value (about -10 degrees).
+pub const BLADERF_CORR_PHASE_MIN: i16 = -4096;
+/// Maximum FPGA phase correction value (about +10 degrees).
+pub const BLADERF_CORR_PHASE_MAX: i16 = 4096;
+/// Minimum FPGA gain correction value (gain of 0.0).
+pub const BLADERF_CORR_GAIN_MIN: i16 = -4096;
+/// Maximum FPGA gain correction value (gain of 2.0).
+pub const BLADERF_CORR_GAIN_MAX: i16 = 4096;
+
+impl Correction {
+    /// Returns the range of values accepted by `set_correction()`.
+    pub fn range(&self) -> RangeInclusive<i16> {
+        match self {
+            Correction::DcOffI | Correction::DcOffQ => {
+                BLADERF_CORR_DCOFF_MIN..=BLADERF_CORR_DCOFF_MAX
+            }
+            Correction::Phase => BLADERF_CORR_PHASE_MIN..=BLADERF_CORR_PHASE_MAX,
+            Correction::Gain => BLADERF_CORR_GAIN_MIN..=BLADERF_CORR_GAIN_MAX,
+        }
+    }
+}
 impl RfLinkSession<'_> {
     /// Returns the current value of the requested IQ correction parameter.
     ///
     /// DC offset values are read from LMS6002D registers. IQ phase and gain
     /// corrections are read from the FPGA's internal correction registers.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn get_correction(&mut self, ch: Channel, corr: &Correction) -> Result<i16> {
         self.require_initialized()?;
         match corr {
@@ -59,9 +87,19 @@ impl RfLinkSession<'_> {
     /// corrections are written to the FPGA's internal correction registers
     /// (gain is offset by 4096 internally).
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::Argument` if `value` lies outside `corr.range()`.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn set_correction(&mut self, ch: Channel, corr: &Correction, value: i16) -> Result<()> {
         self.require_initialized()?;
+        let range = corr.range();
+        if !range.contains(&value) {
+            return Err(Error::Argument(format!(
+                "{corr:?} correction {value} outside {}..={}",
+                range.start(),
+                range.end()
+            )));
+        }
         match corr {
             Correction::Phase => self.nios.nios_set_iq_phase_correction(ch, value),
             Correction::Gain => self.nios.nios_set_iq_gain_correction(ch, value + 4_096),
@@ -71,28 +109,171 @@ impl RfLinkSession<'_> {
     }
     /// Runs DC calibration on the TX LPF path of the LMS6002D.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn cal_tx_lpf(&mut self) -> Result<()> {
         self.require_initialized()?;
         self.calibrate_dc(DcCalModule::TxLpf)
     }
     /// Runs DC calibration on the specified LMS6002D module.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn calibrate_dc(&mut self, module: DcCalModule) -> Result<()> {
         self.require_initialized()?;
         self.lms().calibrate_dc(module)
     }
+    /// DC calibration steps run by `calibrate_dc_all_rx()`, in order.
+    pub const DC_CAL_RX_MODULES: [DcCalModule; 3] = [
+        DcCalModule::LpfTuning,
+        DcCalModule::RxLpf,
+        DcCalModule::RxVga2,
+    ];
+    /// DC calibration steps run by `calibrate_dc_all_tx()`, in order.
+    pub const DC_CAL_TX_MODULES: [DcCalModule; 2] = [DcCalModule::LpfTuning, DcCalModule::TxLpf];
+    /// Runs the standard RX DC calibration sequence (LPF tuning, RX LPF,
+    /// RX VGA2) and returns the resulting calibration values.
+    ///
+    /// The RX stage gains are captured first and restored afterwards, also
+    /// when a step fails. A failed step takes precedence over a failed
+    /// restore, which is then only logged.
+    ///
+    /// Returns `Error::DcCalibration` naming the module of the first step
+    /// that failed, with the underlying error as its source.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn calibrate_dc_all_rx(&mut self) -> Result<DcCals> {
+        self.calibrate_dc_sequence(Channel::Rx, &Self::DC_CAL_RX_MODULES)
+    }
+    /// Runs the standard TX DC calibration sequence (LPF tuning, TX LPF) and
+    /// returns the resulting calibration values.
+    ///
+    /// The TX stage gains are captured first and restored afterwards, also
+    /// when a step fails. A failed step takes precedence over a failed
+    /// restore, which is then only logged.
+    ///
+    /// Returns `Error::DcCalibration` naming the module of the first step
+    /// that failed, with the underlying error as its source.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn calibrate_dc_all_tx(&mut self) -> Result<DcCals> {
+        self.calibrate_dc_sequence(Channel::Tx, &Self::DC_CAL_TX_MODULES)
+    }
+    fn calibrate_dc_sequence(
+        &mut self,
+        channel: Channel,
+        modules: &[DcCalModule],
+    ) -> Result<DcCals> {
+        self.require_initialized()?;
+        let gains = Self::get_gain_stages(channel)
+            .iter()
+            .map(|&stage| Ok((stage, self.get_gain_stage(stage)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let result = modules.iter().try_for_each(|&module| {
+            log::debug!(target: log_target::BOARD, "Calibrating: {module:?}");
+            self.lms()
+                .calibrate_dc(module)
+                .map_err(|source| Error::DcCalibration {
+                    module,
+                    source: Box::new(source),
+                })
+        });
+        let restored = gains
+            .iter()
+            .try_for_each(|&(stage, gain)| self.set_gain_stage(stage, gain));
+        if let (Err(_), Err(e)) = (&result, &restored) {
+            log::warn!(
+                target: log_target::BOARD,
+                "Failed to restore {channel:?} gains after DC calibration: {e}"
+            );
+        }
+        result?;
+        restored?;
+        self.get_dc_cals()
+    }
+    /// Runs the LMS6002D LPF tuning calibration and returns the resulting DC value.
+    ///
+    /// The calibrated value sets the LPF corner frequency and is written to the
+    /// `DCO_DACCAL` field of both the TX (0x35) and RX (0x55) LPF registers.
+    /// This is separate from the I/Q DC offset calibrations.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn tune_lpf(&mut self) -> Result<u8> {
+        self.calibrate_dc(DcCalModule::LpfTuning)?;
+        self.lms().get_lpf_tuning()
+    }
+    /// Returns the LPF tuning value currently in the `DCO_DACCAL` field of the
+    /// RX LPF register (0x55).
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn get_lpf_tuning(&mut self) -> Result<u8> {
+        self.require_initialized()?;
+        self.lms().get_lpf_tuning()
+    }
+    /// Writes an LPF tuning value, e.g. one saved from [`tune_lpf`](Self::tune_lpf),
+    /// to the TX (0x35) and RX (0x55) LPF registers.
+    ///
+    /// Returns `Error::Argument` if `value` is outside `0..=0x3f`, or
+    /// `Error::WrongState` if the board has not been initialized.
+    pub fn set_lpf_tuning(&mut self, value: u8) -> Result<()> {
+        self.require_initialized()?;
+        if value > 0x3f {
+            return Err(Error::Argument(format!(
+                "LPF tuning value {value:#x} is outside 0..=0x3f"
+            )));
+        }
+        self.lms().set_lpf_tuning(value)
+    }
+    /// Runs DC calibration on the specified LMS6002D module with the RX and TX
+    /// RF frontends powered down.
+    ///
+    /// Disabling the frontends keeps external signals and PA leakage out of the
+    /// measurement loop, which can make convergence more repeatable. The tradeoff
+    /// is that the resulting offsets are measured without the frontend's own
+    /// contribution, and any running stream is interrupted for the duration.
+    /// The previous RFFE state of each channel is restored afterwards, including
+    /// when calibration fails. A calibration error takes precedence over a
+    /// failed restore, which is then only logged.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn calibrate_dc_rffe_disabled(&mut self, module: DcCalModule) -> Result<()> {
+        self.require_initialized()?;
+        let rx_enabled = self.lms().is_rffe_enabled(Channel::Rx)?;
+        let tx_enabled = self.lms().is_rffe_enabled(Channel::Tx)?;
+        self.lms().enable_rffe(Channel::Rx, false)?;
+        let mut result = self.lms().enable_rffe(Channel::Tx, false);
+        if result.is_ok() {
+            result = self.lms().calibrate_dc(module);
+        }
+        let rx_restored = self.lms().enable_rffe(Channel::Rx, rx_enabled);
+        let tx_restored = self.lms().enable_rffe(Channel::Tx, tx_enabled);
+        if rx_restored.is_err() || tx_restored.is_err() {
+            self.module_enabled.invalidate();
+        }
+        if result.is_err() {
+            if let Err(e) = &rx_restored {
+                log::warn!(
+                    target: log_target::BOARD,
+                    "Failed to restore RX RFFE after DC calibration: {e}"
+                );
+            }
+            if let Err(e) = &tx_restored {
+                log::warn!(
+                    target: log_target::BOARD,
+                    "Failed to restore TX RFFE after DC calibration: {e}"
+                );
+            }
+        }
+        result.and(rx_restored).and(tx_restored)
+    }
     /// Applies a full set of DC calibration parameters to the LMS6002D.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn set_dc_cals(&mut self, dc_cals: DcCals) -> Result<()> {
         self.require_initialized()?;
         self.lms().set_dc_cals(dc_cals)
     }
     /// Returns the current DC calibration parameters from the LMS6002D.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn get_dc_cals(&mut self) -> Result<DcCals> {
         self.require_initialized()?;
         self.lms().get_dc_cals()
@@ -113,7 +294,7 @@ impl RfLinkSession<'_> {
     /// they can be restored via `set_rx_cal_backup()` after the calibration
     /// sweep modifies these parameters.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn get_rx_cal_backup(&mut self) -> Result<lms6002d::dc_calibration::RxCalBackup> {
         self.require_initialized()?;
         Ok(lms6002d::dc_calibration::RxCalBackup::new(
@@ -127,7 +308,7 @@ impl RfLinkSession<'_> {
     /// Restores the rational sample rate, bandwidth, and TX frequency to their
     /// pre-calibration values. Use after completing an RX DC calibration sweep.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn set_rx_cal_backup(
         &mut self,
         rx_cal_backup: &mut lms6002d::dc_calibration::RxCalBackup,
@@ -143,7 +324,7 @@ impl RfLinkSession<'_> {
     /// 1 MHz separation if the frequency difference is too small. Advances the
     /// retune timestamp by `RX_CAL_TS_INC`.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn rx_cal_update_frequency(
         &mut self,
         cal: &mut lms6002d::dc_calibration::RxCal,
@@ -151,8 +332,8 @@ impl RfLinkSession<'_> {
     ) -> Result<()> {
         self.require_initialized()?;
         let f_diff: u64 = cal.tx_frequency().abs_diff(rx_freq);
-        log::debug!("Set F_RX = {rx_freq}");
-        log::debug!("F_diff(RX, TX) = {f_diff}");
+        log::debug!(target: log_target::BOARD, "Set F_RX = {rx_freq}");
+        log::debug!(target: log_target::BOARD, "F_diff(RX, TX) = {f_diff}");
         if f_diff < 1_000_000 {
             let new_tx_freq =
                 if rx_freq >= (lms6002d::frequency::get_frequency_min() + 1_000_000) as u64 {
@@ -162,7 +343,7 @@ impl RfLinkSession<'_> {
                 };
             cal.set_tx_frequency(new_tx_freq);
             self.set_frequency(Channel::Tx, new_tx_freq, TuningMode::Fpga)?;
-            log::debug!("Adjusted TX frequency: {new_tx_freq}");
+            log::debug!(target: log_target::BOARD, "Adjusted TX frequency: {new_tx_freq}");
         }
         self.set_frequency(Channel::Rx, rx_freq, TuningMode::Fpga)?;
         cal.set_timestamp(cal.timestamp() + Self::RX_CAL_TS_INC);
@@ -170,7 +351,7 @@ impl RfLinkSession<'_> {
     }
     /// Sets both I and Q DC offset corrections for the RX channel in one call.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn set_rx_dc_corr(&mut self, i: i16, q: i16) -> Result<()> {
         self.require_initialized()?;
         self.set_correction(Channel::Rx, &Correction::DcOffI, i)?;
@@ -8,6 +8,9 @@
 use crate::bladerf1::board::RfLinkSession;
 use crate::error::Result;
 
+/// DAC trim used when no factory calibration is available (mid-scale).
+pub(crate) const DEFAULT_DAC_TRIM: u16 = 0x8000;
+
 impl RfLinkSession<'_> {
     /// Writes a 16-bit trim value to the DAC161S055 to adjust the VCTCXO frequency.
     ///
@@ -15,7 +18,7 @@ impl RfLinkSession<'_> {
     /// fine frequency calibration. The value 0x0000 produces the minimum output
     /// voltage and 0xFFFF produces the maximum.
     ///
-    /// Returns `Error::BoardState` if the board is not initialized.
+    /// Returns `Error::WrongState` if the board is not initialized.
     pub fn set_dac_trim(&mut self, value: u16) -> Result<()> {
         self.require_initialized()?;
         self.dac().write(value)
@@ -26,7 +29,7 @@ impl RfLinkSession<'_> {
     /// Reads the DAC161S055 output register to determine the active VCTCXO
     /// tuning setting.
     ///
-    /// Returns `Error::BoardState` if the board is not initialized.
+    /// Returns `Error::WrongState` if the board is not initialized.
     pub fn get_dac_trim(&mut self) -> Result<u16> {
         self.require_initialized()?;
         self.dac().read()
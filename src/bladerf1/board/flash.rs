@@ -11,6 +11,7 @@ use crate::bladerf1::hardware::spi_flash::{
     BLADERF_FLASH_ERASE_BLOCK_SIZE, BLADERF_FLASH_PAGE_SIZE,
 };
 use crate::error::{Error, Result};
+use crate::log_target;
 
 const MAX_RETRIES: u8 = 3;
 
@@ -70,6 +71,7 @@ impl FlashSession<'_> {
                     Ok(()) => break,
                     Err(e) if attempt < MAX_RETRIES => {
                         log::warn!(
+                            target: log_target::BOARD,
                             "Verification failed at sector {sector}, retry {}/{}: {e:#}",
                             attempt + 1,
                             MAX_RETRIES,
@@ -13,7 +13,8 @@ use crate::bladerf1::hardware::spi_flash::{
     BLADERF_FLASH_ADDR_FPGA, BLADERF_FLASH_ERASE_BLOCK_SIZE, BLADERF_FLASH_PAGE_SIZE,
 };
 use crate::error::{Error, Result};
-use crate::flash::{binkv_encode_field, pad_to_page};
+use crate::flash::{FpgaImageInfo, decode_fpga_meta_page, make_fpga_meta_page, pad_to_page};
+use crate::log_target;
 use crate::usb::{
     BladeRf1UsbInterfaceCommands, CONTROL_ENDPOINT_OUT, UsbInterfaceCommands, VendorRequest,
 };
@@ -68,14 +69,17 @@ pub struct FwLogEntry {
 }
 
 impl FwLogEntry {
+    /// Returns the 16-bit data value logged by the firmware.
     pub fn data(&self) -> u16 {
         self.data
     }
 
+    /// Returns the source line that logged the entry.
     pub fn line(&self) -> u16 {
         self.line
     }
 
+    /// Returns the source file that logged the entry.
     pub fn file(&self) -> FwLogFile {
         self.file
     }
@@ -112,9 +116,10 @@ impl FwLogFile {
     }
 }
 
-impl FwLogEntry {
-    /// Decodes a 32-bit log entry word into structured fields.
-    fn from_u32(entry: u32) -> Self {
+impl From<u32> for FwLogEntry {
+    /// Decodes a 32-bit log entry word: data in bits 0-15, line in bits
+    /// 16-26 and file ID in bits 27-31.
+    fn from(entry: u32) -> Self {
         Self {
             data: (entry & 0xFFFF) as u16,
             line: ((entry >> 16) & 0x7FF) as u16,
@@ -155,10 +160,10 @@ impl RfLinkSession<'_> {
                 break;
             }
             if raw == LOG_ERR {
-                log::warn!("firmware log read error");
+                log::warn!(target: log_target::BOARD, "firmware log read error");
                 break;
             }
-            entries.push(FwLogEntry::from_u32(raw));
+            entries.push(FwLogEntry::from(raw));
         }
         Ok(entries)
     }
@@ -167,9 +172,11 @@ impl RfLinkSession<'_> {
 impl ConfigSession<'_> {
     /// Loads an FPGA bitstream directly from the host over USB.
     ///
-    /// Begins FPGA programming via USB, sends the raw bitstream over the
-    /// bulk-out endpoint, and polls the configuration status until the FPGA
-    /// reports success or a timeout is reached. Requires the device to be in
+    /// Begins FPGA programming via USB, streams the raw bitstream over the
+    /// bulk-out endpoint in 64 KiB transfers, and polls the configuration
+    /// status until the FPGA reports success or a timeout is reached. The FX3
+    /// protocol has no end-of-programming request; completion is detected
+    /// solely by polling `QueryFpgaStatus`. Requires the device to be in
     /// config mode (USB alternate setting switched away from RfLink).
     ///
     /// Returns `Error::Argument` if the bitstream size is not 40KLE or
@@ -234,9 +241,7 @@ impl FlashSession<'_> {
 
         let padded = pad_to_page(bitstream);
 
-        let mut meta = [0xFFu8; BLADERF_FLASH_PAGE_SIZE];
-        let len_str = bitstream.len().to_string();
-        binkv_encode_field(&mut meta, 0, "LEN", &len_str)?;
+        let meta = make_fpga_meta_page(bitstream.len())?;
 
         let mut all_data = Vec::with_capacity(BLADERF_FLASH_PAGE_SIZE + padded.len());
         all_data.extend_from_slice(&meta);
@@ -247,6 +252,23 @@ impl FlashSession<'_> {
         Ok(())
     }
 
+    /// Reads the metadata of the FPGA image stored for autoloading.
+    ///
+    /// Returns `None` if the FPGA region is blank, so tooling can decide
+    /// whether to load an FPGA from the host instead. The reported length is
+    /// not checked against the bitstream that follows it.
+    ///
+    /// Returns `Error::BoardState` if the metadata page is written but
+    /// cannot be decoded.
+    pub fn get_fpga_autoload_info(&mut self) -> Result<Option<FpgaImageInfo>> {
+        let mut buf = [0u8; BLADERF_FLASH_PAGE_SIZE];
+        self.read_page(
+            BLADERF_FLASH_ADDR_FPGA / BLADERF_FLASH_PAGE_SIZE as u32,
+            &mut buf,
+        )?;
+        decode_fpga_meta_page(&buf)
+    }
+
     /// Erases the FPGA region of the SPI flash.
     ///
     /// Erases all sectors from the FPGA start address to the end of flash,
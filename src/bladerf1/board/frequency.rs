@@ -22,6 +22,7 @@ pub use crate::bladerf1::hardware::lms6002d::frequency::QuickTune;
 use crate::bladerf1::protocol::RetuneTimestamp;
 use crate::channel::Channel;
 use crate::error::{Error, Result};
+use crate::log_target;
 use crate::range::{Range, RangeItem};
 
 /// Determines how frequency changes are applied to the LMS6002D.
@@ -36,7 +37,91 @@ pub enum TuningMode {
     Fpga,
 }
 
+/// Environment variable that overrides the default tuning mode, matching libbladeRF.
+pub const BLADERF_DEFAULT_TUNING_MODE_ENV: &str = "BLADERF_DEFAULT_TUNING_MODE";
+
+impl TuningMode {
+    /// Returns the tuning mode named by `BLADERF_DEFAULT_TUNING_MODE`, or
+    /// `TuningMode::Fpga` if the variable is unset or not recognized.
+    pub fn from_env() -> Self {
+        match std::env::var(BLADERF_DEFAULT_TUNING_MODE_ENV) {
+            Ok(value) => Self::try_from(value.as_str()).unwrap_or_else(|_| {
+                log::warn!(
+                    target: log_target::BOARD,
+                    "Ignoring unknown {BLADERF_DEFAULT_TUNING_MODE_ENV} value: {value}"
+                );
+                Self::Fpga
+            }),
+            Err(_) => Self::Fpga,
+        }
+    }
+}
+
+impl TryFrom<&str> for TuningMode {
+    type Error = Error;
+    fn try_from(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "host" => Ok(TuningMode::Host),
+            "fpga" => Ok(TuningMode::Fpga),
+            _ => Err(Error::Argument("unknown tuning mode".into())),
+        }
+    }
+}
+
 impl RfLinkSession<'_> {
+    /// Sets the tuning mode used by `set_frequency_default_mode()`.
+    ///
+    /// The mode is stored on the device handle and persists across sessions.
+    pub fn set_tuning_mode(&mut self, mode: TuningMode) {
+        log::debug!(target: log_target::BOARD, "Tuning mode: {mode:?}");
+        *self.tuning_mode = mode;
+    }
+
+    /// Returns the tuning mode used by `set_frequency_default_mode()`.
+    ///
+    /// Defaults to the value of `BLADERF_DEFAULT_TUNING_MODE` when the device
+    /// was opened, or `TuningMode::Fpga` if it was not set.
+    pub fn get_tuning_mode(&self) -> TuningMode {
+        *self.tuning_mode
+    }
+
+    /// Sets the RF frequency for the given channel using the configured tuning mode.
+    ///
+    /// In `TuningMode::Host` the `LmsFreq` register values are computed on the host
+    /// and written to the LMS6002D directly; in `TuningMode::Fpga` a retune packet
+    /// is sent to the NIOS core. See `set_frequency()` for details.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn set_frequency_default_mode(&mut self, channel: Channel, frequency: u64) -> Result<()> {
+        let mode = self.get_tuning_mode();
+        self.set_frequency(channel, frequency, mode)
+    }
+
+    /// Tunes the RX and TX channels in one call using the configured tuning
+    /// mode, as for an FDD setup.
+    ///
+    /// Both frequencies are checked against `get_frequency_range()` before
+    /// either channel is retuned, so an out-of-range value leaves both
+    /// untouched. RX is then tuned before TX with `set_frequency()`; each
+    /// channel has its own synthesizer and band switches, so there is no
+    /// band selection the two could share.
+    ///
+    /// Returns `Error::Argument` if either frequency is out of range.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn set_frequency_both(&mut self, rx_frequency: u64, tx_frequency: u64) -> Result<()> {
+        self.require_initialized()?;
+        #[cfg(feature = "xb200")]
+        let xb200 = self.nios.xb200_is_enabled()?;
+        #[cfg(not(feature = "xb200"))]
+        let xb200 = false;
+        Self::check_frequency(rx_frequency, xb200)?;
+        Self::check_frequency(tx_frequency, xb200)?;
+        let mode = self.get_tuning_mode();
+        self.set_frequency(Channel::Rx, rx_frequency, mode)?;
+        self.set_frequency(Channel::Tx, tx_frequency, mode)
+    }
+
     /// Sets the RF frequency for the given channel.
     ///
     /// With `TuningMode::Host`, the LMS6002D is tuned immediately via SPI,
@@ -49,20 +134,107 @@ impl RfLinkSession<'_> {
     /// minimum, the signal is routed through the XB-200 upconverter path
     /// using `1248 MHz - desired_freq` translation.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// The frequency is remembered per channel so that `get_frequency()` can
+    /// cross-check the LMS6002D registers against it. If the synthesizer was
+    /// last sent to a frequency with the same PLL settings (see
+    /// `retune_required()`), the PLL programming and VCOCAP search are
+    /// skipped; band selection, the XB-200 path and DC corrections are still
+    /// applied. Use `set_frequency_force()` to always retune.
+    ///
+    /// Returns `Error::Argument` if the frequency is outside
+    /// `get_frequency_range()`; use `set_frequency_clamped()` to clamp instead.
+    ///
+    /// Failures while programming the LMS6002D PLL or charge pumps are
+    /// wrapped in `Error::Context` naming the register step, e.g. a VCOCAP
+    /// search that finds no lock surfaces as `Error::TuningFailed` inside a
+    /// `"tuning VCOCAP"` context. Use `Error::root_cause()` to match on the
+    /// underlying variant.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn set_frequency(
+        &mut self,
+        channel: Channel,
+        frequency: u64,
+        mode: TuningMode,
+    ) -> Result<()> {
+        self.tune(channel, frequency, mode, false)
+    }
+
+    /// Sets the RF frequency like `set_frequency()`, but always reprograms
+    /// the PLL and reruns the VCOCAP search, e.g. to recalibrate after the
+    /// board temperature has drifted.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn set_frequency_force(
+        &mut self,
+        channel: Channel,
+        frequency: u64,
+        mode: TuningMode,
+    ) -> Result<()> {
+        self.tune(channel, frequency, mode, true)
+    }
+
+    /// Returns `true` if tuning to `requested` needs the synthesizer to be
+    /// reprogrammed, given the frequency it was last `commanded` to.
+    ///
+    /// A retune is skipped only when both frequencies give the LMS6002D the
+    /// same VCO, divider and NINT/NFRAC words, i.e. they differ by less than
+    /// the synthesizer resolution of a few Hz. XB-200 mixer frequencies below
+    /// the LMS6002D minimum are compared at their `1248 MHz - f` LMS6002D
+    /// frequency. With nothing commanded a retune is always required.
+    pub fn retune_required(commanded: Option<u64>, requested: u64) -> bool {
+        let Some(commanded) = commanded else {
+            return true;
+        };
+        if commanded == requested {
+            return false;
+        }
+        let freq_min = lms6002d::frequency::get_frequency_min() as u64;
+        let lms_frequency = |f: u64| {
+            if f < freq_min {
+                1_248_000_000u64.saturating_sub(f)
+            } else {
+                f
+            }
+        };
+        if (commanded < freq_min) != (requested < freq_min) {
+            return true;
+        }
+        match (
+            LmsFreq::try_from(lms_frequency(commanded)),
+            LmsFreq::try_from(lms_frequency(requested)),
+        ) {
+            (Ok(a), Ok(b)) => {
+                (a.freqsel, a.nint, a.nfrac, a.flags) != (b.freqsel, b.nint, b.nfrac, b.flags)
+            }
+            _ => true,
+        }
+    }
+
+    fn tune(
         &mut self,
         channel: Channel,
         #[allow(unused_mut)] mut frequency: u64,
         mode: TuningMode,
+        force: bool,
     ) -> Result<()> {
         self.require_initialized()?;
-        log::trace!("Setting Frequency on channel {channel:?} to {frequency}Hz");
+        log::trace!(
+            target: log_target::BOARD,
+            "Setting Frequency on channel {channel:?} to {frequency}Hz"
+        );
         #[cfg(feature = "xb200")]
-        if self.nios.xb200_is_enabled()? {
+        let xb200 = self.nios.xb200_is_enabled()?;
+        #[cfg(not(feature = "xb200"))]
+        let xb200 = false;
+        Self::check_frequency(frequency, xb200)?;
+        let requested = frequency;
+        #[cfg(feature = "xb200")]
+        if xb200 {
             let freq_min = lms6002d::frequency::get_frequency_min() as u64;
             if frequency < freq_min {
                 log::debug!(
+                    target: log_target::BOARD,
                     "Setting path to Mix (freq {} < min {})",
                     frequency,
                     freq_min
@@ -70,6 +242,7 @@ impl RfLinkSession<'_> {
                 self.xb200_set_path(channel, Xb200Path::Mix)?;
                 self.xb200_auto_filter_selection(channel, frequency)?;
                 log::debug!(
+                    target: log_target::BOARD,
                     "Converting frequency: 1248000000 - {} = {}",
                     frequency,
                     1_248_000_000 - frequency
@@ -77,6 +250,7 @@ impl RfLinkSession<'_> {
                 frequency = 1_248_000_000 - frequency;
             } else {
                 log::debug!(
+                    target: log_target::BOARD,
                     "Setting path to Bypass (freq {} >= min {})",
                     frequency,
                     freq_min
@@ -84,15 +258,27 @@ impl RfLinkSession<'_> {
                 self.xb200_set_path(channel, Xb200Path::Bypass)?;
             }
         }
-        match mode {
-            TuningMode::Host => {
-                self.lms().set_frequency(channel, frequency)?;
-                let band = lms6002d::Band::from(frequency);
-                self.band_select(channel, band)?;
-            }
-            TuningMode::Fpga => {
-                self.schedule_retune(channel, RetuneTimestamp::Now, frequency, None)?;
+        let commanded = self.commanded_frequency[channel as usize];
+        if !force && !Self::retune_required(commanded, requested) {
+            log::trace!(
+                target: log_target::BOARD,
+                "{channel:?} synthesizer already at {commanded:?}Hz, skipping retune"
+            );
+            let band = lms6002d::Band::from(frequency);
+            self.band_select(channel, band)?;
+        } else {
+            self.commanded_frequency[channel as usize] = None;
+            match mode {
+                TuningMode::Host => {
+                    self.lms().set_frequency(channel, frequency)?;
+                    let band = lms6002d::Band::from(frequency);
+                    self.band_select(channel, band)?;
+                }
+                TuningMode::Fpga => {
+                    self.schedule_retune(channel, RetuneTimestamp::Now, frequency, None)?;
+                }
             }
+            self.commanded_frequency[channel as usize] = Some(requested);
         }
         let table = match channel {
             Channel::Rx => self.dc_rx_table,
@@ -116,50 +302,119 @@ impl RfLinkSession<'_> {
     /// enabled in Mix path, applies the inverse translation
     /// `1248 MHz - raw_freq` to report the actual user-facing frequency.
     ///
+    /// If the result differs from the frequency last passed to
+    /// `set_frequency()` by more than one VCOCAP step (see
+    /// `frequency_diverges()`), a warning is logged. This catches retunes
+    /// that left the synthesizer somewhere other than where it was sent.
+    ///
     /// Returns `Error::BoardState` if the LMS6002D register read yields
     /// an invalid value.
     pub fn get_frequency(&mut self, channel: Channel) -> Result<u64> {
         self.require_initialized()?;
         let f = self.lms().get_frequency(channel)?;
         if f.x == 0 {
-            log::error!("LMSFreq.x was zero!");
+            log::error!(target: log_target::BOARD, "LMSFreq.x was zero!");
             return Err(Error::BoardState("LMSFreq.x was zero"));
         }
         #[allow(unused_mut)]
         let mut frequency_hz: u64 = (&f).into();
-        log::trace!("Frequency Hz: {frequency_hz}");
+        log::trace!(target: log_target::BOARD, "Frequency Hz: {frequency_hz}");
         #[cfg(feature = "xb200")]
         if self.nios.xb200_is_enabled()? {
             let path = self.xb200_get_path(channel)?;
-            log::trace!("XB200 path detected: {:?}", path);
+            log::trace!(target: log_target::BOARD, "XB200 path detected: {:?}", path);
             if path == Xb200Path::Mix {
-                log::debug!("Mix path - converting: 1248000000 - {}", frequency_hz);
+                log::debug!(
+                    target: log_target::BOARD,
+                    "Mix path - converting: 1248000000 - {}", frequency_hz
+                );
                 frequency_hz = 1_248_000_000 - frequency_hz;
             }
         }
+        if let Some(commanded) = self.commanded_frequency[channel as usize]
+            && Self::frequency_diverges(commanded, frequency_hz)
+        {
+            log::warn!(
+                target: log_target::BOARD,
+                "{channel:?} frequency read back as {frequency_hz}Hz, but {commanded}Hz was commanded"
+            );
+        }
         Ok(frequency_hz)
     }
 
-    /// Returns the supported RF frequency range in Hz.
+    /// Returns `true` if `actual` is more than one VCOCAP step away from
+    /// `commanded`.
     ///
-    /// When the XB-200 is enabled, the minimum is extended to 0 Hz since the
-    /// upconverter path can reach below the LMS6002D's native minimum.
+    /// The step is taken at the frequency the LMS6002D is tuned to, which
+    /// for XB-200 mixer frequencies below the LMS6002D minimum is
+    /// `1248 MHz - commanded`.
+    pub fn frequency_diverges(commanded: u64, actual: u64) -> bool {
+        let lms_frequency = if commanded < lms6002d::frequency::get_frequency_min() as u64 {
+            1_248_000_000u64.saturating_sub(commanded)
+        } else {
+            commanded
+        };
+        commanded.abs_diff(actual) > lms6002d::frequency::vcocap_step_hz(lms_frequency)
+    }
+
+    /// Returns the supported RF frequency range in Hz: 237.5 MHz to 3.8 GHz,
+    /// or 0 Hz to 3.8 GHz when the XB-200 is enabled, since its mixer path
+    /// reaches below the LMS6002D's native minimum.
+    ///
+    /// The XB-200 state is read from the expansion GPIO RF_ON bit on every
+    /// call rather than cached, so the range stays correct after an FPGA
+    /// reload clears the expansion GPIO.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn get_frequency_range(&mut self) -> Result<Range> {
         self.require_initialized()?;
         #[cfg(feature = "xb200")]
-        let freq_min = if self.nios.xb200_is_enabled()? {
+        let xb200 = self.nios.xb200_is_enabled()?;
+        #[cfg(not(feature = "xb200"))]
+        let xb200 = false;
+        Ok(Self::frequency_range(xb200))
+    }
+
+    /// Returns the RF frequency range in Hz with or without an enabled XB-200.
+    pub fn frequency_range(xb200_enabled: bool) -> Range {
+        let freq_min = if xb200_enabled {
             0.0
         } else {
             lms6002d::frequency::get_frequency_min() as f64
         };
-        #[cfg(not(feature = "xb200"))]
-        let freq_min = lms6002d::frequency::get_frequency_min() as f64;
         let freq_max = lms6002d::frequency::get_frequency_max() as f64;
-        Ok(Range::new(vec![RangeItem::Step(
-            freq_min, freq_max, 1f64, 1f64,
-        )]))
+        Range::new(vec![RangeItem::Step(freq_min, freq_max, 1f64, 1f64)])
+    }
+
+    /// Returns `Error::Argument` if `frequency` is outside
+    /// `frequency_range(xb200_enabled)`.
+    pub fn check_frequency(frequency: u64, xb200_enabled: bool) -> Result<()> {
+        Self::frequency_range(xb200_enabled)
+            .check(frequency as f64, "frequency")
+            .inspect_err(|e| log::error!(target: log_target::BOARD, "{e}"))
+    }
+
+    /// Clamps `frequency` to `get_frequency_range()` and tunes to it with
+    /// `set_frequency()`. Returns the frequency that was requested from the
+    /// hardware.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn set_frequency_clamped(
+        &mut self,
+        channel: Channel,
+        frequency: u64,
+        mode: TuningMode,
+    ) -> Result<u64> {
+        let range = self.get_frequency_range()?;
+        let clamped = frequency.clamp(range.min_checked()? as u64, range.max_checked()? as u64);
+        if clamped != frequency {
+            log::debug!(
+                target: log_target::BOARD,
+                "Clamped frequency {frequency}Hz to {clamped}Hz"
+            );
+        }
+        self.set_frequency(channel, clamped, mode)?;
+        Ok(clamped)
     }
 
     /// Selects the LMS6002D band (low or high) for the given channel based on frequency.
@@ -173,7 +428,7 @@ impl RfLinkSession<'_> {
     /// Retains only the `LmsFreq` from the full response; use
     /// `schedule_retune_with_duration` to also retrieve the retune duration.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn schedule_retune(
         &mut self,
         channel: Channel,
@@ -196,7 +451,11 @@ impl RfLinkSession<'_> {
     /// If `quick_tune` is provided, it is converted directly to register values,
     /// bypassing the frequency-to-register conversion (useful for rapid hopping).
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// The retune moves the synthesizer away from the last `set_frequency()`
+    /// target, so the frequency remembered for `channel` is cleared until the
+    /// next `set_frequency()`.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn schedule_retune_with_duration(
         &mut self,
         channel: Channel,
@@ -205,18 +464,20 @@ impl RfLinkSession<'_> {
         quick_tune: Option<QuickTune>,
     ) -> Result<(LmsFreq, u64)> {
         self.require_initialized()?;
+        self.commanded_frequency[channel as usize] = None;
         let f: LmsFreq = if let Some(qt) = quick_tune {
             qt.into()
         } else {
             #[cfg(feature = "xb200")]
             if self.nios.xb200_is_enabled()? {
                 log::info!(
+                    target: log_target::BOARD,
                     "Consider supplying the quick_tune parameter to schedule_retune() when the XB-200 is enabled."
                 );
             }
             frequency.try_into()?
         };
-        log::trace!("{f:?}");
+        log::trace!(target: log_target::BOARD, "{f:?}");
         let band = if (f.flags & lms6002d::LMS_FREQ_FLAGS_LOW_BAND) != 0 {
             lms6002d::Band::Low
         } else {
@@ -233,12 +494,36 @@ impl RfLinkSession<'_> {
         Ok((f, result.duration()))
     }
 
+    /// Re-applies a previously captured `QuickTune` profile via the NIOS retune queue.
+    ///
+    /// The request is always sent with `Tune::Quick` so the stored VCOCAP value is
+    /// used as-is and the VCOCAP search is skipped, which makes this the fastest way
+    /// to hop between frequencies captured with `get_quick_tune()`. Pass
+    /// `RetuneTimestamp::Now` to retune immediately, `RetuneTimestamp::Scheduled`
+    /// to retune at a hardware timestamp, or `RetuneTimestamp::ClearQueue` to
+    /// flush pending requests.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn schedule_quick_retune(
+        &mut self,
+        channel: Channel,
+        timestamp: RetuneTimestamp,
+        quick_tune: &QuickTune,
+    ) -> Result<()> {
+        let mut quick_tune = *quick_tune;
+        quick_tune.flags |= lms6002d::LMS_FREQ_FLAGS_FORCE_VCOCAP;
+        self.schedule_retune(channel, timestamp, 0, Some(quick_tune))?;
+        Ok(())
+    }
+
     /// Cancels all pending FPGA-initiated retune requests for a channel.
     ///
     /// Sends a `RetuneTimestamp::ClearQueue` command through the NIOS retune
     /// interface to flush the queue without applying any frequency change.
+    /// Only the success flag of the response is checked; the duration and
+    /// VCOCAP fields are not valid for a clear request.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn cancel_scheduled_retunes(&mut self, channel: Channel) -> Result<()> {
         self.require_initialized()?;
         self.nios.nios_retune(
@@ -262,7 +547,7 @@ impl RfLinkSession<'_> {
     /// When the XB-200 is enabled, the parameters account for the
     /// upconverter path.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn get_quick_tune(&mut self, channel: Channel) -> Result<QuickTune> {
         self.require_initialized()?;
         #[cfg(feature = "xb200")]
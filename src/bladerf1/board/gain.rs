@@ -5,16 +5,25 @@
 //! for TX). The apportionment algorithm follows the LMS6002D programming
 //! guide to distribute gain optimally across stages.
 //!
+//! The aggregate gain used by `get_gain()`, `set_gain()` and
+//! `get_gain_range()` is the *system* gain of libbladeRF: the sum of the
+//! stage gains plus `BLADERF1_RX_GAIN_OFFSET` (-6 dB) on RX or
+//! `BLADERF1_TX_GAIN_OFFSET` (52 dB) on TX. A TX system gain of about 60 dB
+//! corresponds to roughly 0 dBm output. Per-stage calls such as
+//! `set_gain_stage()` take the stage's own gain without any offset.
+//!
 //! Supports two RX gain modes: Default (AGC) and Mgc (manual gain control).
 //! TX channel does not support gain modes.
 
-use crate::bladerf1::board::RfLinkSession;
+use crate::bladerf1::board::{Capability, RfLinkSession};
+use crate::bladerf1::hardware::lms6002d::dc_calibration::AgcDcCorrection;
 use crate::bladerf1::hardware::lms6002d::gain::{
     BLADERF1_RX_GAIN_OFFSET, BLADERF1_TX_GAIN_OFFSET, GAIN_SPEC_LNA, GAIN_SPEC_RXVGA1,
     GAIN_SPEC_RXVGA2, GAIN_SPEC_TXVGA1, GAIN_SPEC_TXVGA2, GainDb, GainStage,
 };
 use crate::channel::Channel;
 use crate::error::{Error, Result};
+use crate::log_target;
 use crate::range::{Range, RangeItem};
 
 /// GPIO bit that enables automatic gain control on the RX channel.
@@ -31,6 +40,19 @@ pub enum GainMode {
     Mgc,
 }
 
+/// Name, supported range, and current value of a single amplifier stage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GainStageInfo {
+    /// The amplifier stage.
+    pub stage: GainStage,
+    /// Stage name as accepted by `GainStage::try_from(&str)`.
+    pub name: &'static str,
+    /// Supported gain range of the stage.
+    pub range: Range,
+    /// Current gain of the stage.
+    pub gain: GainDb,
+}
+
 impl RfLinkSession<'_> {
     fn _apportion_gain(stage_gain_range: &Range, stage_gain: i8, gain: i8) -> Result<(i8, i8)> {
         let stage_max_gain =
@@ -77,34 +99,69 @@ impl RfLinkSession<'_> {
     /// `Error::Unsupported`.
     pub fn get_gain_modes(&self, channel: Channel) -> Result<Vec<GainMode>> {
         if channel.is_tx() {
-            log::error!("TX does not support gain modes");
+            log::error!(target: log_target::BOARD, "TX does not support gain modes");
             Err(Error::Unsupported("TX gain modes"))
         } else {
             Ok(vec![GainMode::Mgc, GainMode::Default])
         }
     }
+    /// Loads the FPGA AGC DC correction registers for the current RX frequency.
+    ///
+    /// The six `AGC_DC_{I,Q}_{MIN,MID,MAX}` values are taken from the loaded RX
+    /// DC calibration table. The AGC switches between these corrections as it
+    /// moves between its gain settings.
+    fn load_agc_dc_correction(&mut self) -> Result<()> {
+        let table = self.dc_rx_table.ok_or(Error::Unsupported(
+            "AGC requires an RX DC calibration table",
+        ))?;
+        let frequency = self.get_frequency(Channel::Rx)?;
+        let entry = table.lookup(frequency);
+        self.nios
+            .nios_set_agc_dc_correction(&AgcDcCorrection::from(&entry))
+    }
     /// Sets the gain mode for the given channel.
     ///
     /// Only the RX channel supports gain modes. Calling with TX returns
     /// `Error::Unsupported`. Toggles the AGC enable bit in the config GPIO.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Enabling `GainMode::Default` first loads the AGC DC correction values
+    /// for the current RX frequency. The AGC cannot run without them, so
+    /// `Error::Unsupported` is returned if no RX DC calibration table is loaded,
+    /// or if the FPGA lacks `Capability::AgcDcLut` to hold them.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn set_gain_mode(&mut self, channel: Channel, mode: GainMode) -> Result<()> {
         self.require_initialized()?;
         if channel.is_tx() {
-            log::error!("Setting gain mode for TX is not supported");
+            log::error!(target: log_target::BOARD, "Setting gain mode for TX is not supported");
             return Err(Error::Unsupported("TX gain modes"));
         }
+        if mode == GainMode::Default {
+            self.require_fpga_cap(Capability::AgcDcLut)
+                .map_err(|err| match err {
+                    Error::NoApi { .. } => Error::Unsupported("AGC requires FPGA v0.7.0 or newer"),
+                    err => err,
+                })?;
+            self.load_agc_dc_correction()?;
+        }
         self.config_gpio_modify(|gpio| match mode {
             GainMode::Default => gpio | BLADERF_GPIO_AGC_ENABLE,
             GainMode::Mgc => gpio & !BLADERF_GPIO_AGC_ENABLE,
         })
     }
-    /// Returns the current RX gain mode by reading the AGC enable bit from config GPIO.
+    /// Returns the current gain mode of the given channel by reading the AGC
+    /// enable bit from config GPIO.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
-    pub fn get_gain_mode(&mut self) -> Result<GainMode> {
+    /// Mirrors `set_gain_mode()`: only the RX channel has gain modes, so
+    /// calling with TX returns `Error::Unsupported`.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn get_gain_mode(&mut self, channel: Channel) -> Result<GainMode> {
         self.require_initialized()?;
+        if channel.is_tx() {
+            log::error!(target: log_target::BOARD, "Getting gain mode for TX is not supported");
+            return Err(Error::Unsupported("TX gain modes"));
+        }
         let data = self.config_gpio_read()?;
         let gain_mode = if (data & BLADERF_GPIO_AGC_ENABLE) != 0 {
             GainMode::Default
@@ -115,7 +172,7 @@ impl RfLinkSession<'_> {
     }
     /// Returns the current gain of an individual amplifier stage in dB.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn get_gain_stage(&mut self, stage: GainStage) -> Result<GainDb> {
         self.require_initialized()?;
         match stage {
@@ -131,7 +188,7 @@ impl RfLinkSession<'_> {
     /// Use `set_gain()` for automatic apportioning across stages.
     /// Direct stage control is available for fine tuning.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn set_gain_stage(&mut self, stage: GainStage, gain: GainDb) -> Result<()> {
         self.require_initialized()?;
         match stage {
@@ -142,6 +199,35 @@ impl RfLinkSession<'_> {
             GainStage::Lna => self.lms().lna_set_gain(gain),
         }
     }
+    /// Returns the current gain of the stage of `channel` named `name`
+    /// (`"lna"`, `"rxvga1"`, `"rxvga2"`, `"txvga1"` or `"txvga2"`).
+    ///
+    /// Returns `Error::Argument` if the name is unknown or the stage belongs
+    /// to the other direction, and `Error::WrongState` if the board has
+    /// not been initialized.
+    pub fn get_gain_stage_by_name(&mut self, channel: Channel, name: &str) -> Result<GainDb> {
+        self.get_gain_stage(GainStage::for_channel(channel, name)?)
+    }
+    /// Sets the gain of the stage of `channel` named `name`.
+    ///
+    /// Returns `Error::Argument` if the name is unknown or the stage belongs
+    /// to the other direction, and `Error::WrongState` if the board has
+    /// not been initialized.
+    pub fn set_gain_stage_by_name(
+        &mut self,
+        channel: Channel,
+        name: &str,
+        gain: GainDb,
+    ) -> Result<()> {
+        self.set_gain_stage(GainStage::for_channel(channel, name)?, gain)
+    }
+    /// Returns the supported gain range of the stage of `channel` named `name`.
+    ///
+    /// Returns `Error::Argument` if the name is unknown or the stage belongs
+    /// to the other direction.
+    pub fn get_gain_stage_range_by_name(channel: Channel, name: &str) -> Result<Range> {
+        Ok(GainStage::for_channel(channel, name)?.gain_range())
+    }
     /// Returns the ordered list of amplifier stages for the given channel.
     ///
     /// RX: [LNA, RXVGA1, RXVGA2]. TX: [TXVGA1, TXVGA2].
@@ -152,33 +238,93 @@ impl RfLinkSession<'_> {
             &[GainStage::Lna, GainStage::RxVga1, GainStage::RxVga2]
         }
     }
+    /// Returns the name, range, and current gain of every amplifier stage of the
+    /// given channel, in the order reported by `get_gain_stages()`.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn gain_stage_info(&mut self, channel: Channel) -> Result<Vec<GainStageInfo>> {
+        Self::get_gain_stages(channel)
+            .iter()
+            .map(|&stage| {
+                Ok(GainStageInfo {
+                    stage,
+                    name: stage.into(),
+                    range: stage.gain_range(),
+                    gain: self.get_gain_stage(stage)?,
+                })
+            })
+            .collect()
+    }
     /// Returns the supported gain range for an individual amplifier stage.
     pub fn get_gain_stage_range(stage: GainStage) -> Range {
         stage.gain_range()
     }
     fn get_tx_gain(&mut self) -> Result<GainDb> {
         self.require_initialized()?;
-        let txvga1 = self.lms().txvga1_get_gain()?;
-        let txvga2 = self.lms().txvga2_get_gain()?;
-        Ok((txvga1.db() + txvga2.db() + BLADERF1_TX_GAIN_OFFSET as i8).into())
+        let stages = [
+            (GainStage::TxVga1, self.lms().txvga1_get_gain()?),
+            (GainStage::TxVga2, self.lms().txvga2_get_gain()?),
+        ];
+        Self::check_stage_order(Channel::Tx, &stages)?;
+        Ok(Self::system_gain(Channel::Tx, &stages))
     }
     fn get_rx_gain(&mut self) -> Result<GainDb> {
         self.require_initialized()?;
-        let lna_gain_db = self.lms().lna_get_gain()?;
-        let rxvga1_gain_db = self.lms().rxvga1_get_gain()?;
-        let rxvga2_gain_db = self.lms().rxvga2_get_gain()?;
-        Ok((lna_gain_db.db()
-            + rxvga1_gain_db.db()
-            + rxvga2_gain_db.db()
-            + BLADERF1_RX_GAIN_OFFSET as i8)
-            .into())
+        let stages = [
+            (GainStage::Lna, self.lms().lna_get_gain()?),
+            (GainStage::RxVga1, self.lms().rxvga1_get_gain()?),
+            (GainStage::RxVga2, self.lms().rxvga2_get_gain()?),
+        ];
+        Self::check_stage_order(Channel::Rx, &stages)?;
+        Ok(Self::system_gain(Channel::Rx, &stages))
+    }
+    /// Returns the system gain of `channel` for the given stage gains: their
+    /// sum plus `BLADERF1_RX_GAIN_OFFSET` or `BLADERF1_TX_GAIN_OFFSET`.
+    ///
+    /// Each stage gain is already in dB (see `GainStage::gain_from_code()`
+    /// for decoding register values), so the sum needs no further
+    /// conversion. Stages of the other direction are ignored.
+    pub fn system_gain(channel: Channel, stages: &[(GainStage, GainDb)]) -> GainDb {
+        let offset = if channel.is_tx() {
+            BLADERF1_TX_GAIN_OFFSET
+        } else {
+            BLADERF1_RX_GAIN_OFFSET
+        };
+        let sum: i16 = stages
+            .iter()
+            .filter(|(stage, _)| stage.belongs_to(channel))
+            .map(|(_, gain)| gain.db() as i16)
+            .sum();
+        ((sum + offset as i16).clamp(i8::MIN as i16, i8::MAX as i16) as i8).into()
+    }
+    /// Logs a debug message if the stage gains read back differ from what
+    /// `set_gain()` would program for the same total, e.g. RXVGA2 at its
+    /// maximum with the LNA bypassed. Such a state was set stage by stage
+    /// or by the AGC and usually has a worse noise figure.
+    fn check_stage_order(channel: Channel, stages: &[(GainStage, GainDb)]) -> Result<()> {
+        let total = Self::system_gain(channel, stages);
+        let expected = Self::gain_distribution(channel, total)?;
+        if expected != stages {
+            log::debug!(
+                target: log_target::BOARD,
+                "{channel:?} stage gains {stages:?} differ from the distribution {expected:?} \
+                 set_gain() uses for {} dB",
+                total.db()
+            );
+        }
+        Ok(())
     }
     /// Returns the current aggregate gain of the given channel in dB.
     ///
-    /// Sums all amplifier stages (LNA + RXVGA1 + RXVGA2 for RX,
-    /// TXVGA1 + TXVGA2 for TX) along with the board gain offset.
+    /// This is the system gain: the sum of all amplifier stages (LNA +
+    /// RXVGA1 + RXVGA2 for RX, TXVGA1 + TXVGA2 for TX) plus the board gain
+    /// offset (`BLADERF1_RX_GAIN_OFFSET` / `BLADERF1_TX_GAIN_OFFSET`), as
+    /// reported by libbladeRF. Each stage is read back from the LMS6002D and
+    /// the total is computed with `system_gain()`. A debug message is logged
+    /// if the stages are not distributed the way `set_gain()` would program
+    /// that total.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn get_gain(&mut self, channel: Channel) -> Result<GainDb> {
         self.require_initialized()?;
         if channel.is_tx() {
@@ -189,12 +335,13 @@ impl RfLinkSession<'_> {
     }
     /// Sets the aggregate gain for the given channel.
     ///
-    /// Distributes the requested gain across the available amplifier stages
-    /// using an apportionment algorithm from the LMS6002D programming guide.
-    /// If the exact gain cannot be achieved, the closest achievable value
-    /// is set with a debug log message.
+    /// `gain` is the system gain, so the board gain offset is subtracted
+    /// before the remainder is distributed across the amplifier stages as
+    /// computed by `gain_distribution()`. Requests outside the channel's
+    /// gain range are clamped. If the exact gain cannot be achieved, the
+    /// closest achievable value is set with a debug log message.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn set_gain(&mut self, channel: Channel, gain: GainDb) -> Result<()> {
         self.require_initialized()?;
         if channel.is_tx() {
@@ -203,15 +350,56 @@ impl RfLinkSession<'_> {
             self.set_rx_gain(gain)
         }
     }
-    /// Sets the TX aggregate gain by apportioning across TXVGA1 and TXVGA2.
+    /// Sets the aggregate gain for the given channel from a linear (voltage) ratio.
     ///
-    /// Begins with both stages at minimum, then distributes remaining gain
-    /// greedily in stage order (TXVGA2 first, then TXVGA1).
+    /// The ratio is converted with `20 * log10(linear)` and clamped to the
+    /// channel's gain range before being applied with `set_gain()`.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
-    pub fn set_tx_gain(&mut self, gain_db: GainDb) -> Result<()> {
-        self.require_initialized()?;
-        let desired_gain = gain_db.db();
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn set_gain_linear(&mut self, channel: Channel, linear: f32) -> Result<()> {
+        let range = Self::get_gain_range(channel);
+        let db = GainDb::from_linear(linear).db() as f64;
+        let clamped = db.clamp(range.min_checked()?, range.max_checked()?);
+        self.set_gain(channel, (clamped as i8).into())
+    }
+    /// Returns the current aggregate gain of the given channel as a linear (voltage) ratio.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn get_gain_linear(&mut self, channel: Channel) -> Result<f32> {
+        Ok(self.get_gain(channel)?.linear())
+    }
+    /// Computes the per-stage gains that `set_gain()` programs for a requested
+    /// aggregate gain, in the order reported by `get_gain_stages()`.
+    ///
+    /// The request is first clamped to `get_gain_range()`. The board gain
+    /// offset is then removed and the remainder is distributed, starting from
+    /// every stage at its minimum, following libbladeRF:
+    ///
+    /// * TX: TXVGA2 is filled first, then TXVGA1.
+    /// * RX: the LNA is filled to at most half its maximum, then RXVGA1, then
+    ///   the rest of the LNA, then RXVGA2. If gain is still missing with
+    ///   RXVGA1 at its maximum, one RXVGA2 step is traded from RXVGA1 so that
+    ///   RXVGA2's coarser steps can absorb it.
+    ///
+    /// With the BladeRF1 stage ranges, the stages plus the offset sum to the
+    /// clamped request for every whole-dB value in range.
+    pub fn gain_distribution(channel: Channel, gain: GainDb) -> Result<Vec<(GainStage, GainDb)>> {
+        let range = Self::get_gain_range(channel);
+        let desired_gain =
+            (gain.db() as f64).clamp(range.min_checked()?, range.max_checked()?) as i8;
+        let gains = if channel.is_tx() {
+            Self::distribute_tx_gain(desired_gain)?.to_vec()
+        } else {
+            Self::distribute_rx_gain(desired_gain)?.to_vec()
+        };
+        Ok(Self::get_gain_stages(channel)
+            .iter()
+            .zip(gains)
+            .map(|(&stage, db)| (stage, db.into()))
+            .collect())
+    }
+    /// Returns `[txvga1, txvga2]` for an aggregate TX gain already within range.
+    fn distribute_tx_gain(desired_gain: i8) -> Result<[i8; 2]> {
         let txvga1_range = Self::get_gain_stage_range(GainStage::TxVga1);
         let txvga2_range = Self::get_gain_stage_range(GainStage::TxVga2);
         let mut txvga1 =
@@ -219,28 +407,34 @@ impl RfLinkSession<'_> {
         let mut txvga2 =
             (txvga2_range.scale_checked()? * txvga2_range.min_checked()?).round() as i8;
         let mut gain = desired_gain - (BLADERF1_TX_GAIN_OFFSET as i8 + txvga1 + txvga2);
-        log::trace!("gain={desired_gain} -> txvga2={txvga2} txvga1={txvga1} remainder={gain}");
+        log::trace!(
+            target: log_target::BOARD,
+            "gain={desired_gain} -> txvga2={txvga2} txvga1={txvga1} remainder={gain}"
+        );
         (txvga2, gain) = Self::_apportion_gain(&txvga2_range, txvga2, gain)?;
-        log::trace!("gain={desired_gain} -> txvga2={txvga2} txvga1={txvga1} remainder={gain}");
+        log::trace!(
+            target: log_target::BOARD,
+            "gain={desired_gain} -> txvga2={txvga2} txvga1={txvga1} remainder={gain}"
+        );
         (txvga1, gain) = Self::_apportion_gain(&txvga1_range, txvga1, gain)?;
-        log::trace!("gain={desired_gain} -> txvga2={txvga2} txvga1={txvga1} remainder={gain}");
+        log::trace!(
+            target: log_target::BOARD,
+            "gain={desired_gain} -> txvga2={txvga2} txvga1={txvga1} remainder={gain}"
+        );
         if gain != 0 {
-            log::debug!("unable to achieve requested gain {desired_gain} (missed by {gain})");
-            log::debug!("gain={desired_gain} -> txvga2={txvga2} txvga1={txvga1} remainder={gain}");
+            log::debug!(
+                target: log_target::BOARD,
+                "unable to achieve requested gain {desired_gain} (missed by {gain})"
+            );
+            log::debug!(
+                target: log_target::BOARD,
+                "gain={desired_gain} -> txvga2={txvga2} txvga1={txvga1} remainder={gain}"
+            );
         }
-        self.lms().txvga1_set_gain(txvga1.into())?;
-        self.lms().txvga2_set_gain(txvga2.into())
+        Ok([txvga1, txvga2])
     }
-    /// Sets the RX aggregate gain by apportioning across LNA, RXVGA1, and RXVGA2.
-    ///
-    /// Begins with all stages at minimum, then distributes remaining gain using
-    /// a multi-pass algorithm that clamps the LNA to half its maximum and
-    /// adjusts RXVGA1 headroom for RXVGA2 as needed.
-    ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
-    pub fn set_rx_gain(&mut self, gain_db: GainDb) -> Result<()> {
-        self.require_initialized()?;
-        let desired_gain = gain_db.db();
+    /// Returns `[lna, rxvga1, rxvga2]` for an aggregate RX gain already within range.
+    fn distribute_rx_gain(desired_gain: i8) -> Result<[i8; 3]> {
         let lna_range = Self::get_gain_stage_range(GainStage::Lna);
         let rxvga1_range = Self::get_gain_stage_range(GainStage::RxVga1);
         let rxvga2_range = Self::get_gain_stage_range(GainStage::RxVga2);
@@ -251,6 +445,7 @@ impl RfLinkSession<'_> {
             (rxvga2_range.scale_checked()? * rxvga2_range.min_checked()?).round() as i8;
         let mut gain = desired_gain - (BLADERF1_RX_GAIN_OFFSET as i8 + lna + rxvga1 + rxvga2);
         log::trace!(
+            target: log_target::BOARD,
             "gain={desired_gain} -> lna={lna} rxvga1={rxvga1} rxvga2={rxvga2} remainder={gain}"
         );
         (lna, gain) = Self::_apportion_gain(&lna_range, lna, gain)?;
@@ -259,18 +454,22 @@ impl RfLinkSession<'_> {
             lna = lna - (lna - GAIN_SPEC_LNA.max / 2);
         }
         log::trace!(
+            target: log_target::BOARD,
             "gain={desired_gain} -> lna={lna} rxvga1={rxvga1} rxvga2={rxvga2} remainder={gain}"
         );
         (rxvga1, gain) = Self::_apportion_gain(&rxvga1_range, rxvga1, gain)?;
         log::trace!(
+            target: log_target::BOARD,
             "gain={desired_gain} -> lna={lna} rxvga1={rxvga1} rxvga2={rxvga2} remainder={gain}"
         );
         (lna, gain) = Self::_apportion_gain(&lna_range, lna, gain)?;
         log::trace!(
+            target: log_target::BOARD,
             "gain={desired_gain} -> lna={lna} rxvga1={rxvga1} rxvga2={rxvga2} remainder={gain}"
         );
         (rxvga2, gain) = Self::_apportion_gain(&rxvga2_range, rxvga2, gain)?;
         log::trace!(
+            target: log_target::BOARD,
             "gain={desired_gain} -> lna={lna} rxvga1={rxvga1} rxvga2={rxvga2} remainder={gain}"
         );
         let rxvga1_max =
@@ -284,16 +483,42 @@ impl RfLinkSession<'_> {
             (rxvga1, gain) = Self::_apportion_gain(&rxvga1_range, rxvga1, gain)?;
         }
         log::trace!(
+            target: log_target::BOARD,
             "gain={desired_gain} -> lna={lna} rxvga1={rxvga1} rxvga2={rxvga2} remainder={gain}"
         );
         if gain != 0 {
-            log::debug!("unable to achieve requested gain {desired_gain} (missed by {gain})");
             log::debug!(
+                target: log_target::BOARD,
+                "unable to achieve requested gain {desired_gain} (missed by {gain})"
+            );
+            log::debug!(
+                target: log_target::BOARD,
                 "gain={desired_gain} -> lna={lna} rxvga1={rxvga1} rxvga2={rxvga2} remainder={gain}"
             );
         }
-        self.lms().lna_set_gain(lna.into())?;
-        self.lms().rxvga1_set_gain(rxvga1.into())?;
-        self.lms().rxvga2_set_gain(rxvga2.into())
+        Ok([lna, rxvga1, rxvga2])
+    }
+    /// Sets the TX aggregate gain by apportioning across TXVGA1 and TXVGA2.
+    ///
+    /// See `gain_distribution()` for the algorithm.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn set_tx_gain(&mut self, gain_db: GainDb) -> Result<()> {
+        self.set_stage_gains(Channel::Tx, gain_db)
+    }
+    /// Sets the RX aggregate gain by apportioning across LNA, RXVGA1, and RXVGA2.
+    ///
+    /// See `gain_distribution()` for the algorithm.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
+    pub fn set_rx_gain(&mut self, gain_db: GainDb) -> Result<()> {
+        self.set_stage_gains(Channel::Rx, gain_db)
+    }
+    fn set_stage_gains(&mut self, channel: Channel, gain: GainDb) -> Result<()> {
+        self.require_initialized()?;
+        for (stage, stage_gain) in Self::gain_distribution(channel, gain)? {
+            self.set_gain_stage(stage, stage_gain)?;
+        }
+        Ok(())
     }
 }
@@ -14,43 +14,65 @@ use crate::bladerf1::board::RfLinkSession;
 /// Re-exported from the LMS6002D driver. Includes baseband loopback
 /// (BB variants), RF loopback (LNA variants), and firmware loopback.
 pub use crate::bladerf1::hardware::lms6002d::loopback::Loopback;
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::usb::BladeRf1UsbInterfaceCommands;
+use crate::version::SemanticVersion;
+
+/// Minimum FX3 firmware version that implements firmware loopback.
+pub const BLADERF1_FW_LOOPBACK_VERSION: SemanticVersion = SemanticVersion::new(1, 7, 1);
 impl RfLinkSession<'_> {
     /// Sets the loopback mode.
     ///
     /// `Loopback::Firmware` enables USB-level firmware loopback while
     /// configuring the LMS6002D RF loopback through LNA3. All other modes
     /// first disable firmware loopback if it was active, then configure the
-    /// LMS6002D loopback path.
+    /// LMS6002D loopback path. Leaving firmware loopback resets the USB
+    /// alt setting back to `UsbAltSetting::RfLink`.
+    ///
+    /// Returns `Error::Argument` for `Loopback::RficBist`, which is only
+    /// available on BladeRF2. Returns `Error::NoApi` for `Loopback::Firmware`
+    /// if the FX3 firmware is older than [`BLADERF1_FW_LOOPBACK_VERSION`].
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn set_loopback(&mut self, lb: Loopback) -> Result<()> {
         self.require_initialized()?;
         match lb {
+            Loopback::RficBist => Err(Error::Argument(
+                "RFIC BIST loopback is not supported on BladeRF1".into(),
+            )),
             Loopback::Firmware => {
+                self.require_fx3_version(BLADERF1_FW_LOOPBACK_VERSION)?;
                 self.lms().set_loopback_mode(Loopback::Lna3)?;
-                self.nios.usb_set_firmware_loopback(true)
+                self.set_firmware_loopback(true)
             }
             _ => {
-                let fw_lb_enabled: bool = self.nios.usb_get_firmware_loopback()?;
+                let fw_lb_enabled = self
+                    .require_fx3_version(BLADERF1_FW_LOOPBACK_VERSION)
+                    .is_ok()
+                    && self.nios.usb_get_firmware_loopback()?;
                 if fw_lb_enabled {
-                    self.nios.usb_set_firmware_loopback(false)?;
+                    self.set_firmware_loopback(false)?;
                 }
                 self.lms().set_loopback_mode(lb)
             }
         }
     }
+    /// Toggles firmware loopback. The alt setting cycle this requires resets
+    /// the streaming modules, so the module enable cache is invalidated.
+    fn set_firmware_loopback(&mut self, enable: bool) -> Result<()> {
+        self.module_enabled.invalidate();
+        self.nios.usb_set_firmware_loopback(enable)
+    }
     /// Sets the loopback mode on the LMS6002D only, without affecting firmware loopback.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn set_lms_loopback(&mut self, lb: Loopback) -> Result<()> {
         self.require_initialized()?;
         self.lms().set_loopback_mode(lb)
     }
     /// Returns the current LMS6002D loopback mode, independent of firmware loopback.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn get_lms_loopback(&mut self) -> Result<Loopback> {
         self.require_initialized()?;
         self.lms().get_loopback_mode()
@@ -60,11 +82,14 @@ impl RfLinkSession<'_> {
     /// Checks for firmware loopback first; if not active, returns the
     /// LMS6002D loopback mode.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn get_loopback(&mut self) -> Result<Loopback> {
         self.require_initialized()?;
         let mut lb = Loopback::None;
-        let fw_lb_enabled = self.nios.usb_get_firmware_loopback()?;
+        let fw_lb_enabled = self
+            .require_fx3_version(BLADERF1_FW_LOOPBACK_VERSION)
+            .is_ok()
+            && self.nios.usb_get_firmware_loopback()?;
         if fw_lb_enabled {
             lb = Loopback::Firmware;
         }
@@ -74,6 +99,9 @@ impl RfLinkSession<'_> {
         Ok(lb)
     }
     /// Returns true if the given loopback mode is supported on BladeRF1.
+    ///
+    /// Firmware loopback additionally requires FX3 firmware
+    /// [`BLADERF1_FW_LOOPBACK_VERSION`] or later.
     pub fn is_loopback_mode_supported(&self, lb: Loopback) -> bool {
         let supported_modes = [
             Loopback::None,
@@ -86,6 +114,13 @@ impl RfLinkSession<'_> {
             Loopback::Lna2,
             Loopback::Lna3,
         ];
+        if lb == Loopback::Firmware
+            && self
+                .require_fx3_version(BLADERF1_FW_LOOPBACK_VERSION)
+                .is_err()
+        {
+            return false;
+        }
         supported_modes.contains(&lb)
     }
 }
@@ -11,7 +11,7 @@ use crate::error::Result;
 impl RfLinkSession<'_> {
     /// Sets the LPF operating mode for the given channel.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn set_lpf_mode(&mut self, channel: Channel, mode: LpfMode) -> Result<()> {
         self.require_initialized()?;
         self.lms().lpf_set_mode(channel, mode)
@@ -19,7 +19,7 @@ impl RfLinkSession<'_> {
 
     /// Returns the current LPF operating mode for the given channel.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn get_lpf_mode(&mut self, channel: Channel) -> Result<LpfMode> {
         self.require_initialized()?;
         self.lms().lpf_get_mode(channel)
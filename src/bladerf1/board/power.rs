@@ -0,0 +1,167 @@
+//! RX power measurement for BladeRF1.
+//!
+//! Computes the average power of a `capture()` block in the time domain and,
+//! with the `dsp` feature, its power spectral density with a small in-crate
+//! radix-2 FFT.
+//!
+//! All levels are in dB relative to full scale (dBFS). The reference is a
+//! sample magnitude of `POWER_FULL_SCALE` (2048) counts, the scale of
+//! `SampleFormat::to_complex_f32`, so a complex tone whose I and Q swing the
+//! full 12-bit ADC range measures 0 dBFS.
+
+use crate::bladerf1::board::RfLinkSession;
+use crate::channel::Channel;
+use crate::error::{Error, Result};
+use num_complex::Complex;
+use std::time::Duration;
+
+#[cfg(feature = "dsp")]
+use std::f32::consts::TAU;
+
+/// Sample magnitude in `Sc16Q11` counts that corresponds to 0 dBFS.
+pub const POWER_FULL_SCALE: f32 = 2048.0;
+
+const POWER_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Returns the mean power `|s|²` of `samples` in dBFS.
+///
+/// `samples` are scaled as by `SampleFormat::to_complex_f32`, so a magnitude
+/// of 1.0 is full scale. An empty or all-zero slice measures negative
+/// infinity.
+pub fn power_dbfs(samples: &[Complex<f32>]) -> f32 {
+    if samples.is_empty() {
+        return f32::NEG_INFINITY;
+    }
+    let mean = samples.iter().map(|s| s.norm_sqr() as f64).sum::<f64>() / samples.len() as f64;
+    10.0 * (mean as f32).log10()
+}
+
+/// Returns the power spectral density of `samples` in dBFS per bin.
+///
+/// `samples` are split into consecutive blocks of `fft_size`; a trailing
+/// partial block is ignored. Each block is Hann windowed and transformed,
+/// and the bin powers are averaged over all blocks. The result has
+/// `fft_size` bins ordered from `-fs/2` to just below `+fs/2`, with DC at
+/// index `fft_size / 2`. Bins are normalized to the window gain, so a
+/// full-scale tone centred on a bin reads 0 dBFS in that bin.
+///
+/// Requires the `dsp` feature. Returns `Error::Argument` if `fft_size` is
+/// not a power of two of at least 2, or `samples` holds less than one block.
+#[cfg(feature = "dsp")]
+pub fn psd_dbfs(samples: &[Complex<f32>], fft_size: usize) -> Result<Vec<f32>> {
+    if fft_size < 2 || !fft_size.is_power_of_two() {
+        return Err(Error::Argument(format!(
+            "FFT size {fft_size} is not a power of two"
+        )));
+    }
+    if samples.len() < fft_size {
+        return Err(Error::Argument(format!(
+            "PSD needs at least {fft_size} samples, got {}",
+            samples.len()
+        )));
+    }
+
+    let window: Vec<f32> = (0..fft_size)
+        .map(|n| 0.5 - 0.5 * (TAU * n as f32 / fft_size as f32).cos())
+        .collect();
+    let gain: f32 = window.iter().sum();
+
+    let mut power = vec![0f64; fft_size];
+    let mut block = vec![Complex::default(); fft_size];
+    let blocks = samples.chunks_exact(fft_size);
+    let count = blocks.len();
+    for chunk in blocks {
+        for ((b, s), w) in block.iter_mut().zip(chunk).zip(&window) {
+            *b = s * w;
+        }
+        fft(&mut block);
+        for (p, b) in power.iter_mut().zip(&block) {
+            *p += (b.norm_sqr() / (gain * gain)) as f64;
+        }
+    }
+
+    power.rotate_left(fft_size / 2);
+    Ok(power
+        .into_iter()
+        .map(|p| 10.0 * ((p / count as f64) as f32).log10())
+        .collect())
+}
+
+/// In-place iterative radix-2 FFT. `buf.len()` must be a power of two.
+#[cfg(feature = "dsp")]
+fn fft(buf: &mut [Complex<f32>]) {
+    let n = buf.len();
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            buf.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let step = Complex::from_polar(1.0, -TAU / len as f32);
+        for start in (0..n).step_by(len) {
+            let mut twiddle = Complex::new(1.0, 0.0);
+            for k in 0..len / 2 {
+                let even = buf[start + k];
+                let odd = buf[start + k + len / 2] * twiddle;
+                buf[start + k] = even + odd;
+                buf[start + k + len / 2] = even - odd;
+                twiddle *= step;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+impl RfLinkSession<'_> {
+    /// Captures `num_samples` RX samples with `capture()` and returns their
+    /// average power in dBFS, computed in the time domain by `power_dbfs()`.
+    ///
+    /// If the capture times out, the power of the samples received until
+    /// then is returned.
+    ///
+    /// Returns `Error::Unsupported` for `Channel::Tx`, `Error::Timeout` if no
+    /// samples arrive within one second, and `Error::WrongState` if the
+    /// board has not been initialized.
+    pub fn measure_power(&mut self, channel: Channel, num_samples: usize) -> Result<f32> {
+        let samples = self.capture_for_power(channel, num_samples)?;
+        Ok(power_dbfs(&samples))
+    }
+
+    /// Captures `num_samples` RX samples with `capture()` and returns their
+    /// power spectral density as computed by `psd_dbfs()`.
+    ///
+    /// Requires the `dsp` feature. Returns `Error::Unsupported` for
+    /// `Channel::Tx`, `Error::Argument` for an invalid `fft_size` or fewer
+    /// than `fft_size` received samples, `Error::Timeout` if no samples
+    /// arrive within one second, and `Error::WrongState` if the board has
+    /// not been initialized.
+    #[cfg(feature = "dsp")]
+    pub fn measure_psd(
+        &mut self,
+        channel: Channel,
+        num_samples: usize,
+        fft_size: usize,
+    ) -> Result<Vec<f32>> {
+        let samples = self.capture_for_power(channel, num_samples)?;
+        psd_dbfs(&samples, fft_size)
+    }
+
+    fn capture_for_power(
+        &mut self,
+        channel: Channel,
+        num_samples: usize,
+    ) -> Result<Vec<Complex<f32>>> {
+        if channel.is_tx() {
+            return Err(Error::Unsupported("power measurement on TX"));
+        }
+        let capture = self.capture(num_samples, POWER_TIMEOUT)?;
+        if capture.samples.is_empty() {
+            return Err(Error::Timeout);
+        }
+        Ok(capture.samples)
+    }
+}
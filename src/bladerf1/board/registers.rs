@@ -0,0 +1,74 @@
+//! Raw register access for debugging.
+//!
+//! Thin wrappers that read and write LMS6002D, Si5338 and DAC161S055
+//! registers directly over NIOS, mirroring libbladeRF's `bladerf_lms_read`
+//! family. They bypass every driver-side check and cached setting, so a
+//! write can leave the board in a state the other session methods do not
+//! expect. Only available with the `direct-register-access` feature.
+
+use crate::bladerf1::board::RfLinkSession;
+use crate::error::Result;
+
+impl RfLinkSession<'_> {
+    /// Reads LMS6002D register `addr`.
+    ///
+    /// A read-modify-write enabling the TX and RX DSM SPI clocks in the
+    /// clock-enable register 0x09:
+    ///
+    /// ```no_run
+    /// use libbladerf_rs::bladerf1::BladeRf1;
+    ///
+    /// let mut sdr = BladeRf1::from_first()?;
+    /// let mut rf = sdr.rf_link_session()?;
+    /// let clk_en = rf.lms_read(0x09)?;
+    /// rf.lms_write(0x09, clk_en | 0x05)?;
+    /// # Ok::<(), libbladerf_rs::Error>(())
+    /// ```
+    pub fn lms_read(&mut self, addr: u8) -> Result<u8> {
+        self.lms().read(addr)
+    }
+
+    /// Writes `data` to LMS6002D register `addr`. See [`lms_read`](Self::lms_read)
+    /// for a read-modify-write example.
+    pub fn lms_write(&mut self, addr: u8, data: u8) -> Result<()> {
+        self.lms().write(addr, data)
+    }
+
+    /// Reads the LMS6002D registers in `LMS_REG_DUMPSET` as
+    /// `(address, value)` pairs in address order, e.g. to compare the
+    /// configuration against libbladeRF register by register.
+    pub fn lms_dump_registers(&mut self) -> Result<Vec<(u8, u8)>> {
+        self.lms().dump_registers()
+    }
+
+    /// Reads Si5338 register `addr`.
+    pub fn si5338_read(&mut self, addr: u8) -> Result<u8> {
+        self.si().read(addr)
+    }
+
+    /// Writes `data` to Si5338 register `addr`.
+    pub fn si5338_write(&mut self, addr: u8, data: u8) -> Result<()> {
+        self.si().write(addr, data)
+    }
+
+    /// Reads the Si5338 registers in `SI5338_REG_DUMPSET` as
+    /// `(address, value)` pairs, e.g. to diff the clock configuration before
+    /// and after `set_sample_rate()`.
+    pub fn si5338_dump_registers(&mut self) -> Result<Vec<(u8, u8)>> {
+        self.si().dump_registers()
+    }
+
+    /// Writes back Si5338 registers from `si5338_dump_registers()`, skipping
+    /// the status registers in `SI5338_VOLATILE_REGS`.
+    pub fn si5338_restore_registers(&mut self, regs: &[(u8, u8)]) -> Result<()> {
+        self.si().restore_registers(regs)
+    }
+
+    /// Writes a raw code to the DAC161S055 VCTCXO trim DAC.
+    ///
+    /// Unlike [`set_dac_trim`](Self::set_dac_trim), this does not require the
+    /// board to be initialized.
+    pub fn dac_write(&mut self, value: u16) -> Result<()> {
+        self.dac().write(value)
+    }
+}
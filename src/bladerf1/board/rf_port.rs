@@ -144,7 +144,7 @@ impl RfLinkSession<'_> {
     /// converted to a PA selection. Returns `Error::Argument` if the port
     /// is not valid for the channel.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn set_rf_port(&mut self, channel: Channel, port: RfPort) -> Result<()> {
         self.require_initialized()?;
         if !port.is_valid_for(channel) {
@@ -167,7 +167,7 @@ impl RfLinkSession<'_> {
     /// Reads the active LNA selection on RX or PA selection on TX and
     /// converts it to the corresponding `RfPort`.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn get_rf_port(&mut self, channel: Channel) -> Result<RfPort> {
         self.require_initialized()?;
         match channel {
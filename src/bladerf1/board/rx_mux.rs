@@ -53,7 +53,7 @@ impl RfLinkSession<'_> {
     /// The board must be initialized. The mux is configured via the config GPIO
     /// register using an atomic read-modify-write.
     ///
-    /// Returns `Error::BoardState` if the board is not initialized.
+    /// Returns `Error::WrongState` if the board is not initialized.
     pub fn set_rx_mux(&mut self, mode: RxMux) -> Result<()> {
         self.require_initialized()?;
         let rx_mux_val = (mode as u32) << BLADERF_GPIO_RX_MUX_SHIFT;
@@ -63,7 +63,7 @@ impl RfLinkSession<'_> {
     /// Returns the currently selected RX input mux.
     ///
     /// Reads the config GPIO register and extracts the RX mux field.
-    /// Returns `Error::BoardState` if the board is not initialized.
+    /// Returns `Error::WrongState` if the board is not initialized.
     pub fn get_rx_mux(&mut self) -> Result<RxMux> {
         self.require_initialized()?;
         let mut config_gpio = self.config_gpio_read()?;
@@ -3,26 +3,53 @@
 //! Configures the Si5338 MultiSynth clocks to produce the desired sample rate.
 //! The Si5338 supports arbitrary rational rates via `RationalRate`, allowing
 //! precise frequency synthesis beyond integer sample rates.
+//!
+//! The Si5338 is programmed over I2C and a rate change leaves the config
+//! GPIO alone, so streams with metadata keep their packet and timestamp
+//! bits across it.
 
 use crate::bladerf1::board::RfLinkSession;
 use crate::bladerf1::hardware::si5338;
 use crate::channel::Channel;
 use crate::error::Result;
+use crate::log_target;
 use crate::range::{Range, RangeItem};
 impl RfLinkSession<'_> {
     /// Sets the sample rate for the given channel in samples per second.
     ///
     /// Programs the Si5338 MultiSynth clock to the desired integer rate.
-    /// Returns the actual rate applied, which may differ if rounding is needed.
+    /// Returns the integer part of the rate actually applied, which callers
+    /// may ignore. In-range integer rates are reproduced exactly by the
+    /// fractional MultiSynth; use `set_rational_sample_rate()` to see any
+    /// fractional remainder.
+    ///
+    /// The packet and timestamp bits of the config GPIO are not touched, so
+    /// a rate change while streaming with metadata keeps the timestamp
+    /// format. Timestamps still count samples, so times derived from them
+    /// change scale with the rate. See `BLADERF_GPIO_TIMESTAMP_DIV2` for the
+    /// FPGA version the timestamp bits require.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::Argument` if `rate` is outside
+    /// `get_sample_rate_range()`.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn set_sample_rate(&mut self, channel: Channel, rate: u32) -> Result<u32> {
+        Self::check_sample_rate(rate)?;
         self.require_initialized()?;
         self.si().set_sample_rate(channel, rate)
     }
+    /// Returns `Error::Argument` if `rate` is outside `get_sample_rate_range()`.
+    pub fn check_sample_rate(rate: u32) -> Result<()> {
+        Self::get_sample_rate_range()
+            .check(rate as f64, "sample rate")
+            .inspect_err(|e| log::error!(target: log_target::BOARD, "{e}"))
+    }
     /// Returns the current sample rate for the given channel in samples per second.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Derived from the rational rate read back from the Si5338, rounded to
+    /// the nearest integer. Use `get_rational_sample_rate()` for the exact value.
+    ///
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn get_sample_rate(&mut self, channel: Channel) -> Result<u32> {
         self.require_initialized()?;
         self.si().get_sample_rate(channel)
@@ -36,6 +63,13 @@ impl RfLinkSession<'_> {
             1f64,
         )])
     }
+    /// Returns the sample rate range in samples per second supported by
+    /// this device.
+    ///
+    /// See `get_sample_rate_range()`, which holds the Si5338 limits.
+    pub fn sample_rate_range(&self) -> Range {
+        Self::get_sample_rate_range()
+    }
     /// Sets the sample rate for the given channel using a rational number.
     ///
     /// The `RationalRate` provides exact clock configuration via numerator,
@@ -43,9 +77,13 @@ impl RfLinkSession<'_> {
     /// precise non-integer sample rates. The input rate is normalized and
     /// updated with the actual applied values.
     ///
-    /// Returns the actual `RationalRate` applied by the hardware.
+    /// Returns the actual `RationalRate` applied by the hardware. As with
+    /// `set_sample_rate()`, the packet and timestamp GPIO bits are kept.
+    ///
+    /// Returns `Error::InvalidSampleRate` if the denominator is zero or the rate
+    /// lies outside the range reported by `get_sample_rate_range()`.
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn set_rational_sample_rate(
         &mut self,
         channel: Channel,
@@ -59,7 +97,7 @@ impl RfLinkSession<'_> {
     /// Reads the Si5338 MultiSynth registers and returns the actual rate as
     /// a `RationalRate` (numerator, denominator, post-divider).
     ///
-    /// Returns `Error::NotInitialized` if the board has not been initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized.
     pub fn get_rational_sample_rate(&mut self, channel: Channel) -> Result<si5338::RationalRate> {
         self.require_initialized()?;
         self.si().get_rational_sample_rate(channel)
@@ -17,7 +17,7 @@ impl RfLinkSession<'_> {
     /// Routes the Si5338 multisynth output to the SMB connector, configures
     /// the connector as an external clock input, or disables SMB operation.
     ///
-    /// Returns `Error::BoardState` if the board is not initialized.
+    /// Returns `Error::WrongState` if the board is not initialized.
     pub fn set_smb_mode(&mut self, mode: si5338::SmbMode) -> Result<()> {
         self.require_initialized()?;
         self.si().set_smb_mode(mode)
@@ -25,7 +25,7 @@ impl RfLinkSession<'_> {
 
     /// Returns the current SMB clock mode.
     ///
-    /// Returns `Error::BoardState` if the board is not initialized.
+    /// Returns `Error::WrongState` if the board is not initialized.
     pub fn get_smb_mode(&mut self) -> Result<si5338::SmbMode> {
         self.require_initialized()?;
         self.si().get_smb_mode()
@@ -37,7 +37,7 @@ impl RfLinkSession<'_> {
     /// (rounded to the nearest achievable value). The actual achieved
     /// frequency is returned.
     ///
-    /// Returns `Error::BoardState` if the board is not initialized.
+    /// Returns `Error::WrongState` if the board is not initialized.
     pub fn set_smb_freq(&mut self, rate: u32) -> Result<u32> {
         self.require_initialized()?;
         self.si().set_smb_freq(rate)
@@ -45,7 +45,7 @@ impl RfLinkSession<'_> {
 
     /// Returns the current SMB output frequency in Hz.
     ///
-    /// Returns `Error::BoardState` if the board is not initialized.
+    /// Returns `Error::WrongState` if the board is not initialized.
     pub fn get_smb_freq(&mut self) -> Result<u32> {
         self.require_initialized()?;
         self.si().get_smb_freq()
@@ -57,7 +57,7 @@ impl RfLinkSession<'_> {
     /// represented as a simple integer. Returns the actual achieved rational
     /// rate.
     ///
-    /// Returns `Error::BoardState` if the board is not initialized.
+    /// Returns `Error::WrongState` if the board is not initialized.
     pub fn set_rational_smb_freq(
         &mut self,
         rate: si5338::RationalRate,
@@ -68,7 +68,7 @@ impl RfLinkSession<'_> {
 
     /// Returns the current SMB output rate as a rational numerator/denominator.
     ///
-    /// Returns `Error::BoardState` if the board is not initialized.
+    /// Returns `Error::WrongState` if the board is not initialized.
     pub fn get_rational_smb_freq(&mut self) -> Result<si5338::RationalRate> {
         self.require_initialized()?;
         self.si().get_rational_smb_freq()
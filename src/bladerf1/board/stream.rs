@@ -6,7 +6,8 @@
 //!    buffers (RX) or begins the send/receive loop.
 //! 3. `stop()` or `close()` — tears down the stream: cancels pending
 //!    transfers, disables the module, drains cancelled buffers, clears
-//!    halt, and deconfigures format GPIO bits.
+//!    halt, and deconfigures format GPIO bits (re-applying the other
+//!    direction's format if it still has a stream open).
 //!
 //! `RxStream` and `TxStream` own a `BufferPool` wrapping an nusb `Endpoint`
 //! and a pool of reusable `Buffer` instances. No `Drop` impl is provided on
@@ -15,9 +16,12 @@
 use crate::bladerf1::board::RfLinkSession;
 use crate::channel::Channel;
 use crate::error::{Error, Result};
-use nusb::MaybeFuture;
+use crate::log_target;
+use num_complex::Complex;
 use nusb::transfer::{Buffer, Bulk, Completion, EndpointDirection, In, Out, TransferError};
+use nusb::{MaybeFuture, Speed};
 use std::collections::VecDeque;
+use std::ops::{BitOr, BitOrAssign};
 use std::time::Duration;
 
 /// Zero-copy buffer pool wrapping an nusb Bulk `Endpoint`.
@@ -64,6 +68,27 @@ impl<Dir: EndpointDirection> BufferPool<Dir> {
         self.endpoint.pending()
     }
 
+    /// Resizes the pool to `buffer_count` buffers.
+    ///
+    /// Only allowed while no transfers are in flight and every buffer has
+    /// been returned to the pool. `buffer_count` is capped at
+    /// [`MAX_BUFFER_COUNT`].
+    pub(crate) fn set_buffer_count(&mut self, buffer_count: usize) -> Result<()> {
+        let buffer_count = check_buffer_count(buffer_count)?;
+        if self.endpoint.pending() > 0 || self.available.len() != self.buffer_count {
+            return Err(Error::BoardState(
+                "cannot change buffer count while transfers are in flight",
+            ));
+        }
+        while self.available.len() < buffer_count {
+            let buffer = self.endpoint.allocate(self.buffer_size);
+            self.available.push_back(buffer);
+        }
+        self.available.truncate(buffer_count);
+        self.buffer_count = buffer_count;
+        Ok(())
+    }
+
     fn submit(&mut self, buffer: Buffer) {
         self.endpoint.submit(buffer);
     }
@@ -80,6 +105,16 @@ impl<Dir: EndpointDirection> BufferPool<Dir> {
         self.endpoint.wait_next_complete(timeout)
     }
 
+    /// Awaits the next completed transfer, or returns `None` immediately if
+    /// no transfers are pending.
+    #[cfg(feature = "async")]
+    async fn next_completion(&mut self) -> Option<Completion> {
+        if self.endpoint.pending() == 0 {
+            return None;
+        }
+        Some(self.endpoint.next_complete().await)
+    }
+
     fn recycle(&mut self, mut buffer: Buffer) {
         buffer.clear();
         self.available.push_back(buffer);
@@ -106,6 +141,7 @@ impl<Dir: EndpointDirection> BufferPool<Dir> {
             let timeout = remaining.min(Duration::from_secs(1));
             if timeout.is_zero() {
                 log::warn!(
+                    target: log_target::STREAM,
                     "Timeout collecting cancelled transfers, {} remain",
                     self.endpoint.pending()
                 );
@@ -115,7 +151,10 @@ impl<Dir: EndpointDirection> BufferPool<Dir> {
                 match completion.status {
                     Ok(()) | Err(nusb::transfer::TransferError::Cancelled) => {}
                     Err(e) => {
-                        log::warn!("Transfer error during deactivation: {e}");
+                        log::warn!(
+                            target: log_target::STREAM,
+                            "Transfer error during deactivation: {e}"
+                        );
                     }
                 }
                 let mut buf = completion.buffer;
@@ -163,8 +202,16 @@ impl<Dir: EndpointDirection> BufferPool<Dir> {
 /// Construct via `RxStream::builder()`. The stream follows the
 /// build → start → read/recycle → close lifecycle. No `Drop`
 /// teardown is performed; call `close()` for clean resource release.
+///
+/// `read()` resubmits recycled buffers on its own. For explicit control of
+/// the in-flight transfers, use `poll_complete()` and `submit()` instead:
+/// `start()` queues every buffer, and each buffer returned by
+/// `poll_complete()` is only queued again when passed to `submit()`.
 pub struct RxStream {
     pool: Option<BufferPool<In>>,
+    format: SampleFormat,
+    message_size: usize,
+    overruns: u64,
 }
 
 /// Transmit stream backed by a pool of Bulk-OUT buffers.
@@ -174,6 +221,42 @@ pub struct RxStream {
 /// teardown is performed; call `close()` for clean resource release.
 pub struct TxStream {
     pool: Option<BufferPool<Out>>,
+    format: SampleFormat,
+    message_size: usize,
+    next_timestamp: Option<u64>,
+}
+
+/// Sample formats claimed by the RX and TX streams, indexed by
+/// `Channel as usize`.
+///
+/// Both directions share the format GPIO bits, so a stream claims its format
+/// when it is built or started and releases it when it is stopped or closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StreamFormats([Option<SampleFormat>; 2]);
+
+impl StreamFormats {
+    /// Returns the format claimed by `channel`, if any.
+    pub fn get(&self, channel: Channel) -> Option<SampleFormat> {
+        self.0[channel as usize]
+    }
+
+    /// Records `format` as `channel`'s format. Claiming again, e.g. when a
+    /// stopped stream is restarted, re-checks it against the other direction.
+    ///
+    /// Returns `Error::Argument` if `format` conflicts with the other
+    /// direction's claim (see `RfLinkSession::check_format_conflict()`).
+    pub fn claim(&mut self, channel: Channel, format: SampleFormat) -> Result<()> {
+        RfLinkSession::check_format_conflict(channel, format, self.0[1 - channel as usize])?;
+        self.0[channel as usize] = Some(format);
+        Ok(())
+    }
+
+    /// Forgets `channel`'s claim and returns the other direction's format,
+    /// which is the one the format GPIO bits must be left in.
+    pub fn release(&mut self, channel: Channel) -> Option<SampleFormat> {
+        self.0[channel as usize] = None;
+        self.0[1 - channel as usize]
+    }
 }
 
 /// I/Q sample format for streaming.
@@ -200,6 +283,10 @@ pub const BLADERF_GPIO_PACKET: u32 = 1 << 19;
 /// GPIO bit that enables per-transfer timestamp metadata.
 pub const BLADERF_GPIO_TIMESTAMP: u32 = 1 << 16;
 /// GPIO bit that halves the timestamp counter rate.
+///
+/// Both timestamp bits are only honoured by FPGA images that provide
+/// `Capability::Timestamps` (v0.1.0 or newer); older images ignore them and
+/// stream without usable timestamps.
 pub const BLADERF_GPIO_TIMESTAMP_DIV2: u32 = 1 << 17;
 /// GPIO bit that enables 8-bit sample mode (Sc8Q7).
 pub const BLADERF_GPIO_8BIT_MODE: u32 = 1 << 20;
@@ -209,6 +296,148 @@ pub const BLADERF_GPIO_HIGHLY_PACKED_MODE: u32 = 1 << 21;
 /// Size of the metadata header in bytes for *-Meta formats.
 pub const METADATA_HEADER_SIZE: usize = 16;
 
+/// Upper bound on the number of buffers in a stream's pool.
+pub const MAX_BUFFER_COUNT: usize = 64;
+
+/// Returns the stream block size in bytes for the given USB speed.
+///
+/// A block is two USB packets: 2048 bytes (512 Sc16Q11 samples) at
+/// SuperSpeed and 1024 bytes (256 samples) at Hi-Speed. It is also the
+/// message size of the *-Meta formats, so buffers must hold whole blocks.
+///
+/// Returns `Error::UnsupportedSpeed` below Hi-Speed.
+pub fn stream_block_size(speed: Speed) -> Result<usize> {
+    match speed {
+        Speed::Super | Speed::SuperPlus => Ok(2048),
+        Speed::High => Ok(1024),
+        _ => Err(Error::UnsupportedSpeed),
+    }
+}
+
+/// Rounds `size` up to a whole number of stream blocks for `speed`,
+/// logging a warning if it had to be changed.
+///
+/// Returns `Error::Argument` if `size` is zero and
+/// `Error::UnsupportedSpeed` below Hi-Speed.
+pub fn align_buffer_size(size: usize, speed: Speed) -> Result<usize> {
+    if size == 0 {
+        return Err(Error::Argument("buffer size must be non-zero".into()));
+    }
+    let block = stream_block_size(speed)?;
+    let aligned = size.next_multiple_of(block);
+    if aligned != size {
+        log::warn!(
+            target: log_target::STREAM,
+            "buffer_size {size} is not a multiple of the {block} byte block size, using {aligned}"
+        );
+    }
+    Ok(aligned)
+}
+
+/// Caps `count` at [`MAX_BUFFER_COUNT`], logging a warning if it had to be
+/// changed.
+///
+/// Returns `Error::Argument` if `count` is zero.
+pub fn check_buffer_count(count: usize) -> Result<usize> {
+    if count == 0 {
+        return Err(Error::Argument("buffer count must be non-zero".into()));
+    }
+    if count > MAX_BUFFER_COUNT {
+        log::warn!(
+            target: log_target::STREAM,
+            "buffer_count {count} exceeds {MAX_BUFFER_COUNT}, using {MAX_BUFFER_COUNT}"
+        );
+        return Ok(MAX_BUFFER_COUNT);
+    }
+    Ok(count)
+}
+
+/// How `TxStream::write()` bridges the samples between the end of the
+/// current burst and a later requested timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxGap {
+    /// The timestamp continues the burst directly.
+    None,
+    /// The gap is shorter than one message and is filled with this many
+    /// zero samples, so the burst continues.
+    ZeroFill(u64),
+    /// The current burst is ended with a zero-filled `TX_BURST_END` message
+    /// and a new burst starts at the timestamp.
+    Restart,
+}
+
+impl TxGap {
+    /// Classifies the gap between `next`, the end of the samples already
+    /// scheduled, and the requested timestamp `ts`, for messages of
+    /// `samples_per_message` samples. `ts` must not precede `next`.
+    pub fn between(next: u64, ts: u64, samples_per_message: u64) -> Self {
+        match ts.saturating_sub(next) {
+            0 => TxGap::None,
+            gap if gap < samples_per_message => TxGap::ZeroFill(gap),
+            _ => TxGap::Restart,
+        }
+    }
+}
+
+/// Metadata flags carried in the `meta_flags` field of a [`MetadataHeader`].
+///
+/// TX flags mark the start and end of a timed burst; RX flags report
+/// hardware conditions such as underflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct BladeRfMetaFlags(u32);
+
+impl BladeRfMetaFlags {
+    /// Marks the first message of a TX burst.
+    pub const TX_BURST_START: Self = Self(1 << 0);
+    /// Marks the final message of a TX burst.
+    pub const TX_BURST_END: Self = Self(1 << 1);
+    /// Transmits immediately instead of waiting for the header timestamp.
+    pub const TX_NOW: Self = Self(1 << 2);
+    /// Updates the TX timestamp without ending the burst.
+    pub const TX_UPDATE_TIMESTAMP: Self = Self(1 << 3);
+    /// Reports that the RX FIFO underflowed before this message.
+    pub const RX_HW_UNDERFLOW: Self = Self(1 << 0);
+    /// Miniexpansion GPIO 1 state sampled with this message.
+    pub const RX_HW_MINIEXP1: Self = Self(1 << 16);
+    /// Miniexpansion GPIO 2 state sampled with this message.
+    pub const RX_HW_MINIEXP2: Self = Self(1 << 17);
+    /// Requests samples starting from the current RX timestamp.
+    pub const RX_NOW: Self = Self(1 << 31);
+
+    /// Returns an empty flag set.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Creates a flag set from raw header bits.
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw header bits.
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Returns `true` if all flags in `other` are set.
+    pub const fn contains(self, other: Self) -> bool {
+        (self.0 & other.0) == other.0
+    }
+}
+
+impl BitOr for BladeRfMetaFlags {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for BladeRfMetaFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
 /// Metadata header prepended to transfers using *-Meta sample formats.
 ///
 /// Each field serves a dual purpose depending on whether the format
@@ -247,6 +476,16 @@ impl MetadataHeader {
         Some(unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const Self) })
     }
 
+    /// Serializes the header into its little-endian wire representation.
+    pub fn to_bytes(&self) -> [u8; METADATA_HEADER_SIZE] {
+        let mut bytes = [0u8; METADATA_HEADER_SIZE];
+        bytes[0..2].copy_from_slice(&{ self.reserved_or_length }.to_le_bytes());
+        bytes[2..4].copy_from_slice(&{ self.flags_or_core }.to_le_bytes());
+        bytes[4..12].copy_from_slice(&{ self.timestamp }.to_le_bytes());
+        bytes[12..16].copy_from_slice(&{ self.meta_flags }.to_le_bytes());
+        bytes
+    }
+
     /// Returns the 40-bit hardware timestamp from the header.
     pub fn timestamp(&self) -> u64 {
         self.timestamp
@@ -287,6 +526,102 @@ impl MetadataHeader {
     pub fn packet_flags(&self) -> u8 {
         self.flags_or_core as u8
     }
+
+    /// Creates a `PacketMeta` header for a payload of `length` 32-bit words.
+    pub fn packet(length: u16, core_id: u8, flags: u8, timestamp: u64) -> Self {
+        Self::new(length, ((core_id as u16) << 8) | flags as u16, timestamp, 0)
+    }
+
+    /// Counts the messages in `buf` whose header carries all of `flags`.
+    ///
+    /// `buf` is split into `message_size` byte messages, each starting with a
+    /// metadata header. A trailing partial message is ignored.
+    pub fn count_flagged(buf: &[u8], message_size: usize, flags: BladeRfMetaFlags) -> usize {
+        buf.chunks_exact(message_size)
+            .filter_map(Self::from_bytes)
+            .filter(|header| BladeRfMetaFlags::from_bits(header.meta_flags()).contains(flags))
+            .count()
+    }
+}
+
+/// A packet exchanged with an FPGA digital core in `PacketMeta` mode.
+///
+/// On the wire a packet is a [`MetadataHeader`] carrying the payload length
+/// in 32-bit words, the core ID and flags, and the timestamp, followed by
+/// the little-endian payload words. Each packet occupies one USB transfer.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Packet {
+    /// ID of the FPGA core the packet is addressed to or came from.
+    pub core_id: u8,
+    /// Core-specific packet flags.
+    pub flags: u8,
+    /// Hardware timestamp of the packet.
+    pub timestamp: u64,
+    /// Payload as 32-bit words.
+    pub payload: Vec<u32>,
+}
+
+impl Packet {
+    /// Serializes the header and payload into their wire representation.
+    ///
+    /// Returns `Error::Argument` if the payload exceeds the 16-bit word
+    /// count of the header.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let length = u16::try_from(self.payload.len()).map_err(|_| {
+            Error::Argument(format!(
+                "packet payload of {} words exceeds {}",
+                self.payload.len(),
+                u16::MAX
+            ))
+        })?;
+        let header = MetadataHeader::packet(length, self.core_id, self.flags, self.timestamp);
+        let mut bytes = Vec::with_capacity(METADATA_HEADER_SIZE + 4 * self.payload.len());
+        bytes.extend_from_slice(&header.to_bytes());
+        for word in &self.payload {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        Ok(bytes)
+    }
+
+    /// Parses a packet from a received transfer.
+    ///
+    /// Bytes after the payload length given in the header are ignored.
+    ///
+    /// Returns `Error::Argument` if `bytes` is shorter than a header, is not
+    /// a whole number of 32-bit words, or holds fewer words than the header
+    /// announces.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let header = MetadataHeader::from_bytes(bytes).ok_or_else(|| {
+            Error::Argument(format!(
+                "packet of {} bytes is shorter than its header",
+                bytes.len()
+            ))
+        })?;
+        if !bytes.len().is_multiple_of(4) {
+            return Err(Error::Argument(format!(
+                "packet of {} bytes is not a whole number of 32-bit words",
+                bytes.len()
+            )));
+        }
+        let words = header.packet_length() as usize;
+        let payload = bytes[METADATA_HEADER_SIZE..]
+            .get(..4 * words)
+            .ok_or_else(|| {
+                Error::Argument(format!(
+                    "packet header announces {words} words but only {} were received",
+                    (bytes.len() - METADATA_HEADER_SIZE) / 4
+                ))
+            })?;
+        Ok(Self {
+            core_id: header.packet_core_id(),
+            flags: header.packet_flags(),
+            timestamp: header.timestamp(),
+            payload: payload
+                .chunks_exact(4)
+                .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+                .collect(),
+        })
+    }
 }
 
 #[inline(always)]
@@ -304,6 +639,51 @@ impl SampleFormat {
         }
     }
 
+    /// Returns the number of bytes needed to stream `num_samples` samples on
+    /// each of `num_channels` channels at the given USB speed.
+    ///
+    /// For the metadata formats every stream block (see
+    /// [`stream_block_size`]) starts with a [`METADATA_HEADER_SIZE`] byte
+    /// header, so the result is a whole number of blocks. For example, 2048
+    /// Sc16Q11 samples on one channel take 8192 bytes, while 2048
+    /// Sc16Q11Meta samples at SuperSpeed take five 2048 byte blocks.
+    ///
+    /// Returns `Error::UnsupportedSpeed` below Hi-Speed.
+    pub fn buffer_size_bytes(
+        self,
+        num_samples: usize,
+        num_channels: usize,
+        speed: Speed,
+    ) -> Result<usize> {
+        let total = num_samples * num_channels;
+        if self.sample_offset() == 0 {
+            return Ok(total * self.sample_size());
+        }
+        let block = stream_block_size(speed)?;
+        Ok(total.div_ceil(self.samples_per_message(block)) * block)
+    }
+
+    /// Returns the number of samples held by a buffer of `byte_len` bytes at
+    /// the given USB speed, excluding metadata headers. The inverse of
+    /// [`buffer_size_bytes`](SampleFormat::buffer_size_bytes) for one channel.
+    ///
+    /// Returns `Error::UnsupportedSpeed` below Hi-Speed.
+    pub fn samples_per_buffer(self, byte_len: usize, speed: Speed) -> Result<usize> {
+        let offset = self.sample_offset();
+        if offset == 0 {
+            return Ok(byte_len / self.sample_size());
+        }
+        let block = stream_block_size(speed)?;
+        let tail = (byte_len % block).saturating_sub(offset) / self.sample_size();
+        Ok(byte_len / block * self.samples_per_message(block) + tail)
+    }
+
+    /// Returns the number of samples in one `message_size` byte message,
+    /// after its metadata header for the metadata formats.
+    fn samples_per_message(self, message_size: usize) -> usize {
+        (message_size - self.sample_offset()) / self.sample_size()
+    }
+
     /// Unpacks Sc16Q11Packed data (3 bytes per sample) into standard Sc16Q11 (4 bytes per sample).
     /// `num_samples` must be a multiple of 2. Returns `Error::Argument` if buffers are too small.
     pub fn unpack_sc16q11_packed(src: &[u8], dst: &mut [u8], num_samples: usize) -> Result<()> {
@@ -382,6 +762,151 @@ impl SampleFormat {
 }
 
 impl SampleFormat {
+    /// Returns the number of bytes preceding the first sample of a message:
+    /// [`METADATA_HEADER_SIZE`] for the metadata formats, otherwise 0.
+    fn sample_offset(self) -> usize {
+        match self {
+            Self::Sc16Q11Meta | Self::Sc8Q7Meta => METADATA_HEADER_SIZE,
+            _ => 0,
+        }
+    }
+
+    fn check_planar(self, bytes: usize) -> Result<usize> {
+        if matches!(self, Self::Sc16Q11Packed | Self::PacketMeta) {
+            return Err(Error::Unsupported(
+                "planar conversion supports Sc16Q11 and Sc8Q7 formats only",
+            ));
+        }
+        let payload = bytes
+            .checked_sub(self.sample_offset())
+            .ok_or(Error::Argument(
+                "buffer is shorter than the metadata header".into(),
+            ))?;
+        if !payload.is_multiple_of(self.sample_size()) {
+            return Err(Error::Argument(
+                "sample data is not a whole number of samples".into(),
+            ));
+        }
+        Ok(payload / self.sample_size())
+    }
+
+    /// Splits interleaved I/Q sample bytes into planar I and Q slices.
+    ///
+    /// `src` holds one message in this format; for metadata formats the
+    /// leading [`MetadataHeader`] is skipped, so buffers containing several
+    /// messages must be split into messages first. Sc8Q7 components are
+    /// sign-extended to `i16`. Returns the number of samples written.
+    ///
+    /// Returns `Error::Argument` if `i_out` or `q_out` is too small, and
+    /// `Error::Unsupported` for `Sc16Q11Packed` and `PacketMeta`.
+    pub fn deinterleave(self, src: &[u8], i_out: &mut [i16], q_out: &mut [i16]) -> Result<usize> {
+        let num_samples = self.check_planar(src.len())?;
+        if i_out.len() < num_samples || q_out.len() < num_samples {
+            return Err(Error::Argument("destination buffer too small".into()));
+        }
+        let samples = src[self.sample_offset()..].chunks_exact(self.sample_size());
+        for ((s, i), q) in samples.zip(i_out.iter_mut()).zip(q_out.iter_mut()) {
+            if self.sample_size() == 4 {
+                *i = i16::from_le_bytes([s[0], s[1]]);
+                *q = i16::from_le_bytes([s[2], s[3]]);
+            } else {
+                *i = s[0] as i8 as i16;
+                *q = s[1] as i8 as i16;
+            }
+        }
+        Ok(num_samples)
+    }
+
+    /// Merges planar I and Q slices into interleaved sample bytes.
+    ///
+    /// The inverse of [`deinterleave`](SampleFormat::deinterleave). For
+    /// metadata formats the leading [`METADATA_HEADER_SIZE`] bytes of `dst`
+    /// are left untouched for the caller's header. Sc8Q7 components are
+    /// saturated to the `i8` range. Returns the number of samples written.
+    ///
+    /// Returns `Error::Argument` if `i_in` or `q_in` is too small, and
+    /// `Error::Unsupported` for `Sc16Q11Packed` and `PacketMeta`.
+    pub fn interleave(self, i_in: &[i16], q_in: &[i16], dst: &mut [u8]) -> Result<usize> {
+        let num_samples = self.check_planar(dst.len())?;
+        if i_in.len() < num_samples || q_in.len() < num_samples {
+            return Err(Error::Argument("source buffer too small".into()));
+        }
+        let sample_size = self.sample_size();
+        let samples = dst[self.sample_offset()..].chunks_exact_mut(sample_size);
+        for ((d, &i), &q) in samples.zip(i_in).zip(q_in) {
+            if sample_size == 4 {
+                d[..2].copy_from_slice(&i.to_le_bytes());
+                d[2..].copy_from_slice(&q.to_le_bytes());
+            } else {
+                d[0] = i.clamp(i8::MIN as i16, i8::MAX as i16) as i8 as u8;
+                d[1] = q.clamp(i8::MIN as i16, i8::MAX as i16) as i8 as u8;
+            }
+        }
+        Ok(num_samples)
+    }
+
+    /// Copies interleaved I/Q sample bytes into `Complex<i16>` values
+    /// without scaling.
+    ///
+    /// Same message handling as [`deinterleave`](SampleFormat::deinterleave):
+    /// the metadata header is skipped and Sc8Q7 components are sign-extended.
+    /// This is the fast path for recording raw samples. Returns the number
+    /// of samples written.
+    ///
+    /// Returns `Error::Argument` if `dst` is too small, and
+    /// `Error::Unsupported` for `Sc16Q11Packed` and `PacketMeta`.
+    pub fn to_complex_i16(self, src: &[u8], dst: &mut [Complex<i16>]) -> Result<usize> {
+        let num_samples = self.check_planar(src.len())?;
+        if dst.len() < num_samples {
+            return Err(Error::Argument("destination buffer too small".into()));
+        }
+        let samples = src[self.sample_offset()..].chunks_exact(self.sample_size());
+        for (s, d) in samples.zip(dst.iter_mut()) {
+            *d = if self.sample_size() == 4 {
+                Complex::new(
+                    i16::from_le_bytes([s[0], s[1]]),
+                    i16::from_le_bytes([s[2], s[3]]),
+                )
+            } else {
+                Complex::new(s[0] as i8 as i16, s[1] as i8 as i16)
+            };
+        }
+        Ok(num_samples)
+    }
+
+    /// Converts interleaved I/Q sample bytes into `Complex<f32>` values
+    /// normalized to the ADC full scale, so that `±1.0` is `±2048` for the
+    /// Sc16Q11 formats and `±128` for the Sc8Q7 formats.
+    ///
+    /// Otherwise identical to [`to_complex_i16`](SampleFormat::to_complex_i16).
+    ///
+    /// Returns `Error::Argument` if `dst` is too small, and
+    /// `Error::Unsupported` for `Sc16Q11Packed` and `PacketMeta`.
+    pub fn to_complex_f32(self, src: &[u8], dst: &mut [Complex<f32>]) -> Result<usize> {
+        let num_samples = self.check_planar(src.len())?;
+        if dst.len() < num_samples {
+            return Err(Error::Argument("destination buffer too small".into()));
+        }
+        let scale = if self.sample_size() == 4 {
+            1.0 / 2048.0
+        } else {
+            1.0 / 128.0
+        };
+        let samples = src[self.sample_offset()..].chunks_exact(self.sample_size());
+        for (s, d) in samples.zip(dst.iter_mut()) {
+            let (i, q) = if self.sample_size() == 4 {
+                (
+                    i16::from_le_bytes([s[0], s[1]]),
+                    i16::from_le_bytes([s[2], s[3]]),
+                )
+            } else {
+                (s[0] as i8 as i16, s[1] as i8 as i16)
+            };
+            *d = Complex::new(i as f32 * scale, q as f32 * scale);
+        }
+        Ok(num_samples)
+    }
+
     /// Returns `true` if this format requires timestamp metadata headers.
     pub fn requires_timestamps(self) -> bool {
         matches!(
@@ -423,7 +948,8 @@ pub struct RxStreamBuilder<'a, 'b> {
 }
 
 impl<'a, 'b> RxStreamBuilder<'a, 'b> {
-    /// Sets the buffer size in bytes. Aligned up to the endpoint's max packet size.
+    /// Sets the buffer size in bytes. Rounded up to a whole number of stream
+    /// blocks by `build()`; see [`align_buffer_size`].
     pub fn buffer_size(mut self, size: usize) -> Self {
         self.buffer_size = size;
         self
@@ -441,22 +967,33 @@ impl<'a, 'b> RxStreamBuilder<'a, 'b> {
 
     /// Builds the `RxStream`. Acquires the RX streaming endpoint, configures
     /// format GPIO bits, and allocates the buffer pool.
+    /// Returns `Error::Argument` if the format conflicts with an open
+    /// TX stream (see `check_format_conflict()`).
     /// Requires the board to be initialized. Returns `Error` on USB failure.
     pub fn build(self) -> Result<RxStream> {
         self.dev.require_initialized()?;
+        self.dev.ensure_rf_link()?;
         let endpoint = self.dev.nios.transport().acquire_streaming_rx_endpoint()?;
         let mps = endpoint.max_packet_size();
-        let buffer_size = self.buffer_size.next_multiple_of(mps);
+        let speed = self.dev.nios.transport().speed();
+        let buffer_size = align_buffer_size(self.buffer_size, speed)?;
+        let buffer_count = check_buffer_count(self.buffer_count)?;
         log::trace!(
+            target: log_target::STREAM,
             "Creating RxStream: buffer_size={}, buffer_count={}, format={:?}",
             buffer_size,
-            self.buffer_count,
+            buffer_count,
             self.format
         );
-        self.dev.perform_format_config(self.format)?;
-        let mut pool = BufferPool::new(endpoint, buffer_size, self.buffer_count);
+        self.dev.claim_stream_format(Channel::Rx, self.format)?;
+        let mut pool = BufferPool::new(endpoint, buffer_size, buffer_count);
         pool.clear_halt()?;
-        Ok(RxStream { pool: Some(pool) })
+        Ok(RxStream {
+            pool: Some(pool),
+            format: self.format,
+            message_size: 2 * mps,
+            overruns: 0,
+        })
     }
 }
 
@@ -478,13 +1015,18 @@ impl RxStream {
         dev.close_stream(Channel::Rx, &mut pool)
     }
 
-    /// Enables the RX streaming module and submits all buffers for incoming data.
-    /// Returns `Error` if the stream is already closed or the module fails to enable.
+    /// Re-claims the stream format released by `stop()`, enables the RX
+    /// streaming module and submits all buffers for incoming data.
+    /// Returns `Error::Argument` if a TX stream claimed a conflicting format
+    /// in the meantime, and `Error` if the stream is already closed or the
+    /// module fails to enable.
     pub fn start(&mut self, dev: &mut RfLinkSession<'_>) -> Result<()> {
+        self.pool_ref()?;
+        dev.claim_stream_format(Channel::Rx, self.format)?;
         dev.enable_module(Channel::Rx, true)?;
         dev.nios.stream_started();
         self.pool_mut()?.submit_all_available();
-        log::trace!("RxStream started");
+        log::trace!(target: log_target::STREAM, "RxStream started");
         Ok(())
     }
 
@@ -517,6 +1059,7 @@ impl RxStream {
         }
         completion.status?;
         self.pool_mut()?.drain_extras();
+        self.count_overruns(&completion.buffer);
         Ok(completion.buffer)
     }
 
@@ -533,9 +1076,132 @@ impl RxStream {
         }
         completion.status?;
         self.pool_mut()?.drain_extras();
+        self.count_overruns(&completion.buffer);
+        Ok(completion.buffer)
+    }
+
+    /// Awaits the next completed transfer buffer without blocking the thread.
+    ///
+    /// The async counterpart of `read()`. No timeout is applied; wrap the
+    /// future with the runtime's timeout if one is needed. Dropping the future
+    /// before it resolves leaves the transfers queued, so a later `read()` or
+    /// `read_async()` picks up where it left off.
+    ///
+    /// Returns `Error::NoTransfersPending` if every buffer is held by the
+    /// caller and none can be submitted.
+    #[cfg(feature = "async")]
+    pub async fn read_async(&mut self) -> Result<Buffer> {
+        let pool = self.pool_mut()?;
+        pool.submit_all_available();
+        let completion = pool
+            .next_completion()
+            .await
+            .ok_or(Error::NoTransfersPending)?;
+        if let Err(TransferError::Cancelled) = completion.status {
+            return Err(Error::Timeout);
+        }
+        completion.status?;
+        pool.drain_extras();
+        self.count_overruns(&completion.buffer);
         Ok(completion.buffer)
     }
 
+    fn count_overruns(&mut self, buf: &[u8]) {
+        if !matches!(
+            self.format,
+            SampleFormat::Sc16Q11Meta | SampleFormat::Sc8Q7Meta
+        ) {
+            return;
+        }
+        let count = MetadataHeader::count_flagged(
+            buf,
+            self.message_size,
+            BladeRfMetaFlags::RX_HW_UNDERFLOW,
+        );
+        if count > 0 {
+            log::warn!(target: log_target::STREAM, "RX overrun reported in {count} messages");
+            self.overruns += count as u64;
+        }
+    }
+
+    /// Reads the next buffer with `read()`, copies its samples into `dst`
+    /// with [`SampleFormat::to_complex_i16`] and recycles the buffer.
+    ///
+    /// For metadata formats every message of the buffer is converted and
+    /// the headers are dropped. Returns the number of samples written.
+    ///
+    /// Returns `Error::Argument` if `dst` cannot hold a whole buffer, and
+    /// `Error::Unsupported` for `Sc16Q11Packed` and `PacketMeta`.
+    pub fn read_complex_i16(
+        &mut self,
+        dst: &mut [Complex<i16>],
+        timeout: Option<Duration>,
+    ) -> Result<usize> {
+        let buf = self.read(timeout)?;
+        let result = self.convert_messages(&buf, dst, SampleFormat::to_complex_i16);
+        self.recycle(buf);
+        result
+    }
+
+    /// Reads the next buffer with `read()`, converts its samples into `dst`
+    /// with [`SampleFormat::to_complex_f32`] and recycles the buffer.
+    ///
+    /// Otherwise identical to `read_complex_i16()`.
+    pub fn read_complex_f32(
+        &mut self,
+        dst: &mut [Complex<f32>],
+        timeout: Option<Duration>,
+    ) -> Result<usize> {
+        let buf = self.read(timeout)?;
+        let result = self.convert_messages(&buf, dst, SampleFormat::to_complex_f32);
+        self.recycle(buf);
+        result
+    }
+
+    /// Reads the next transfer with `read()` and parses it as a [`Packet`].
+    ///
+    /// Returns `Error::Unsupported` unless the stream uses
+    /// `SampleFormat::PacketMeta`, and `Error::Argument` for a malformed
+    /// packet (see `Packet::from_bytes()`).
+    pub fn recv_packet(&mut self, timeout: Option<Duration>) -> Result<Packet> {
+        if self.format != SampleFormat::PacketMeta {
+            return Err(Error::Unsupported("packet I/O requires PacketMeta"));
+        }
+        let buf = self.read(timeout)?;
+        let packet = Packet::from_bytes(&buf);
+        self.recycle(buf);
+        packet
+    }
+
+    fn convert_messages<T>(
+        &self,
+        buf: &[u8],
+        dst: &mut [T],
+        convert: fn(SampleFormat, &[u8], &mut [T]) -> Result<usize>,
+    ) -> Result<usize> {
+        let chunk = if self.format.requires_timestamps() {
+            self.message_size
+        } else {
+            buf.len().max(1)
+        };
+        let mut written = 0;
+        for message in buf.chunks(chunk) {
+            written += convert(self.format, message, &mut dst[written..])?;
+        }
+        Ok(written)
+    }
+
+    /// Returns the number of received messages whose header reported that
+    /// the FPGA's RX FIFO ran dry or dropped samples, i.e. that the host did
+    /// not keep up. Accumulated across `read()`, `try_read()` and
+    /// `read_async()` since the stream was built.
+    ///
+    /// Only meaningful for `Sc16Q11Meta` and `Sc8Q7Meta`; other formats carry
+    /// no per-message header and always report zero.
+    pub fn overruns(&self) -> u64 {
+        self.overruns
+    }
+
     /// Returns the configured buffer size in bytes.
     pub fn buffer_size(&self) -> Result<usize> {
         Ok(self.pool_ref()?.buffer_size())
@@ -546,12 +1212,80 @@ impl RxStream {
         Ok(self.pool_ref()?.buffer_count())
     }
 
+    /// Changes the number of buffers (in-flight USB transfers) in the pool.
+    ///
+    /// More buffers keep more transfers queued on the endpoint, which absorbs
+    /// scheduling jitter on the host and improves sustained throughput at
+    /// high sample rates. Fewer buffers reduce memory use and bound how much
+    /// stale data can queue up between the device and `read()`.
+    ///
+    /// Must be called while the stream is stopped and all buffers have been
+    /// returned; otherwise returns `Error::BoardState`. Returns
+    /// `Error::Argument` if `count` is zero.
+    pub fn set_buffer_count(&mut self, count: usize) -> Result<()> {
+        self.pool_mut()?.set_buffer_count(count)
+    }
+
     /// Returns a used buffer to the available pool for reuse.
     pub fn recycle(&mut self, buf: Buffer) {
         if let Some(ref mut pool) = self.pool {
             pool.recycle(buf);
         }
     }
+    /// Waits for the next completed transfer and returns its buffer without
+    /// queueing any other buffer, unlike `read()`.
+    ///
+    /// Together with `submit()` this gives explicit control over which
+    /// buffers are in flight. Once every buffer has been handed out and none
+    /// was resubmitted, no transfer is pending and `Error::NoTransfersPending`
+    /// is returned instead of waiting. `None` timeouts wait indefinitely.
+    ///
+    /// Returns `Error::Timeout` if no transfer completes within `timeout`,
+    /// and also for a cancelled transfer. The buffer of a cancelled or failed
+    /// transfer goes back to the pool, from which `start()` and `read()`
+    /// queue it again.
+    pub fn poll_complete(&mut self, timeout: Option<Duration>) -> Result<Buffer> {
+        let pool = self.pool_mut()?;
+        if pool.pending() == 0 {
+            return Err(Error::NoTransfersPending);
+        }
+        let completion = pool
+            .wait_completion(timeout.unwrap_or(Duration::MAX))
+            .ok_or(Error::Timeout)?;
+        if let Err(err) = completion.status {
+            pool.recycle(completion.buffer);
+            return Err(match err {
+                TransferError::Cancelled => Error::Timeout,
+                err => err.into(),
+            });
+        }
+        self.count_overruns(&completion.buffer);
+        Ok(completion.buffer)
+    }
+
+    /// Queues `buf` for another transfer of `buffer_size()` bytes.
+    ///
+    /// Returns `Error::Argument` if `buf` is smaller than `buffer_size()`,
+    /// e.g. a buffer taken from a different stream.
+    pub fn submit(&mut self, mut buf: Buffer) -> Result<()> {
+        let pool = self.pool_mut()?;
+        if buf.capacity() < pool.buffer_size {
+            return Err(Error::Argument(format!(
+                "buffer of {} bytes is smaller than the stream buffer size {}",
+                buf.capacity(),
+                pool.buffer_size
+            )));
+        }
+        buf.clear();
+        buf.set_requested_len(pool.buffer_size);
+        pool.submit(buf);
+        Ok(())
+    }
+
+    /// Returns the number of transfers currently queued on the endpoint.
+    pub fn pending(&self) -> Result<usize> {
+        Ok(self.pool_ref()?.pending())
+    }
 }
 
 /// Builder for configuring and constructing a `TxStream`.
@@ -563,13 +1297,14 @@ pub struct TxStreamBuilder<'a, 'b> {
 }
 
 impl<'a, 'b> TxStreamBuilder<'a, 'b> {
-    /// Sets the buffer size in bytes. Aligned up to the endpoint's max packet size.
+    /// Sets the buffer size in bytes. Rounded up to a whole number of stream
+    /// blocks by `build()`; see [`align_buffer_size`].
     pub fn buffer_size(mut self, size: usize) -> Self {
         self.buffer_size = size;
         self
     }
 
-    /// Sets the number of buffers in the pool.
+    /// Sets the number of buffers in the pool, capped at [`MAX_BUFFER_COUNT`].
     pub fn buffer_count(mut self, count: usize) -> Self {
         self.buffer_count = count;
         self
@@ -583,22 +1318,33 @@ impl<'a, 'b> TxStreamBuilder<'a, 'b> {
 
     /// Builds the `TxStream`. Acquires the TX streaming endpoint, configures
     /// format GPIO bits, and allocates the buffer pool.
+    /// Returns `Error::Argument` if the format conflicts with an open
+    /// RX stream (see `check_format_conflict()`).
     /// Requires the board to be initialized. Returns `Error` on USB failure.
     pub fn build(self) -> Result<TxStream> {
         self.dev.require_initialized()?;
+        self.dev.ensure_rf_link()?;
         let endpoint = self.dev.nios.transport().acquire_streaming_tx_endpoint()?;
         let mps = endpoint.max_packet_size();
-        let buffer_size = self.buffer_size.next_multiple_of(mps);
+        let speed = self.dev.nios.transport().speed();
+        let buffer_size = align_buffer_size(self.buffer_size, speed)?;
+        let buffer_count = check_buffer_count(self.buffer_count)?;
         log::trace!(
+            target: log_target::STREAM,
             "Creating TxStream: buffer_size={}, buffer_count={}, format={:?}",
             buffer_size,
-            self.buffer_count,
+            buffer_count,
             self.format
         );
-        self.dev.perform_format_config(self.format)?;
-        let mut pool = BufferPool::new(endpoint, buffer_size, self.buffer_count);
+        self.dev.claim_stream_format(Channel::Tx, self.format)?;
+        let mut pool = BufferPool::new(endpoint, buffer_size, buffer_count);
         pool.clear_halt()?;
-        Ok(TxStream { pool: Some(pool) })
+        Ok(TxStream {
+            pool: Some(pool),
+            format: self.format,
+            message_size: 2 * mps,
+            next_timestamp: None,
+        })
     }
 }
 
@@ -615,7 +1361,7 @@ impl TxStream {
     }
 
     /// Performs full stream teardown: disables the TX module, cancels pending
-    /// transfers, drains them, clears halt, and deconfigures format GPIO bits.
+    /// transfers, drains them, clears halt, and releases the stream format.
     /// Consumes the stream pool; subsequent calls return `Error::StreamClosed`.
     pub fn close(&mut self, dev: &mut RfLinkSession<'_>) -> Result<()> {
         let mut pool = self.pool.take().ok_or(Error::StreamClosed)?;
@@ -623,17 +1369,23 @@ impl TxStream {
         dev.close_stream(Channel::Tx, &mut pool)
     }
 
-    /// Enables the TX streaming module. Unlike RX, no automatic buffer submission occurs.
-    /// Returns `Error` if the stream is already closed or the module fails to enable.
+    /// Re-claims the stream format released by `stop()` and enables the TX
+    /// streaming module. Unlike RX, no automatic buffer submission occurs.
+    /// Returns `Error::Argument` if an RX stream claimed a conflicting format
+    /// in the meantime, and `Error` if the stream is already closed or the
+    /// module fails to enable.
     pub fn start(&mut self, dev: &mut RfLinkSession<'_>) -> Result<()> {
+        self.pool_ref()?;
+        dev.claim_stream_format(Channel::Tx, self.format)?;
         dev.enable_module(Channel::Tx, true)?;
         dev.nios.stream_started();
-        log::trace!("TxStream started");
+        log::trace!(target: log_target::STREAM, "TxStream started");
         Ok(())
     }
 
-    /// Stops the TX stream: disables the module and tears down transfers,
-    /// but retains the buffer pool so the stream can be restarted.
+    /// Stops the TX stream: disables the module, tears down transfers and
+    /// releases the stream format, but retains the buffer pool so the stream
+    /// can be restarted.
     pub fn stop(&mut self, dev: &mut RfLinkSession<'_>) -> Result<()> {
         let pool = self.pool_mut()?;
         dev.nios.stream_stopped();
@@ -674,6 +1426,28 @@ impl TxStream {
         }
     }
 
+    /// Awaits a buffer from the pool for filling with TX data.
+    ///
+    /// The async counterpart of `get_buffer()`. Returns a pooled buffer
+    /// immediately if one is available, otherwise awaits the next completed
+    /// transfer. Returns `Error::NoTransfersPending` if the pool is empty
+    /// and no transfers are pending.
+    #[cfg(feature = "async")]
+    pub async fn get_buffer_async(&mut self) -> Result<Buffer> {
+        let pool = self.pool_mut()?;
+        if let Some(buffer) = pool.pop_available() {
+            return Ok(buffer);
+        }
+        let completion = pool
+            .next_completion()
+            .await
+            .ok_or(Error::NoTransfersPending)?;
+        completion.status?;
+        let mut buf = completion.buffer;
+        buf.clear();
+        Ok(buf)
+    }
+
     /// Tries to get a buffer without blocking. Returns `Error::WouldBlock`
     /// if no buffer is immediately available in the pool.
     pub fn try_get_buffer(&mut self) -> Result<Buffer> {
@@ -695,6 +1469,222 @@ impl TxStream {
         Ok(())
     }
 
+    /// Writes Sc16Q11 sample bytes to the stream, splitting them across pool buffers.
+    ///
+    /// For `Sc16Q11Meta` streams the samples are framed into USB messages, each
+    /// prefixed with a [`MetadataHeader`] carrying its hardware timestamp, so the
+    /// FPGA transmits them at `timestamp` rather than on arrival. The first message
+    /// of a burst carries `TX_BURST_START`; when `end_burst` is set, the final
+    /// message carries `TX_BURST_END`. Passing `None` continues the current burst
+    /// directly after the previous write. A partially filled final message is
+    /// zero-padded and its padding is counted in the timestamp advance.
+    ///
+    /// A `timestamp` past the end of the current burst is bridged as described
+    /// by [`TxGap`]: a gap shorter than one message is zero-filled, a longer one
+    /// ends the burst with a zero-filled message and starts a new burst at
+    /// `timestamp`.
+    ///
+    /// Returns `Error::Argument` if a timestamp is given for a non-metadata format,
+    /// if a burst is started without a timestamp, or if `timestamp` precedes the end
+    /// of the samples already scheduled in the current burst.
+    pub fn write(
+        &mut self,
+        samples: &[u8],
+        timestamp: Option<u64>,
+        end_burst: bool,
+        timeout: Option<Duration>,
+    ) -> Result<()> {
+        if !self.format.requires_timestamps() {
+            if timestamp.is_some() {
+                return Err(Error::Argument(
+                    "timestamped writes require a metadata sample format".into(),
+                ));
+            }
+            return self.write_unframed(samples, timeout);
+        }
+        if self.format != SampleFormat::Sc16Q11Meta {
+            return Err(Error::Unsupported(
+                "scheduled TX is only supported for Sc16Q11Meta",
+            ));
+        }
+        let sample_size = self.format.sample_size();
+        if !samples.len().is_multiple_of(sample_size) {
+            return Err(Error::Argument(
+                "sample data is not a whole number of samples".into(),
+            ));
+        }
+        let start = match (timestamp, self.next_timestamp) {
+            (Some(ts), Some(next)) if ts < next => {
+                return Err(Error::Argument(format!(
+                    "timestamp {ts} precedes end of scheduled samples at {next}"
+                )));
+            }
+            (Some(ts), _) => ts,
+            (None, Some(next)) => next,
+            (None, None) => {
+                return Err(Error::Argument(
+                    "a timestamp is required to start a burst".into(),
+                ));
+            }
+        };
+        let message_size = self.message_size;
+        let payload_size = message_size - METADATA_HEADER_SIZE;
+        let samples_per_message = self.format.samples_per_message(message_size) as u64;
+        let buffer_size = self.pool_ref()?.buffer_size();
+        if buffer_size < message_size {
+            return Err(Error::Argument(format!(
+                "buffer_size {buffer_size} is smaller than the {message_size} byte message size"
+            )));
+        }
+        let gap = match self.next_timestamp {
+            Some(next) => TxGap::between(next, start, samples_per_message),
+            None => TxGap::None,
+        };
+        let padded;
+        let (start, samples) = match gap {
+            TxGap::ZeroFill(count) => {
+                padded = [&vec![0u8; count as usize * sample_size][..], samples].concat();
+                (start - count, &padded[..])
+            }
+            _ => (start, samples),
+        };
+        let mut burst_start = self.next_timestamp.is_none();
+        let mut buffer: Option<Buffer> = None;
+        if let (TxGap::Restart, Some(next)) = (gap, self.next_timestamp) {
+            log::debug!(
+                target: log_target::STREAM,
+                "Ending TX burst at {next} to restart it at {start}"
+            );
+            let header = MetadataHeader::new(0, 0, next, BladeRfMetaFlags::TX_BURST_END.bits());
+            self.push_message(&mut buffer, header, &[], timeout)?;
+            burst_start = true;
+        }
+        let message_count = samples.len().div_ceil(payload_size).max(1);
+        let mut ts = start;
+        for index in 0..message_count {
+            let chunk_start = index * payload_size;
+            let chunk = &samples[chunk_start..(chunk_start + payload_size).min(samples.len())];
+            let mut flags = BladeRfMetaFlags::empty();
+            if burst_start {
+                flags |= BladeRfMetaFlags::TX_BURST_START;
+                burst_start = false;
+            }
+            if end_burst && index == message_count - 1 {
+                flags |= BladeRfMetaFlags::TX_BURST_END;
+            }
+            let header = MetadataHeader::new(0, 0, ts, flags.bits());
+            self.push_message(&mut buffer, header, chunk, timeout)?;
+            ts = ts
+                .checked_add(samples_per_message)
+                .ok_or(Error::Argument("timestamp overflow".into()))?;
+        }
+        if let Some(buf) = buffer {
+            let len = buf.len();
+            self.submit(buf, len)?;
+        }
+        self.next_timestamp = if end_burst { None } else { Some(ts) };
+        Ok(())
+    }
+
+    /// Appends one metadata message, `header` followed by `chunk` zero-padded
+    /// to the message payload size, to `buffer`. A full buffer is submitted
+    /// and replaced by a fresh one from the pool.
+    fn push_message(
+        &mut self,
+        buffer: &mut Option<Buffer>,
+        header: MetadataHeader,
+        chunk: &[u8],
+        timeout: Option<Duration>,
+    ) -> Result<()> {
+        let message_size = self.message_size;
+        let buffer_size = self.pool_ref()?.buffer_size();
+        let mut buf = match buffer.take() {
+            Some(buf) if buf.len() + message_size <= buffer_size => buf,
+            Some(full) => {
+                let len = full.len();
+                self.submit(full, len)?;
+                self.get_buffer(timeout)?
+            }
+            None => self.get_buffer(timeout)?,
+        };
+        buf.extend_from_slice(&header.to_bytes());
+        buf.extend_from_slice(chunk);
+        buf.extend_fill(message_size - METADATA_HEADER_SIZE - chunk.len(), 0);
+        *buffer = Some(buf);
+        Ok(())
+    }
+
+    /// Sends `packet` to an FPGA core as a single transfer.
+    ///
+    /// Returns `Error::Unsupported` unless the stream uses
+    /// `SampleFormat::PacketMeta`, and `Error::Argument` if the framed packet
+    /// does not fit in one buffer or its payload exceeds the header's word
+    /// count.
+    pub fn send_packet(&mut self, packet: &Packet, timeout: Option<Duration>) -> Result<()> {
+        if self.format != SampleFormat::PacketMeta {
+            return Err(Error::Unsupported("packet I/O requires PacketMeta"));
+        }
+        let bytes = packet.to_bytes()?;
+        let buffer_size = self.pool_ref()?.buffer_size();
+        if bytes.len() > buffer_size {
+            return Err(Error::Argument(format!(
+                "packet of {} bytes exceeds buffer_size {buffer_size}",
+                bytes.len()
+            )));
+        }
+        let mut buf = self.get_buffer(timeout)?;
+        buf.extend_from_slice(&bytes);
+        self.submit(buf, bytes.len())
+    }
+
+    fn write_unframed(&mut self, samples: &[u8], timeout: Option<Duration>) -> Result<()> {
+        let buffer_size = self.pool_ref()?.buffer_size();
+        for chunk in samples.chunks(buffer_size) {
+            let mut buf = self.get_buffer(timeout)?;
+            buf.extend_from_slice(chunk);
+            self.submit(buf, chunk.len())?;
+        }
+        Ok(())
+    }
+
+    /// Writes sample bytes to the stream without blocking the thread,
+    /// splitting them across pool buffers.
+    ///
+    /// The async counterpart of `write()` for formats without metadata.
+    /// Timestamped bursts are not supported; use `write()` or frame the
+    /// messages manually with `get_buffer_async()` and `submit()`.
+    ///
+    /// Returns `Error::Unsupported` for metadata sample formats.
+    #[cfg(feature = "async")]
+    pub async fn write_async(&mut self, samples: &[u8]) -> Result<()> {
+        if self.format.requires_timestamps() {
+            return Err(Error::Unsupported(
+                "async writes do not support metadata sample formats",
+            ));
+        }
+        let buffer_size = self.pool_ref()?.buffer_size();
+        for chunk in samples.chunks(buffer_size) {
+            let mut buf = self.get_buffer_async().await?;
+            buf.extend_from_slice(chunk);
+            self.submit(buf, chunk.len())?;
+        }
+        Ok(())
+    }
+
+    /// Awaits completion of all pending TX transfers, recycling each
+    /// completed buffer back to the pool.
+    ///
+    /// The async counterpart of `wait_completion()`.
+    #[cfg(feature = "async")]
+    pub async fn wait_completion_async(&mut self) -> Result<()> {
+        let pool = self.pool_mut()?;
+        while let Some(completion) = pool.next_completion().await {
+            completion.status?;
+            pool.recycle(completion.buffer);
+        }
+        Ok(())
+    }
+
     /// Waits for all pending TX transfers to complete. Recycles each
     /// completed buffer back to the pool. Returns `Error::Timeout` if
     /// pending transfers do not complete within the time limit.
@@ -748,6 +1738,20 @@ impl TxStream {
         Ok(self.pool_ref()?.buffer_count())
     }
 
+    /// Changes the number of buffers (in-flight USB transfers) in the pool.
+    ///
+    /// More buffers keep more transfers queued on the endpoint, which absorbs
+    /// scheduling jitter on the host and avoids underruns at high sample
+    /// rates. Fewer buffers reduce memory use and the latency between
+    /// submitting samples and their transmission.
+    ///
+    /// Must be called while the stream is stopped and all buffers have been
+    /// returned; otherwise returns `Error::BoardState`. Returns
+    /// `Error::Argument` if `count` is zero.
+    pub fn set_buffer_count(&mut self, count: usize) -> Result<()> {
+        self.pool_mut()?.set_buffer_count(count)
+    }
+
     /// Returns a used buffer to the available pool for reuse.
     pub fn recycle(&mut self, buf: Buffer) {
         if let Some(ref mut pool) = self.pool {
@@ -787,6 +1791,58 @@ impl RfLinkSession<'_> {
         })
     }
 
+    /// Checks that `format` can be used on `channel` while the other
+    /// direction streams `other`.
+    ///
+    /// Both directions share the FPGA timestamp enable, so a format with
+    /// metadata headers cannot be combined with one without. `None` means
+    /// the other direction has no open stream.
+    ///
+    /// Returns `Error::Argument` if the formats conflict.
+    pub fn check_format_conflict(
+        channel: Channel,
+        format: SampleFormat,
+        other: Option<SampleFormat>,
+    ) -> Result<()> {
+        if let Some(other) = other
+            && other.requires_timestamps() != format.requires_timestamps()
+        {
+            log::error!(
+                target: log_target::STREAM,
+                "{channel:?} format {format:?} conflicts with {other:?} on the other direction"
+            );
+            return Err(Error::Argument(format!(
+                "{channel:?} format {format:?} conflicts with {other:?} on the other direction; \
+                 both must either use or not use timestamps"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks `format` against the other direction's stream, programs the
+    /// format GPIO bits and records `format` as `channel`'s stream format.
+    pub(crate) fn claim_stream_format(
+        &mut self,
+        channel: Channel,
+        format: SampleFormat,
+    ) -> Result<()> {
+        let mut formats = *self.stream_format;
+        formats.claim(channel, format)?;
+        self.perform_format_config(format)?;
+        *self.stream_format = formats;
+        Ok(())
+    }
+
+    /// Forgets `channel`'s stream format. The format GPIO bits are cleared
+    /// unless the other direction still has a stream claimed, in which case
+    /// its format is re-applied.
+    pub(crate) fn release_stream_format(&mut self, channel: Channel) -> Result<()> {
+        match self.stream_format.release(channel) {
+            Some(other) => self.perform_format_config(other),
+            None => self.perform_format_deconfig(),
+        }
+    }
+
     /// Clears all global format GPIO bits. Requires the board to be initialized.
     pub fn perform_format_deconfig(&mut self) -> Result<()> {
         self.require_initialized()?;
@@ -0,0 +1,149 @@
+//! Stepped RX frequency sweep for BladeRF1.
+//!
+//! Tunes to each step once up front to capture a `QuickTune` profile, then
+//! hops between the profiles with quick retunes and takes a `capture()` at
+//! every step, so the sweep itself never runs the VCOCAP search.
+
+use crate::bladerf1::board::RfLinkSession;
+use crate::bladerf1::board::frequency::{QuickTune, TuningMode};
+use crate::bladerf1::protocol::RetuneTimestamp;
+use crate::channel::Channel;
+use crate::error::{Error, Result};
+use crate::log_target;
+use num_complex::Complex;
+use std::time::Duration;
+
+const SWEEP_STEP_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Largest number of steps in one sweep.
+pub const SWEEP_MAX_STEPS: u64 = 65_536;
+
+/// Returns the frequencies visited by a sweep from `start` to `stop` in
+/// steps of `step` Hz. `stop` is included only if it lies on a step.
+///
+/// Returns `Error::Argument` if `step` is zero, `start` is above `stop`, or
+/// the sweep would have more than `SWEEP_MAX_STEPS` steps.
+pub fn sweep_frequencies(start: u64, stop: u64, step: u64) -> Result<Vec<u64>> {
+    if step == 0 {
+        return Err(Error::Argument("sweep step must be non-zero".into()));
+    }
+    if start > stop {
+        return Err(Error::Argument(format!(
+            "sweep start {start} Hz is above stop {stop} Hz"
+        )));
+    }
+    let last = (stop - start) / step;
+    if last >= SWEEP_MAX_STEPS {
+        return Err(Error::Argument(format!(
+            "sweep from {start} Hz to {stop} Hz in {step} Hz steps exceeds {SWEEP_MAX_STEPS} steps"
+        )));
+    }
+    Ok((0..=last).map(|n| start + n * step).collect())
+}
+
+/// Samples captured at one step of a sweep.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepPoint {
+    /// RF frequency of this step in Hz.
+    pub frequency: u64,
+    /// Received samples, scaled to `[-1.0, 1.0)`.
+    pub samples: Vec<Complex<f32>>,
+}
+
+/// Iterator over the steps of an RX sweep, created by `RfLinkSession::sweep()`.
+///
+/// Each call to `next()` retunes to the next step and captures its samples.
+/// After an error the remaining steps are still attempted; stop iterating to
+/// abort the sweep.
+pub struct Sweep<'s, 'd> {
+    session: &'s mut RfLinkSession<'d>,
+    steps: std::vec::IntoIter<(u64, QuickTune)>,
+    dwell_samples: usize,
+}
+
+impl Sweep<'_, '_> {
+    fn capture_step(&mut self, frequency: u64, quick_tune: &QuickTune) -> Result<SweepPoint> {
+        self.session
+            .schedule_quick_retune(Channel::Rx, RetuneTimestamp::Now, quick_tune)?;
+        let capture = self
+            .session
+            .capture(self.dwell_samples, SWEEP_STEP_TIMEOUT)?;
+        if capture.timed_out {
+            return Err(Error::Timeout);
+        }
+        Ok(SweepPoint {
+            frequency,
+            samples: capture.samples,
+        })
+    }
+}
+
+impl Iterator for Sweep<'_, '_> {
+    type Item = Result<SweepPoint>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (frequency, quick_tune) = self.steps.next()?;
+        Some(self.capture_step(frequency, &quick_tune))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.steps.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Sweep<'_, '_> {}
+
+impl<'d> RfLinkSession<'d> {
+    /// Prepares an RX sweep over `sweep_frequencies(start, stop, step)` that
+    /// captures `dwell_samples` samples at every step.
+    ///
+    /// Every step is first tuned with `TuningMode::Host` and its
+    /// `QuickTune` profile stored, so the returned iterator only performs
+    /// quick retunes and captures. With an enabled XB-200, steps below the
+    /// LMS6002D minimum are routed through the upconverter as by
+    /// `set_frequency()`, and the profiles carry the matching XB-200 path.
+    /// Quick retunes do not load DC calibration table entries.
+    ///
+    /// Each step fails with `Error::Timeout` if its samples do not arrive
+    /// within one second. RX is left tuned to the last step visited.
+    ///
+    /// Returns `Error::Argument` for an empty step, a reversed range, more
+    /// than `SWEEP_MAX_STEPS` steps or a `start` or `stop` outside
+    /// `get_frequency_range()`, and `Error::WrongState` if
+    /// the board has not been initialized.
+    pub fn sweep(
+        &mut self,
+        start: u64,
+        stop: u64,
+        step: u64,
+        dwell_samples: usize,
+    ) -> Result<Sweep<'_, 'd>> {
+        self.require_initialized()?;
+        #[cfg(feature = "xb200")]
+        let xb200 = self.nios.xb200_is_enabled()?;
+        #[cfg(not(feature = "xb200"))]
+        let xb200 = false;
+        Self::check_frequency(start, xb200)?;
+        Self::check_frequency(stop, xb200)?;
+        let frequencies = sweep_frequencies(start, stop, step)?;
+
+        let steps = frequencies
+            .into_iter()
+            .map(|frequency| {
+                self.set_frequency(Channel::Rx, frequency, TuningMode::Host)?;
+                Ok((frequency, self.get_quick_tune(Channel::Rx)?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        log::debug!(
+            target: log_target::BOARD,
+            "Sweep prepared: {} steps from {start}Hz to {stop}Hz",
+            steps.len()
+        );
+
+        Ok(Sweep {
+            session: self,
+            steps: steps.into_iter(),
+            dwell_samples,
+        })
+    }
+}
@@ -5,8 +5,9 @@
 //! and latency measurement.
 
 use crate::bladerf1::board::RfLinkSession;
+use crate::bladerf1::board::stream::BLADERF_GPIO_TIMESTAMP;
 use crate::channel::Channel;
-use crate::error::Result;
+use crate::error::{Error, Result};
 
 impl RfLinkSession<'_> {
     /// Reads the 64-bit timestamp counter for the given channel.
@@ -15,9 +16,16 @@ impl RfLinkSession<'_> {
     /// alongside stream metadata timestamps to compute latency or correlate
     /// RX/TX samples.
     ///
-    /// Returns `Error::BoardState` if the board is not initialized.
+    /// The counter only runs while timestamps are enabled in the config GPIO,
+    /// which happens when a stream is built with a metadata sample format.
+    ///
+    /// Returns `Error::WrongState` if the board is not initialized and
+    /// `Error::BoardState` if timestamps are not enabled.
     pub fn get_timestamp(&mut self, channel: Channel) -> Result<u64> {
         self.require_initialized()?;
+        if (self.config_gpio_read()? & BLADERF_GPIO_TIMESTAMP) == 0 {
+            return Err(Error::BoardState("timestamps are not enabled"));
+        }
         self.nios.nios_get_timestamp(channel)
     }
 }
@@ -0,0 +1,135 @@
+//! Continuous-wave tone transmission for BladeRF1.
+//!
+//! Synthesizes a complex exponential at a baseband offset from the TX
+//! carrier and streams it for a fixed duration, e.g. for antenna and PA
+//! measurements.
+
+use crate::bladerf1::board::RfLinkSession;
+use crate::bladerf1::board::stream::{SampleFormat, TxStream};
+use crate::channel::Channel;
+use crate::error::{Error, Result};
+use crate::log_target;
+use std::f64::consts::TAU;
+use std::time::Duration;
+
+/// Largest Sc16Q11 magnitude used for a tone of amplitude 1.0.
+pub const TONE_FULL_SCALE: f32 = 2047.0;
+
+const TONE_WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Returns `Error::Argument` if `sample_rate` is zero or `tone_hz` is not
+/// strictly inside `±sample_rate / 2`.
+pub fn check_tone(tone_hz: f32, sample_rate: u32) -> Result<()> {
+    let nyquist = sample_rate as f32 / 2.0;
+    if sample_rate == 0 || !tone_hz.is_finite() || tone_hz.abs() >= nyquist {
+        return Err(Error::Argument(format!(
+            "tone {tone_hz} Hz outside the Nyquist range of ±{nyquist} Hz"
+        )));
+    }
+    Ok(())
+}
+
+fn clamp_amplitude(amplitude: f32) -> f32 {
+    let clamped = amplitude.clamp(0.0, 1.0);
+    if clamped != amplitude {
+        log::warn!(
+            target: log_target::STREAM,
+            "Clamped tone amplitude {amplitude} to {clamped}"
+        );
+    }
+    clamped
+}
+
+/// Returns `num_samples` of a complex tone as `Sc16Q11` bytes.
+///
+/// The tone is `amplitude * exp(j * 2π * tone_hz * n / sample_rate)` for
+/// `n` starting at `first_sample`, so consecutive blocks generated with
+/// increasing `first_sample` join without a phase step. Negative `tone_hz`
+/// places the tone below the carrier. `amplitude` is a fraction of DAC full
+/// scale and is clamped to `0.0..=1.0`.
+///
+/// Returns `Error::Argument` for a tone rejected by `check_tone()`.
+pub fn tone_samples(
+    tone_hz: f32,
+    amplitude: f32,
+    sample_rate: u32,
+    first_sample: u64,
+    num_samples: usize,
+) -> Result<Vec<u8>> {
+    check_tone(tone_hz, sample_rate)?;
+    let scale = (clamp_amplitude(amplitude) * TONE_FULL_SCALE) as f64;
+    let cycles_per_sample = tone_hz as f64 / sample_rate as f64;
+
+    let mut bytes = Vec::with_capacity(num_samples * SampleFormat::Sc16Q11.sample_size());
+    for n in first_sample..first_sample + num_samples as u64 {
+        let phase = TAU * (cycles_per_sample * n as f64).fract();
+        let (sin, cos) = phase.sin_cos();
+        bytes.extend_from_slice(&((cos * scale).round() as i16).to_le_bytes());
+        bytes.extend_from_slice(&((sin * scale).round() as i16).to_le_bytes());
+    }
+    Ok(bytes)
+}
+
+impl RfLinkSession<'_> {
+    /// Transmits a continuous tone `tone_hz` away from the TX carrier for
+    /// `duration`.
+    ///
+    /// Samples come from `tone_samples()` at the current TX sample rate and
+    /// are streamed through a temporary `Sc16Q11` stream, one buffer at a
+    /// time, until `duration` worth of samples has been sent. The call
+    /// returns once the last buffer has been transmitted; the stream is
+    /// closed and the TX module disabled also on error. Frequency, gain and
+    /// sample rate are left as configured.
+    ///
+    /// Returns `Error::Unsupported` for `Channel::Rx`, `Error::Argument` if
+    /// `tone_hz` is outside the Nyquist range, and `Error::WrongState`
+    /// if the board has not been initialized.
+    pub fn transmit_tone(
+        &mut self,
+        channel: Channel,
+        tone_hz: f32,
+        amplitude: f32,
+        duration: Duration,
+    ) -> Result<()> {
+        if channel.is_rx() {
+            return Err(Error::Unsupported("tone transmission on RX"));
+        }
+        let sample_rate = self.get_sample_rate(Channel::Tx)?;
+        check_tone(tone_hz, sample_rate)?;
+        let amplitude = clamp_amplitude(amplitude);
+        let total = (duration.as_secs_f64() * sample_rate as f64).round() as u64;
+
+        let mut tx = TxStream::builder(self)
+            .format(SampleFormat::Sc16Q11)
+            .build()?;
+        let sent = self.transmit_tone_on(&mut tx, tone_hz, amplitude, sample_rate, total);
+        let closed = tx.close(self);
+        sent?;
+        closed
+    }
+
+    fn transmit_tone_on(
+        &mut self,
+        tx: &mut TxStream,
+        tone_hz: f32,
+        amplitude: f32,
+        sample_rate: u32,
+        total: u64,
+    ) -> Result<()> {
+        let per_buffer = (tx.buffer_size()? / SampleFormat::Sc16Q11.sample_size()) as u64;
+        log::debug!(
+            target: log_target::STREAM,
+            "Transmitting {tone_hz} Hz tone at {sample_rate} Sps for {total} samples"
+        );
+        tx.start(self)?;
+        let mut sent = 0;
+        while sent < total {
+            let count = per_buffer.min(total - sent);
+            let block = tone_samples(tone_hz, amplitude, sample_rate, sent, count as usize)?;
+            tx.write(&block, None, false, Some(TONE_WRITE_TIMEOUT))?;
+            sent += count;
+        }
+        let queued = (tx.buffer_count()? as u64 * per_buffer) as f64 / sample_rate as f64;
+        tx.wait_completion(Some(TONE_WRITE_TIMEOUT + Duration::from_secs_f64(queued)))
+    }
+}
@@ -3,8 +3,12 @@
 //! Provides master/slave trigger synchronization so multiple channels can
 //! start streaming simultaneously. The master channel arms and fires the
 //! trigger; slave channels arm and wait for the master's fire signal.
+//!
+//! The trigger signal is carried on mini expansion header pin J71-4, which
+//! is shared by all radios taking part. Triggers require FPGA v0.6.0 or
+//! later.
 
-use crate::bladerf1::board::RfLinkSession;
+use crate::bladerf1::board::{Capability, RfLinkSession};
 use crate::channel::Channel;
 use crate::error::{Error, Result};
 use crate::protocol::nios::NiosPkt8x8Target;
@@ -29,6 +33,7 @@ pub struct TriggerState {
     fire_requested: bool,
 }
 impl TriggerState {
+    /// Creates a trigger state from its parts.
     pub fn new(role: Option<TriggerRole>, fired: bool, fire_requested: bool) -> Self {
         Self {
             role,
@@ -37,19 +42,54 @@ impl TriggerState {
         }
     }
 
+    /// Decodes the trigger state from a trigger control register value.
+    ///
+    /// The trigger line is active low, so a cleared line bit means fired.
+    pub fn from_register(reg: u8) -> Self {
+        let role = if (reg & REG_ARM) != 0 {
+            Some(if (reg & REG_MASTER) != 0 {
+                TriggerRole::Master
+            } else {
+                TriggerRole::Slave
+            })
+        } else {
+            None
+        };
+        Self::new(role, (reg & REG_LINE) == 0, (reg & REG_FIRE) != 0)
+    }
+
+    /// Returns the role if the trigger is armed, `None` if disarmed.
     pub fn role(&self) -> Option<TriggerRole> {
         self.role
     }
 
+    /// Returns `true` if the trigger line has been asserted.
     pub fn fired(&self) -> bool {
         self.fired
     }
 
+    /// Returns `true` if the master has issued a fire command.
     pub fn fire_requested(&self) -> bool {
         self.fire_requested
     }
 }
 
+impl TriggerRole {
+    /// Returns `reg` armed with this role and the fire request cleared.
+    pub fn arm_register(self, reg: u8) -> u8 {
+        let master = match self {
+            TriggerRole::Master => REG_MASTER,
+            TriggerRole::Slave => 0,
+        };
+        (reg & !(REG_FIRE | REG_MASTER)) | REG_ARM | master
+    }
+
+    /// Returns `reg` disarmed, with the role and fire request cleared.
+    pub fn disarm_register(reg: u8) -> u8 {
+        reg & !(REG_ARM | REG_FIRE | REG_MASTER)
+    }
+}
+
 const REG_ARM: u8 = 1 << 0;
 const REG_FIRE: u8 = 1 << 1;
 const REG_MASTER: u8 = 1 << 2;
@@ -64,6 +104,8 @@ fn trigger_target(channel: Channel) -> NiosPkt8x8Target {
 
 impl RfLinkSession<'_> {
     fn trigger_read(&mut self, channel: Channel) -> Result<u8> {
+        self.require_initialized()?;
+        self.require_fpga_cap(Capability::TrxSyncTrig)?;
         self.nios.nios_read::<u8, u8>(trigger_target(channel), 0)
     }
 
@@ -78,36 +120,28 @@ impl RfLinkSession<'_> {
     /// or slave. Clears the fire bit. Only the master can subsequently fire
     /// the trigger via `fire_trigger`.
     ///
-    /// Returns `Error::BoardState` if the board is not initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized,
+    /// or `Error::NoApi` if the FPGA predates triggers.
     pub fn arm_trigger(&mut self, channel: Channel, role: TriggerRole) -> Result<()> {
-        self.require_initialized()?;
         let reg = self.trigger_read(channel)?;
-        let new_reg = (reg & !(REG_FIRE | REG_MASTER))
-            | REG_ARM
-            | match role {
-                TriggerRole::Master => REG_MASTER,
-                TriggerRole::Slave => 0,
-            };
-        self.trigger_write(channel, new_reg)
+        self.trigger_write(channel, role.arm_register(reg))
     }
 
     /// Fires the trigger on the master channel to synchronize armed peers.
     ///
-    /// Only the master channel may fire the trigger. Returns an error if the
-    /// trigger is not armed or if the channel is not configured as master.
+    /// The fire bit stays set until the trigger is disarmed; armed slaves
+    /// sharing the J71-4 line start streaming on the falling edge.
     ///
-    /// Returns `Error::BoardState` if the board is not initialized, the
-    /// trigger is not armed, or the channel is not the master.
+    /// Returns `Error::BoardState` if the trigger is not armed or the channel
+    /// is not the master, `Error::WrongState` if the board has not been
+    /// initialized, or `Error::NoApi` if the FPGA predates triggers.
     pub fn fire_trigger(&mut self, channel: Channel) -> Result<()> {
-        self.require_initialized()?;
         let reg = self.trigger_read(channel)?;
-        if (reg & REG_ARM) == 0 {
-            return Err(Error::BoardState("trigger not armed"));
-        }
-        if (reg & REG_MASTER) == 0 {
-            return Err(Error::BoardState("only master can fire trigger"));
+        match TriggerState::from_register(reg).role() {
+            Some(TriggerRole::Master) => self.trigger_write(channel, reg | REG_FIRE),
+            Some(TriggerRole::Slave) => Err(Error::BoardState("only master can fire trigger")),
+            None => Err(Error::BoardState("trigger not armed")),
         }
-        self.trigger_write(channel, reg | REG_FIRE)
     }
 
     /// Disarms the trigger for a channel, clearing all trigger state.
@@ -115,11 +149,11 @@ impl RfLinkSession<'_> {
     /// Resets arm, fire, and master flags. The channel returns to the default
     /// untriggered streaming mode.
     ///
-    /// Returns `Error::BoardState` if the board is not initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized,
+    /// or `Error::NoApi` if the FPGA predates triggers.
     pub fn disarm_trigger(&mut self, channel: Channel) -> Result<()> {
-        self.require_initialized()?;
         let reg = self.trigger_read(channel)?;
-        self.trigger_write(channel, reg & !(REG_ARM | REG_FIRE | REG_MASTER))
+        self.trigger_write(channel, TriggerRole::disarm_register(reg))
     }
 
     /// Returns the current trigger state for a channel.
@@ -127,23 +161,10 @@ impl RfLinkSession<'_> {
     /// Indicates whether the trigger is armed (and with which role), whether
     /// the trigger line has fired, and whether a fire has been requested.
     ///
-    /// Returns `Error::BoardState` if the board is not initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized,
+    /// or `Error::NoApi` if the FPGA predates triggers.
     pub fn trigger_state(&mut self, channel: Channel) -> Result<TriggerState> {
-        self.require_initialized()?;
         let reg = self.trigger_read(channel)?;
-        let role = if (reg & REG_ARM) != 0 {
-            Some(if (reg & REG_MASTER) != 0 {
-                TriggerRole::Master
-            } else {
-                TriggerRole::Slave
-            })
-        } else {
-            None
-        };
-        Ok(TriggerState::new(
-            role,
-            (reg & REG_LINE) == 0,
-            (reg & REG_FIRE) != 0,
-        ))
+        Ok(TriggerState::from_register(reg))
     }
 }
@@ -4,8 +4,10 @@
 //! discipline the on-board VCTCXO. Selecting a tamer mode connects the
 //! reference input (1 PPS or 10 MHz) so the firmware can lock the VCTCXO
 //! frequency to the external reference for improved timing stability.
+//!
+//! Tamer control requires FPGA v0.5.0 or later.
 
-use crate::bladerf1::board::RfLinkSession;
+use crate::bladerf1::board::{Capability, RfLinkSession};
 use crate::error::{Error, Result};
 use crate::protocol::nios::NiosPkt8x8Target;
 
@@ -13,6 +15,8 @@ use crate::protocol::nios::NiosPkt8x8Target;
 ///
 /// Selects the external reference signal used to discipline the VCTCXO
 /// oscillator. When disabled the VCTCXO runs free with optional DAC trim.
+/// The discriminants are the control byte values used by the FPGA and
+/// libbladeRF's `bladerf_vctcxo_tamer_mode`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum VctcxoTamerMode {
@@ -39,6 +43,13 @@ impl TryFrom<u8> for VctcxoTamerMode {
     }
 }
 
+impl From<VctcxoTamerMode> for u8 {
+    /// Returns the control byte written to the tamer mode register.
+    fn from(mode: VctcxoTamerMode) -> Self {
+        mode as u8
+    }
+}
+
 const MODE_ADDR: u8 = 0xFF;
 
 impl RfLinkSession<'_> {
@@ -48,20 +59,24 @@ impl RfLinkSession<'_> {
     /// `VctcxoTamerMode::Disabled` to return the VCTCXO to free-running
     /// operation.
     ///
-    /// Returns `Error::BoardState` if the board is not initialized.
+    /// Returns `Error::WrongState` if the board has not been initialized,
+    /// or `Error::NoApi` if the FPGA predates the tamer.
     pub fn set_vctcxo_tamer_mode(&mut self, mode: VctcxoTamerMode) -> Result<()> {
         self.require_initialized()?;
+        self.require_fpga_cap(Capability::VctcxoTamingMode)?;
         self.nios
-            .nios_write::<u8, u8>(NiosPkt8x8Target::VctcxoTamer, MODE_ADDR, mode as u8)
+            .nios_write::<u8, u8>(NiosPkt8x8Target::VctcxoTamer, MODE_ADDR, mode.into())
     }
 
     /// Returns the current VCTCXO tamer mode.
     ///
     /// Reads the NIOS VCTCXO tamer register and decodes the mode value.
-    /// Returns `Error::BoardState` if the board is not initialized, or
-    /// `Error::Unsupported` if the device reports an unrecognized mode.
+    /// Returns `Error::WrongState` if the board has not been initialized,
+    /// `Error::NoApi` if the FPGA predates the tamer, or `Error::Unsupported`
+    /// if the device reports an unrecognized mode.
     pub fn get_vctcxo_tamer_mode(&mut self) -> Result<VctcxoTamerMode> {
         self.require_initialized()?;
+        self.require_fpga_cap(Capability::VctcxoTamingMode)?;
         let raw = self
             .nios
             .nios_read::<u8, u8>(NiosPkt8x8Target::VctcxoTamer, MODE_ADDR)?;
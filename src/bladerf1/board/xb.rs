@@ -9,13 +9,33 @@ mod xb100;
 #[cfg(feature = "xb200")]
 pub mod xb200;
 #[cfg(feature = "xb300")]
-mod xb300;
+pub mod xb300;
 
-use crate::bladerf1::board::RfLinkSession;
+use crate::bladerf1::board::{Capability, RfLinkSession};
 use crate::error::{Error, Result};
-#[cfg(any(feature = "xb100", feature = "xb200", feature = "xb300"))]
+use crate::log_target;
+#[cfg(feature = "xb200")]
 use crate::nios_client::NiosCore;
 
+/// Returns the expansion GPIO bit for header signal `pin` (1-32), matching
+/// libbladeRF's `BLADERF_XB_GPIO(n)`.
+///
+/// Returns `Error::Argument` if `pin` is outside 1-32.
+pub fn xb_gpio(pin: u32) -> Result<u32> {
+    if !(1..=32).contains(&pin) {
+        return Err(Error::Argument(format!(
+            "expansion GPIO pin {pin} is outside 1-32"
+        )));
+    }
+    Ok(1 << (pin - 1))
+}
+
+/// Expansion GPIO pins that the attach routine of some supported board
+/// programs as outputs: `0x3C00383E` for the XB-200, `0x004506F0` for the
+/// XB-300 and `0xF9F80000` for the XB-100. It covers every board whether or
+/// not its feature is enabled, and only these pins take part in detection.
+pub(crate) const XB_DIR_KNOWN_MASK: u32 = 0x3C00383E | 0x004506F0 | 0xF9F80000;
+
 /// Identifies the expansion board attached to the BladeRF1.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ExpansionBoard {
@@ -32,33 +52,60 @@ pub enum ExpansionBoard {
     Xb300,
 }
 
-#[cfg(any(feature = "xb100", feature = "xb200"))]
-impl NiosCore {
-    /// Reads the expansion GPIO and checks if `check_mask` bits are set.
-    /// Returns `false` if all GPIO bits read as 1 (no board present).
-    pub(crate) fn detect_xb_board(&mut self, check_mask: u32) -> Result<bool> {
-        let gpio = self.nios_expansion_gpio_read()?;
-        if gpio == 0xffffffff {
-            return Ok(false);
+impl ExpansionBoard {
+    /// Identifies the attached board from the expansion GPIO direction register.
+    ///
+    /// Each board's attach routine programs a fixed set of pins as outputs,
+    /// and the direction register lives in the FPGA, so unlike the pin
+    /// levels it cannot float. A board is recognized when, of the pins in
+    /// any board's signature, exactly those of its own are outputs:
+    ///
+    /// - XB-200: the filter, path, RF_ON and enable lines set by
+    ///   `xb200_attach()`.
+    /// - XB-300: the LED, TRX, amplifier enable and power detector SPI lines
+    ///   set by `xb300_attach()`.
+    /// - XB-100: the eleven LED lines set by `xb100_enable()`.
+    ///
+    /// The XB-200 and XB-100 signatures share pins, so a direction that
+    /// merely contains a signature, such as `0xffffffff`, does not match.
+    /// Returns `ExpansionBoard::XbNone` if no signature matches exactly.
+    #[allow(unused_variables)]
+    pub fn identify(gpio_dir: u32) -> ExpansionBoard {
+        let outputs = gpio_dir & XB_DIR_KNOWN_MASK;
+        #[cfg(feature = "xb200")]
+        if outputs == xb200::XB200_DIR_SIGNATURE {
+            return ExpansionBoard::Xb200;
+        }
+        #[cfg(feature = "xb300")]
+        if outputs == xb300::XB300_DIR_SIGNATURE {
+            return ExpansionBoard::Xb300;
         }
-        Ok((gpio & check_mask) != 0)
+        #[cfg(feature = "xb100")]
+        if outputs == xb100::XB100_LED_MASK {
+            return ExpansionBoard::Xb100;
+        }
+        ExpansionBoard::XbNone
     }
-}
 
-#[cfg(feature = "xb300")]
-impl NiosCore {
-    /// Reads the expansion GPIO direction register and checks if `check_mask` bits indicate a board.
-    pub(crate) fn detect_xb_board_by_dir(&mut self, check_mask: u32) -> Result<bool> {
-        let gpio_dir = self.nios_expansion_gpio_dir_read()?;
-        Ok((gpio_dir & check_mask) != 0)
+    /// Returns `true` if the GPIO direction identifies an XB-200 and its
+    /// RF_ON output is driven high.
+    #[cfg(feature = "xb200")]
+    pub fn is_xb200_enabled_in_gpio(gpio: u32, gpio_dir: u32) -> bool {
+        Self::identify(gpio_dir) == ExpansionBoard::Xb200 && (gpio & xb200::BLADERF_XB_RF_ON) != 0
     }
 }
 
 #[cfg(feature = "xb200")]
 impl NiosCore {
-    /// Returns `true` if the XB-200 board is currently enabled (RF_ON bit set).
+    /// Returns `true` if the XB-200 is attached and enabled, as decided by
+    /// `ExpansionBoard::is_xb200_enabled_in_gpio()`.
     pub(crate) fn xb200_is_enabled(&mut self) -> Result<bool> {
-        self.detect_xb_board(xb200::BLADERF_XB_RF_ON)
+        let gpio_dir = self.nios_expansion_gpio_dir_read()?;
+        if ExpansionBoard::identify(gpio_dir) != ExpansionBoard::Xb200 {
+            return Ok(false);
+        }
+        let gpio = self.nios_expansion_gpio_read()?;
+        Ok(ExpansionBoard::is_xb200_enabled_in_gpio(gpio, gpio_dir))
     }
 }
 
@@ -74,7 +121,16 @@ impl RfLinkSession<'_> {
     }
 
     /// Writes the expansion GPIO value with a mask — only bits set in `mask` are updated.
+    ///
+    /// The mask is carried in the NIOS packet and applied by the FPGA, so the
+    /// selected pins change atomically without a host-side read-modify-write.
+    ///
+    /// Returns `Error::NoApi` for a partial mask if the FPGA predates
+    /// masked XBIO writes (v0.4.1).
     pub fn expansion_gpio_masked_write(&mut self, mask: u32, val: u32) -> Result<()> {
+        if mask != 0xffffffff {
+            self.require_fpga_cap(Capability::MaskedXbioWrite)?;
+        }
         self.nios.nios_expansion_gpio_write(mask, val)
     }
 
@@ -88,45 +144,71 @@ impl RfLinkSession<'_> {
         self.nios.nios_expansion_gpio_dir_write(0xffffffff, val)
     }
 
-    /// Writes the expansion GPIO direction register with a mask.
+    /// Writes the expansion GPIO direction register with a mask. A set bit in
+    /// `val` configures the pin as an output.
+    ///
+    /// Returns `Error::NoApi` for a partial mask if the FPGA predates
+    /// masked XBIO writes (v0.4.1).
     pub fn expansion_gpio_dir_masked_write(&mut self, mask: u32, val: u32) -> Result<()> {
+        if mask != 0xffffffff {
+            self.require_fpga_cap(Capability::MaskedXbioWrite)?;
+        }
         self.nios.nios_expansion_gpio_dir_write(mask, val)
     }
 
     /// Detects and returns the currently attached expansion board.
+    ///
+    /// The board is identified by its GPIO direction signature with
+    /// `ExpansionBoard::identify()`. An XB-200 match is then confirmed with
+    /// `xb200_probe_synth()`, which checks that the ADF4351 drives its
+    /// MUXOUT pin. Only when no signature matches is the old all-ones GPIO
+    /// heuristic consulted, and then only to tell "no board" apart from an
+    /// unrecognized configuration in the log.
+    ///
     /// Returns `ExpansionBoard::XbNone` if no recognized board is present.
     pub fn expansion_get_attached(&mut self) -> Result<ExpansionBoard> {
         self.require_initialized()?;
-        if self.nios.nios_expansion_gpio_read()? == 0xffffffff {
-            return Ok(ExpansionBoard::XbNone);
-        }
-        #[cfg(feature = "xb100")]
-        if self.nios.detect_xb_board(xb100::XB100_DETECT_MASK)? {
-            return Ok(ExpansionBoard::Xb100);
-        }
+        let gpio_dir = self.nios.nios_expansion_gpio_dir_read()?;
+        let board = ExpansionBoard::identify(gpio_dir);
         #[cfg(feature = "xb200")]
-        if self.nios.detect_xb_board(xb200::BLADERF_XB_RF_ON)? {
-            return Ok(ExpansionBoard::Xb200);
+        if board == ExpansionBoard::Xb200 && !self.xb200_probe_synth()? {
+            log::warn!(
+                target: log_target::XB,
+                "XB-200 GPIO directions set but the ADF4351 does not respond"
+            );
+            return Ok(ExpansionBoard::XbNone);
         }
-        #[cfg(feature = "xb300")]
-        if self.nios.detect_xb_board_by_dir(xb300::XB300_DETECT_MASK)? {
-            return Ok(ExpansionBoard::Xb300);
+        if board == ExpansionBoard::XbNone {
+            let gpio = self.nios.nios_expansion_gpio_read()?;
+            if gpio == 0xffffffff {
+                log::trace!(target: log_target::XB, "Expansion GPIO reads all ones: no board");
+            } else {
+                log::trace!(
+                    target: log_target::XB,
+                    "No expansion board signature in GPIO dir {gpio_dir:#010x} (GPIO {gpio:#010x})"
+                );
+            }
         }
-        Ok(ExpansionBoard::XbNone)
+        Ok(board)
     }
 
     /// Attaches and enables the specified expansion board. Performs detection,
     /// attach, enable, and init in sequence. Switching between different board
     /// types is not supported. Returns `Error::Unsupported` on mismatch.
+    ///
+    /// Returns `Error::NoApi` if the loaded FPGA is too old for the board:
+    /// the XB-200 requires FPGA v0.0.5 and the XB-100, which drives its LEDs
+    /// through masked XBIO writes, requires v0.4.1.
     pub fn expansion_attach(&mut self, xb: ExpansionBoard) -> Result<()> {
         self.require_initialized()?;
         let attached = self.expansion_get_attached()?;
         if xb != attached && attached != ExpansionBoard::XbNone {
-            log::error!("Switching XB types is not supported.");
+            log::error!(target: log_target::XB, "Switching XB types is not supported.");
             return Err(Error::Unsupported("switching XB types"));
         }
         #[cfg(feature = "xb100")]
         if xb == ExpansionBoard::Xb100 {
+            self.require_fpga_cap(Capability::MaskedXbioWrite)?;
             self.xb100_attach()?;
             self.xb100_enable(true)?;
             self.xb100_init()?;
@@ -134,6 +216,7 @@ impl RfLinkSession<'_> {
         }
         #[cfg(feature = "xb200")]
         if xb == ExpansionBoard::Xb200 {
+            self.require_fpga_cap(Capability::Xb200)?;
             self.xb200_attach()?;
             self.xb200_enable(true)?;
             self.xb200_init()?;
@@ -147,10 +230,10 @@ impl RfLinkSession<'_> {
             return Ok(());
         }
         if xb == ExpansionBoard::XbNone {
-            log::error!("Disabling an attached XB is not supported.");
+            log::error!(target: log_target::XB, "Disabling an attached XB is not supported.");
             return Err(Error::Unsupported("disabling attached XB"));
         }
-        log::error!("Unknown xb type: {xb:?}");
+        log::error!(target: log_target::XB, "Unknown xb type: {xb:?}");
         Err(Error::Unsupported("unknown XB type"))
     }
 }
@@ -7,38 +7,33 @@
 use crate::bladerf1::board::RfLinkSession;
 use crate::error::Result;
 
-macro_rules! bladerf_xb_gpio {
-    ($n:expr) => {
-        (1 << ($n - 1)) as u8
-    };
-}
-
-const BLADERF_XB_GPIO_20: u8 = bladerf_xb_gpio!(20);
-const BLADERF_XB_GPIO_21: u8 = bladerf_xb_gpio!(21);
-const BLADERF_XB_GPIO_22: u8 = bladerf_xb_gpio!(22);
-const BLADERF_XB_GPIO_23: u8 = bladerf_xb_gpio!(23);
-const BLADERF_XB_GPIO_24: u8 = bladerf_xb_gpio!(24);
-const BLADERF_XB_GPIO_25: u8 = bladerf_xb_gpio!(25);
-const BLADERF_XB_GPIO_28: u8 = bladerf_xb_gpio!(28);
-const BLADERF_XB_GPIO_29: u8 = bladerf_xb_gpio!(29);
-const BLADERF_XB_GPIO_30: u8 = bladerf_xb_gpio!(30);
-const BLADERF_XB_GPIO_31: u8 = bladerf_xb_gpio!(31);
-const BLADERF_XB_GPIO_32: u8 = bladerf_xb_gpio!(32);
+const BLADERF_XB_GPIO_20: u32 = 1 << 19;
+const BLADERF_XB_GPIO_21: u32 = 1 << 20;
+const BLADERF_XB_GPIO_22: u32 = 1 << 21;
+const BLADERF_XB_GPIO_23: u32 = 1 << 22;
+const BLADERF_XB_GPIO_24: u32 = 1 << 23;
+const BLADERF_XB_GPIO_25: u32 = 1 << 24;
+const BLADERF_XB_GPIO_28: u32 = 1 << 27;
+const BLADERF_XB_GPIO_29: u32 = 1 << 28;
+const BLADERF_XB_GPIO_30: u32 = 1 << 29;
+const BLADERF_XB_GPIO_31: u32 = 1 << 30;
+const BLADERF_XB_GPIO_32: u32 = 1 << 31;
 
-const BLADERF_XB100_LED_D1: u8 = BLADERF_XB_GPIO_24;
-const BLADERF_XB100_LED_D2: u8 = BLADERF_XB_GPIO_32;
-const BLADERF_XB100_LED_D3: u8 = BLADERF_XB_GPIO_30;
-const BLADERF_XB100_LED_D4: u8 = BLADERF_XB_GPIO_28;
-const BLADERF_XB100_LED_D5: u8 = BLADERF_XB_GPIO_23;
-const BLADERF_XB100_LED_D6: u8 = BLADERF_XB_GPIO_25;
-const BLADERF_XB100_LED_D7: u8 = BLADERF_XB_GPIO_31;
-const BLADERF_XB100_LED_D8: u8 = BLADERF_XB_GPIO_29;
-const BLADERF_XB100_TLED_RED: u8 = BLADERF_XB_GPIO_22;
-const BLADERF_XB100_TLED_GREEN: u8 = BLADERF_XB_GPIO_21;
-const BLADERF_XB100_TLED_BLUE: u8 = BLADERF_XB_GPIO_20;
+const BLADERF_XB100_LED_D1: u32 = BLADERF_XB_GPIO_24;
+const BLADERF_XB100_LED_D2: u32 = BLADERF_XB_GPIO_32;
+const BLADERF_XB100_LED_D3: u32 = BLADERF_XB_GPIO_30;
+const BLADERF_XB100_LED_D4: u32 = BLADERF_XB_GPIO_28;
+const BLADERF_XB100_LED_D5: u32 = BLADERF_XB_GPIO_23;
+const BLADERF_XB100_LED_D6: u32 = BLADERF_XB_GPIO_25;
+const BLADERF_XB100_LED_D7: u32 = BLADERF_XB_GPIO_31;
+const BLADERF_XB100_LED_D8: u32 = BLADERF_XB_GPIO_29;
+const BLADERF_XB100_TLED_RED: u32 = BLADERF_XB_GPIO_22;
+const BLADERF_XB100_TLED_GREEN: u32 = BLADERF_XB_GPIO_21;
+const BLADERF_XB100_TLED_BLUE: u32 = BLADERF_XB_GPIO_20;
 
-/// Bitmask for all XB-100 GPIO pins, used for board detection.
-pub(crate) const XB100_DETECT_MASK: u32 = (BLADERF_XB100_LED_D1
+/// Bitmask for all XB-100 LED pins. `xb100_enable()` configures exactly these
+/// pins as outputs, which is the direction signature used for detection.
+pub(crate) const XB100_LED_MASK: u32 = BLADERF_XB100_LED_D1
     | BLADERF_XB100_LED_D2
     | BLADERF_XB100_LED_D3
     | BLADERF_XB100_LED_D4
@@ -48,9 +43,7 @@ pub(crate) const XB100_DETECT_MASK: u32 = (BLADERF_XB100_LED_D1
     | BLADERF_XB100_LED_D8
     | BLADERF_XB100_TLED_RED
     | BLADERF_XB100_TLED_GREEN
-    | BLADERF_XB100_TLED_BLUE) as u32;
-
-const XB100_LED_MASK: u32 = XB100_DETECT_MASK;
+    | BLADERF_XB100_TLED_BLUE;
 
 impl RfLinkSession<'_> {
     /// Prepares the XB-100 board. Currently a no-op placeholder.
@@ -12,6 +12,7 @@
 use crate::bladerf1::board::RfLinkSession;
 use crate::channel::Channel;
 use crate::error::{Error, Result};
+use crate::log_target;
 use std::ops::RangeInclusive;
 pub(crate) const BLADERF_XB_CONFIG_TX_PATH_MIX: u32 = 0x04;
 pub(crate) const BLADERF_XB_CONFIG_TX_PATH_BYPASS: u32 = 0x08;
@@ -22,6 +23,14 @@ pub(crate) const BLADERF_XB_CONFIG_RX_PATH_BYPASS: u32 = 0x20;
 pub(crate) const BLADERF_XB_CONFIG_RX_BYPASS: u32 = 0x10;
 pub(crate) const BLADERF_XB_CONFIG_RX_BYPASS_MASK: u32 = 0x30;
 pub(crate) const BLADERF_XB_RF_ON: u32 = 0x0800;
+/// Expansion GPIO directions programmed by `xb200_attach()`, used as the
+/// XB-200 detection signature.
+pub(crate) const XB200_DIR_SIGNATURE: u32 = 0x3C00383E;
+/// Expansion GPIO input wired to the ADF4351 MUXOUT pin.
+const BLADERF_XB_MUXOUT: u32 = 0x1;
+const ADF4351_MUXOUT_DVDD: u32 = 1;
+const ADF4351_MUXOUT_DGND: u32 = 2;
+const ADF4351_MUXOUT_DIGITAL_LOCK_DETECT: u32 = 6;
 pub(crate) const BLADERF_XB_TX_ENABLE: u32 = 0x1000;
 pub(crate) const BLADERF_XB_RX_ENABLE: u32 = 0x2000;
 pub(crate) const BLADERF_XB_TX_MASK: u32 = 0x0C000000;
@@ -75,12 +84,51 @@ impl TryFrom<u32> for Xb200Filter {
             4 => Ok(Xb200Filter::Auto1db),
             5 => Ok(Xb200Filter::Auto3db),
             _ => {
-                log::error!("invalid filter selection!");
+                log::error!(target: log_target::XB, "invalid filter selection!");
                 Err(Error::Argument("invalid XB200 filter value".into()))
             }
         }
     }
 }
+impl Xb200Filter {
+    /// Returns `true` for the `Auto1db` and `Auto3db` selection modes.
+    pub fn is_auto(self) -> bool {
+        matches!(self, Xb200Filter::Auto1db | Xb200Filter::Auto3db)
+    }
+
+    /// Returns `gpio` with the filter switch bits of `ch` set to this bank.
+    ///
+    /// Only the four physical banks can be written to the switch; the
+    /// automatic modes return `Error::Argument`.
+    pub fn apply_to_gpio(self, ch: Channel, gpio: u32) -> Result<u32> {
+        if self.is_auto() {
+            return Err(Error::Argument(format!(
+                "{self:?} is not a physical XB200 filter bank"
+            )));
+        }
+        let (mask, shift) = filter_field(ch);
+        Ok((gpio & !mask) | ((self as u32) << shift))
+    }
+
+    /// Decodes the filter bank selected for `ch` from an expansion GPIO value.
+    pub fn from_gpio(ch: Channel, gpio: u32) -> Self {
+        let (mask, shift) = filter_field(ch);
+        match (gpio & mask) >> shift {
+            0 => Xb200Filter::_50M,
+            1 => Xb200Filter::_144M,
+            2 => Xb200Filter::_222M,
+            _ => Xb200Filter::Custom,
+        }
+    }
+}
+
+/// Returns the expansion GPIO mask and shift of the filter switch for `ch`.
+fn filter_field(ch: Channel) -> (u32, u32) {
+    match ch {
+        Channel::Rx => (BLADERF_XB_RX_MASK, BLADERF_XB_RX_SHIFT),
+        Channel::Tx => (BLADERF_XB_TX_MASK, BLADERF_XB_TX_SHIFT),
+    }
+}
 /// XB-200 signal path mode.
 ///
 /// In `Mix` mode the ADF4351 down-converts (RX) or up-converts (TX) the
@@ -99,7 +147,7 @@ impl RfLinkSession<'_> {
     /// ADF4351 synthesizer, and programs expansion GPIO direction/pin values.
     pub fn xb200_attach(&mut self) -> Result<()> {
         self.require_initialized()?;
-        let muxout: usize = 6;
+        let muxout = ADF4351_MUXOUT_DIGITAL_LOCK_DETECT as usize;
         let mux_lut = [
             "THREE-STATE OUTPUT",
             "DVdd",
@@ -110,41 +158,68 @@ impl RfLinkSession<'_> {
             "DIGITAL LOCK DETECT",
             "RESERVED",
         ];
-        log::trace!("Attaching XB200 transverter board");
+        log::trace!(target: log_target::XB, "Attaching XB200 transverter board");
         let mut val8 = self.si().read(39)?;
-        log::trace!("[xb200_attach] si5338_read: {val8}");
+        log::trace!(target: log_target::XB, "[xb200_attach] si5338_read: {val8}");
         val8 |= 2;
         self.si().write(39, val8)?;
         self.si().write(34, 0x22)?;
         self.config_gpio_modify(|gpio| gpio | 0x80000000)?;
         self.nios
-            .nios_expansion_gpio_dir_write(0xffffffff, 0x3C00383E)?;
+            .nios_expansion_gpio_dir_write(0xffffffff, XB200_DIR_SIGNATURE)?;
         self.nios.nios_expansion_gpio_write(0xffffffff, 0x800)?;
         self.nios.nios_xb200_synth_write(0x580005)?;
         self.nios.nios_xb200_synth_write(0x99A16C)?;
         self.nios.nios_xb200_synth_write(0xC004B3)?;
-        log::trace!("MUXOUT: {}", mux_lut[muxout]);
-        let value = 0x60008E42 | (1 << 8) | ((muxout as u32) << 26);
-        self.nios.nios_xb200_synth_write(value)?;
+        log::trace!(target: log_target::XB, "MUXOUT: {}", mux_lut[muxout]);
+        self.nios
+            .nios_xb200_synth_write(adf4351_reg2(muxout as u32))?;
         self.nios.nios_xb200_synth_write(0x08008011)?;
         self.nios.nios_xb200_synth_write(0x00410000)?;
         let val = self.nios.nios_expansion_gpio_read()?;
-        log::trace!("[xb200_attach] expansion_gpio_read: {val}");
-        if (val & 0x1) != 0 {
-            log::debug!("MUXOUT Bit set: OK")
+        log::trace!(target: log_target::XB, "[xb200_attach] expansion_gpio_read: {val}");
+        if (val & BLADERF_XB_MUXOUT) != 0 {
+            log::debug!(target: log_target::XB, "MUXOUT Bit set: OK")
         } else {
-            log::debug!("MUXOUT Bit not set: FAIL");
+            log::debug!(target: log_target::XB, "MUXOUT Bit not set: FAIL");
         }
         self.nios
             .nios_expansion_gpio_write(0xffffffff, 0x3C000800)?;
         Ok(())
     }
 
+    /// Confirms that an ADF4351 answers on the XB-200 SPI bus.
+    ///
+    /// Drives the synthesizer's MUXOUT pin high (DVdd) and then low (DGND)
+    /// and checks that the expansion GPIO input it is wired to follows. A
+    /// floating pin cannot track both levels, so this is a positive
+    /// identification. MUXOUT is restored to digital lock detect afterwards.
+    ///
+    /// Only call this with the XB-200 attached: the SPI writes toggle
+    /// expansion pins that other boards use for other purposes.
+    pub fn xb200_probe_synth(&mut self) -> Result<bool> {
+        self.require_initialized()?;
+        self.nios
+            .nios_xb200_synth_write(adf4351_reg2(ADF4351_MUXOUT_DVDD))?;
+        let high = self.nios.nios_expansion_gpio_read()?;
+        self.nios
+            .nios_xb200_synth_write(adf4351_reg2(ADF4351_MUXOUT_DGND))?;
+        let low = self.nios.nios_expansion_gpio_read()?;
+        self.nios
+            .nios_xb200_synth_write(adf4351_reg2(ADF4351_MUXOUT_DIGITAL_LOCK_DETECT))?;
+        let present = (high & BLADERF_XB_MUXOUT) != 0 && (low & BLADERF_XB_MUXOUT) == 0;
+        log::debug!(
+            target: log_target::XB,
+            "ADF4351 MUXOUT probe: high {high:#010x}, low {low:#010x}, present {present}"
+        );
+        Ok(present)
+    }
+
     /// Enables or disables the XB-200 RF circuitry via the RF_ON GPIO bit.
     pub fn xb200_enable(&mut self, enable: bool) -> Result<()> {
         self.require_initialized()?;
         let orig = self.nios.nios_expansion_gpio_read()?;
-        log::trace!("[xb200_enable] expansion_gpio_read: {orig}");
+        log::trace!(target: log_target::XB, "[xb200_enable] expansion_gpio_read: {orig}");
         let mut val = orig;
         if enable {
             val |= BLADERF_XB_RF_ON;
@@ -161,86 +236,97 @@ impl RfLinkSession<'_> {
     /// and both filter banks to Auto1db.
     pub fn xb200_init(&mut self) -> Result<()> {
         self.require_initialized()?;
-        log::trace!("Setting RX path");
+        log::trace!(target: log_target::XB, "Setting RX path");
         self.xb200_set_path(Channel::Rx, Xb200Path::Bypass)?;
-        log::trace!("Setting TX path");
+        log::trace!(target: log_target::XB, "Setting TX path");
         self.xb200_set_path(Channel::Tx, Xb200Path::Bypass)?;
-        log::trace!("Setting RX filter");
+        log::trace!(target: log_target::XB, "Setting RX filter");
         self.xb200_set_filterbank(Channel::Rx, Xb200Filter::Auto1db)?;
-        log::trace!("Setting TX filter");
+        log::trace!(target: log_target::XB, "Setting TX filter");
         self.xb200_set_filterbank(Channel::Tx, Xb200Filter::Auto1db)
     }
-    /// Returns the currently selected filter bank for the given channel.
+    /// Returns the filter bank currently engaged on the given channel, as read
+    /// back from the expansion GPIO filter switch.
+    ///
+    /// The switch only ever holds one of the four physical banks; use
+    /// [`xb200_get_filter_mode`](Self::xb200_get_filter_mode) to find out
+    /// whether automatic selection is active.
     pub fn xb200_get_filterbank(&mut self, ch: Channel) -> Result<Xb200Filter> {
         self.require_initialized()?;
         let val = self.nios.nios_expansion_gpio_read()?;
-        log::trace!("[xb200_get_filterbank] expansion_gpio_read: {val}");
-        let shift = if ch == Channel::Rx {
-            BLADERF_XB_RX_SHIFT
-        } else {
-            BLADERF_XB_TX_SHIFT
-        };
-        Xb200Filter::try_from((val >> shift) & 3)
+        log::trace!(target: log_target::XB, "[xb200_get_filterbank] expansion_gpio_read: {val}");
+        Ok(Xb200Filter::from_gpio(ch, val))
+    }
+    /// Returns the filter mode last set with
+    /// [`xb200_set_filterbank`](Self::xb200_set_filterbank): `Auto1db` or
+    /// `Auto3db` while automatic selection is active, otherwise the engaged
+    /// bank.
+    pub fn xb200_get_filter_mode(&mut self, ch: Channel) -> Result<Xb200Filter> {
+        match self.xb200_auto_filter[ch as usize] {
+            Some(mode) => Ok(mode),
+            None => self.xb200_get_filterbank(ch),
+        }
     }
     /// Directly sets the filter bank mux for the given channel without auto-selection.
+    ///
+    /// Returns `Error::Argument` for `Auto1db` and `Auto3db`.
     pub fn set_filterbank_mux(&mut self, ch: Channel, filter: Xb200Filter) -> Result<()> {
         self.require_initialized()?;
-        let (mask, shift) = if ch == Channel::Rx {
-            (BLADERF_XB_RX_MASK, BLADERF_XB_RX_SHIFT)
-        } else {
-            (BLADERF_XB_TX_MASK, BLADERF_XB_TX_SHIFT)
-        };
         let orig = self.nios.nios_expansion_gpio_read()?;
-        log::trace!("[set_filterbank_mux] expansion_gpio_read: {orig}");
-        let mut val = orig & !mask;
-        val |= (filter as u32) << shift;
+        log::trace!(target: log_target::XB, "[set_filterbank_mux] expansion_gpio_read: {orig}");
+        let val = filter.apply_to_gpio(ch, orig)?;
         if orig != val {
-            let dir = if mask == BLADERF_XB_TX_MASK {
-                "TX"
-            } else {
-                "RX"
-            };
-            log::trace!("Engaging {filter:?} band XB-200 {dir} filter");
+            let dir = if ch == Channel::Tx { "TX" } else { "RX" };
+            log::trace!(target: log_target::XB, "Engaging {filter:?} band XB-200 {dir} filter");
             self.nios.nios_expansion_gpio_write(0xffffffff, val)?;
         }
         Ok(())
     }
-    /// Sets the filter bank for the given channel. For `Auto1db` or `Auto3db`,
-    /// reads the current frequency and selects the appropriate filter from the
-    /// corresponding frequency-range table. For other variants, sets directly.
+    /// Sets the filter bank for the given channel.
+    ///
+    /// `Auto1db` and `Auto3db` enable automatic selection: the bank matching
+    /// the current frequency is engaged now, and
+    /// [`set_frequency`](Self::set_frequency) re-selects it on every retune
+    /// through the XB-200 mixer. Frequencies outside all bands of the table
+    /// select `Custom`. Any other variant is engaged directly and disables
+    /// automatic selection.
     pub fn xb200_set_filterbank(&mut self, ch: Channel, filter: Xb200Filter) -> Result<()> {
         self.require_initialized()?;
         if !self.nios.xb200_is_enabled()? {
-            log::error!("xb_200 not enabled! need to enable?");
+            log::error!(target: log_target::XB, "xb_200 not enabled! need to enable?");
             return Err(Error::Unsupported("XB200 not enabled"));
         }
-        if filter == Xb200Filter::Auto1db || filter == Xb200Filter::Auto3db {
+        if filter.is_auto() {
+            self.xb200_auto_filter[ch as usize] = Some(filter);
             let frequency = self.get_frequency(ch)?;
-            log::trace!("[xb200_set_filterbank] get_frequency {frequency}");
+            log::trace!(target: log_target::XB, "[xb200_set_filterbank] get_frequency {frequency}");
             self.xb200_auto_filter_selection(ch, frequency)
         } else {
+            self.xb200_auto_filter[ch as usize] = None;
             self.set_filterbank_mux(ch, filter)
         }
     }
-    /// Selects the filter bank automatically based on frequency and the currently
-    /// configured auto mode (1 dB or 3 dB threshold). For frequencies above 300 MHz,
-    /// returns immediately without changing the filter (band is outside XB-200 range).
+    /// Selects the filter bank automatically based on frequency and the auto mode
+    /// (1 dB or 3 dB threshold) set with `xb200_set_filterbank`. Does nothing if
+    /// automatic selection is not enabled for the channel. For frequencies above
+    /// 300 MHz, returns immediately without changing the filter (band is outside
+    /// XB-200 range).
     pub fn xb200_auto_filter_selection(&mut self, channel: Channel, frequency: u64) -> Result<()> {
         self.require_initialized()?;
         if frequency >= 300_000_000 {
             return Ok(());
         }
         if !self.nios.xb200_is_enabled()? {
-            log::error!("xb_200 not enabled! need to enable?");
+            log::error!(target: log_target::XB, "xb_200 not enabled! need to enable?");
             return Err(Error::Unsupported("XB200 not enabled"));
         }
-        let fb = self.xb200_get_filterbank(channel)?;
-        log::trace!("xb_200 current filterbank: {fb:?}");
-        let filter = match fb {
-            Xb200Filter::Auto1db => select_filter_from_table(frequency, AUTO_1DB_FILTERS),
-            Xb200Filter::Auto3db => select_filter_from_table(frequency, AUTO_3DB_FILTERS),
+        let mode = self.xb200_auto_filter[channel as usize];
+        log::trace!(target: log_target::XB, "xb_200 auto filter mode: {mode:?}");
+        let filter = match mode {
+            Some(Xb200Filter::Auto1db) => select_filter_from_table(frequency, AUTO_1DB_FILTERS),
+            Some(Xb200Filter::Auto3db) => select_filter_from_table(frequency, AUTO_3DB_FILTERS),
             _ => {
-                log::debug!("not setting filterbank! current value: {fb:?}!");
+                log::debug!(target: log_target::XB, "automatic filter selection not enabled");
                 return Ok(());
             }
         };
@@ -263,7 +349,7 @@ impl RfLinkSession<'_> {
         }
         self.lms().write(0x5A, lval)?;
         let mut val = self.nios.nios_expansion_gpio_read()?;
-        log::trace!("[xb200_set_path] expansion_gpio_read: {val}");
+        log::trace!(target: log_target::XB, "[xb200_set_path] expansion_gpio_read: {val}");
         if (val & BLADERF_XB_RF_ON) == 0 {
             self.xb200_attach()?;
         }
@@ -296,25 +382,32 @@ impl RfLinkSession<'_> {
     pub fn xb200_get_path(&mut self, ch: Channel) -> Result<Xb200Path> {
         self.require_initialized()?;
         let val = self.nios.nios_expansion_gpio_read()?;
-        log::trace!("[xb200_get_path] expansion_gpio_read: {val:#010x}");
+        log::trace!(target: log_target::XB, "[xb200_get_path] expansion_gpio_read: {val:#010x}");
         let bypass_bit = if ch == Channel::Rx {
             BLADERF_XB_CONFIG_RX_BYPASS
         } else {
             BLADERF_XB_CONFIG_TX_BYPASS
         };
         log::trace!(
+            target: log_target::XB,
             "[xb200_get_path] bypass_bit={bypass_bit:#x}, val & bypass_bit = {:#x}",
             val & bypass_bit
         );
         if (val & bypass_bit) != 0 {
-            log::trace!("[xb200_get_path] returning Mix");
+            log::trace!(target: log_target::XB, "[xb200_get_path] returning Mix");
             Ok(Xb200Path::Mix)
         } else {
-            log::trace!("[xb200_get_path] returning Bypass");
+            log::trace!(target: log_target::XB, "[xb200_get_path] returning Bypass");
             Ok(Xb200Path::Bypass)
         }
     }
 }
+/// Returns the ADF4351 register 2 value used by `xb200_attach()` with the
+/// given MUXOUT selection.
+fn adf4351_reg2(muxout: u32) -> u32 {
+    0x60008E42 | (1 << 8) | (muxout << 26)
+}
+
 /// Looks up the appropriate `Xb200Filter` for the given frequency from the
 /// provided table. Returns `Xb200Filter::Custom` if no range matches.
 pub(crate) fn select_filter_from_table(frequency: u64, table: &[FilterEntry]) -> Xb200Filter {
@@ -7,6 +7,7 @@
 
 use crate::bladerf1::board::RfLinkSession;
 use crate::error::Result;
+use crate::log_target;
 pub(crate) const BLADERF_XB_AUX_EN: u32 = 0x000002;
 pub(crate) const BLADERF_XB_TX_LED: u32 = 0x000010;
 pub(crate) const BLADERF_XB_RX_LED: u32 = 0x000020;
@@ -19,35 +20,73 @@ pub(crate) const BLADERF_XB_CS: u32 = 0x010000;
 pub(crate) const BLADERF_XB_CSEL: u32 = 0x040000;
 pub(crate) const BLADERF_XB_DOUT: u32 = 0x100000;
 pub(crate) const BLADERF_XB_SCLK: u32 = 0x400000;
-pub(crate) const XB300_DETECT_MASK: u32 = BLADERF_XB_CS | BLADERF_XB_CSEL | BLADERF_XB_LNA_EN;
+/// Expansion GPIO directions programmed by `xb300_attach()`, used as the
+/// XB-300 detection signature.
+pub(crate) const XB300_DIR_SIGNATURE: u32 = BLADERF_XB_TX_LED
+    | BLADERF_XB_RX_LED
+    | BLADERF_XB_TRX_MASK
+    | BLADERF_XB_PA_EN
+    | BLADERF_XB_LNA_EN
+    | BLADERF_XB_CSEL
+    | BLADERF_XB_SCLK
+    | BLADERF_XB_CS;
+/// XB-300 TRX switch position, routing the antenna connector to the PA or LNA.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum BladeRfXb300Trx {
+pub enum Xb300Trx {
+    /// Antenna connected to the TX power amplifier.
     Tx = 0,
+    /// Antenna connected to the RX low-noise amplifier.
     Rx,
+    /// Neither switch control line asserted.
     Unset,
 }
+/// XB-300 amplifier selection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum BladeRfXb300Amplifier {
+pub enum Xb300Amplifier {
+    /// TX power amplifier (SE2623L).
     Pa = 0,
+    /// RX low-noise amplifier (TSS-53LNB+).
     Lna,
+    /// Auxiliary amplifier (Amp 3).
     Aux,
 }
+impl Xb300Amplifier {
+    /// Returns `gpio` with this amplifier enabled or disabled.
+    ///
+    /// The PA and LNA also drive their TX/RX LEDs. The LNA enable line is
+    /// active low.
+    pub fn apply_to_gpio(self, enable: bool, gpio: u32) -> u32 {
+        match (self, enable) {
+            (Xb300Amplifier::Pa, true) => gpio | BLADERF_XB_TX_LED | BLADERF_XB_PA_EN,
+            (Xb300Amplifier::Pa, false) => gpio & !(BLADERF_XB_TX_LED | BLADERF_XB_PA_EN),
+            (Xb300Amplifier::Lna, true) => (gpio | BLADERF_XB_RX_LED) & !BLADERF_XB_LNA_EN,
+            (Xb300Amplifier::Lna, false) => (gpio & !BLADERF_XB_RX_LED) | BLADERF_XB_LNA_EN,
+            (Xb300Amplifier::Aux, true) => gpio | BLADERF_XB_AUX_EN,
+            (Xb300Amplifier::Aux, false) => gpio & !BLADERF_XB_AUX_EN,
+        }
+    }
+
+    /// Returns `true` if this amplifier is enabled in the expansion GPIO value.
+    pub fn is_enabled_in_gpio(self, gpio: u32) -> bool {
+        match self {
+            Xb300Amplifier::Pa => (gpio & BLADERF_XB_PA_EN) != 0,
+            Xb300Amplifier::Lna => (gpio & BLADERF_XB_LNA_EN) == 0,
+            Xb300Amplifier::Aux => (gpio & BLADERF_XB_AUX_EN) != 0,
+        }
+    }
+}
 impl RfLinkSession<'_> {
+    /// Attaches the XB-300: configures expansion GPIO directions and leaves
+    /// the LNA disabled.
     pub fn xb300_attach(&mut self) -> Result<()> {
         self.require_initialized()?;
-        let mut val = BLADERF_XB_TX_LED
-            | BLADERF_XB_RX_LED
-            | BLADERF_XB_TRX_MASK
-            | BLADERF_XB_PA_EN
-            | BLADERF_XB_LNA_EN
-            | BLADERF_XB_CSEL
-            | BLADERF_XB_SCLK
-            | BLADERF_XB_CS;
-        self.nios.nios_expansion_gpio_dir_write(0xffffffff, val)?;
-        val = BLADERF_XB_CS | BLADERF_XB_LNA_EN;
+        self.nios
+            .nios_expansion_gpio_dir_write(0xffffffff, XB300_DIR_SIGNATURE)?;
+        let val = BLADERF_XB_CS | BLADERF_XB_LNA_EN;
         self.nios.nios_expansion_gpio_write(0xffffffff, val)?;
         Ok(())
     }
+    /// Enables the XB-300 and primes the power detector with a dummy read.
     pub fn xb300_enable(&mut self, _enable: bool) -> Result<()> {
         self.require_initialized()?;
         let val = BLADERF_XB_CS | BLADERF_XB_CSEL | BLADERF_XB_LNA_EN;
@@ -55,80 +94,53 @@ impl RfLinkSession<'_> {
         let _pwr = self.xb300_get_output_power()?;
         Ok(())
     }
+    /// Initializes the XB-300 with the TRX switch in the TX position.
     pub fn xb300_init(&mut self) -> Result<()> {
         self.require_initialized()?;
-        log::debug!("Setting TRX path to TX");
-        self.xb300_set_trx(BladeRfXb300Trx::Tx)
+        log::debug!(target: log_target::XB, "Setting TRX path to TX");
+        self.xb300_set_trx(Xb300Trx::Tx)
     }
-    pub fn xb300_set_trx(&mut self, trx: BladeRfXb300Trx) -> Result<()> {
+    /// Sets the TRX switch position.
+    pub fn xb300_set_trx(&mut self, trx: Xb300Trx) -> Result<()> {
         self.require_initialized()?;
         let mut val = self.nios.nios_expansion_gpio_read()?;
         val &= !BLADERF_XB_TRX_MASK;
         match trx {
-            BladeRfXb300Trx::Rx => val |= BLADERF_XB_TRX_RXN,
-            BladeRfXb300Trx::Tx => val |= BLADERF_XB_TRX_TXN,
-            BladeRfXb300Trx::Unset => {}
+            Xb300Trx::Rx => val |= BLADERF_XB_TRX_RXN,
+            Xb300Trx::Tx => val |= BLADERF_XB_TRX_TXN,
+            Xb300Trx::Unset => {}
         }
         self.nios.nios_expansion_gpio_write(0xffffffff, val)
     }
-    pub fn xb300_get_trx(&mut self) -> Result<BladeRfXb300Trx> {
+    /// Returns the TRX switch position.
+    pub fn xb300_get_trx(&mut self) -> Result<Xb300Trx> {
         self.require_initialized()?;
         let mut val = self.nios.nios_expansion_gpio_read()?;
         val &= BLADERF_XB_TRX_MASK;
         let trx = if val == 0 {
-            BladeRfXb300Trx::Unset
+            Xb300Trx::Unset
         } else if (val & BLADERF_XB_TRX_RXN) != 0 {
-            BladeRfXb300Trx::Rx
+            Xb300Trx::Rx
         } else {
-            BladeRfXb300Trx::Tx
+            Xb300Trx::Tx
         };
         Ok(trx)
     }
-    pub fn xb300_set_amplifier_enable(
-        &mut self,
-        amp: BladeRfXb300Amplifier,
-        enable: bool,
-    ) -> Result<()> {
+    /// Enables or disables one of the XB-300 amplifiers.
+    pub fn xb300_set_amplifier_enable(&mut self, amp: Xb300Amplifier, enable: bool) -> Result<()> {
         self.require_initialized()?;
-        let mut val = self.nios.nios_expansion_gpio_read()?;
-        match amp {
-            BladeRfXb300Amplifier::Pa => {
-                if enable {
-                    val |= BLADERF_XB_TX_LED;
-                    val |= BLADERF_XB_PA_EN;
-                } else {
-                    val &= !BLADERF_XB_TX_LED;
-                    val &= !BLADERF_XB_PA_EN;
-                }
-            }
-            BladeRfXb300Amplifier::Lna => {
-                if enable {
-                    val |= BLADERF_XB_RX_LED;
-                    val &= !BLADERF_XB_LNA_EN;
-                } else {
-                    val &= !BLADERF_XB_RX_LED;
-                    val |= BLADERF_XB_LNA_EN;
-                }
-            }
-            BladeRfXb300Amplifier::Aux => {
-                if enable {
-                    val |= BLADERF_XB_AUX_EN;
-                } else {
-                    val &= !BLADERF_XB_AUX_EN;
-                }
-            }
-        }
-        self.nios.nios_expansion_gpio_write(0xffffffff, val)
+        let val = self.nios.nios_expansion_gpio_read()?;
+        self.nios
+            .nios_expansion_gpio_write(0xffffffff, amp.apply_to_gpio(enable, val))
     }
-    pub fn xb300_get_amplifier_enable(&mut self, amp: BladeRfXb300Amplifier) -> Result<bool> {
+    /// Returns `true` if the given XB-300 amplifier is enabled.
+    pub fn xb300_get_amplifier_enable(&mut self, amp: Xb300Amplifier) -> Result<bool> {
         self.require_initialized()?;
         let val = self.nios.nios_expansion_gpio_read()?;
-        match amp {
-            BladeRfXb300Amplifier::Pa => Ok((val & BLADERF_XB_PA_EN) != 0),
-            BladeRfXb300Amplifier::Lna => Ok((val & BLADERF_XB_LNA_EN) != 0),
-            BladeRfXb300Amplifier::Aux => Ok((val & BLADERF_XB_AUX_EN) != 0),
-        }
+        Ok(amp.is_enabled_in_gpio(val))
     }
+    /// Reads the XB-300 power detector over its SPI-over-GPIO ADC and returns
+    /// the measured output power in dBm.
     pub fn xb300_get_output_power(&mut self) -> Result<f32> {
         self.require_initialized()?;
         let mut ret = 0;
@@ -144,7 +156,7 @@ impl RfLinkSession<'_> {
                 .nios_expansion_gpio_write(0xffffffff, BLADERF_XB_SCLK | val)?;
             let rval = self.nios.nios_expansion_gpio_read()?;
             if (2..=11).contains(&i) {
-                ret |= (!!(rval & BLADERF_XB_DOUT)) << (11 - i);
+                ret |= u32::from((rval & BLADERF_XB_DOUT) != 0) << (11 - i);
             }
         }
         let volt = (1.8f32 / 1_024.0f32) * ret as f32;
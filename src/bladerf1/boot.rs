@@ -0,0 +1,286 @@
+//! Cypress FX3 ROM bootloader access for BladeRF1 firmware recovery.
+//!
+//! After [`BladeRf1::jump_to_bootloader`](crate::bladerf1::BladeRf1::jump_to_bootloader),
+//! or when the SPI flash holds no bootable firmware, the FX3 enumerates as
+//! its ROM bootloader. [`BladeRfBoot`] opens that device and downloads an FX3
+//! firmware image (`.img`) into RAM, where it runs until the next reset.
+//!
+//! # Image format
+//!
+//! An image starts with the magic `"CY"`, a control byte (bit 0 clear for an
+//! executable image) and the image type `0xB0`. Sections follow, each as a
+//! little-endian length in 32-bit words, a load address and the data. A
+//! section of length zero terminates the list and carries the entry point,
+//! followed by the checksum: the wrapping sum of all section data words.
+
+use crate::bladerf1::board::{FX3_BOOTLOADER_USB_PID, FX3_BOOTLOADER_USB_VID};
+use crate::error::{Error, Result};
+use crate::log_target;
+use nusb::transfer::{ControlIn, ControlOut, ControlType, Recipient};
+use nusb::{Interface, MaybeFuture};
+use std::time::Duration;
+
+/// Magic bytes at the start of an FX3 firmware image.
+pub const FX3_IMAGE_MAGIC: [u8; 2] = *b"CY";
+
+/// Image type of a normal FX3 firmware image with checksum.
+pub const FX3_IMAGE_TYPE_CHECKSUM: u8 = 0xB0;
+
+/// Vendor request the FX3 bootloader uses for RAM reads, writes and the
+/// final jump to the entry point.
+pub const FX3_BOOTLOADER_LOAD_REQUEST: u8 = 0xA0;
+
+/// Largest RAM write accepted by the FX3 bootloader in one request, in bytes.
+pub const FX3_BOOTLOADER_MAX_LOAD_LEN: usize = 4096;
+
+/// FX3 instruction tightly-coupled memory, as a half-open address range.
+const FX3_ITCM: (u32, u32) = (0x0000_0000, 0x0000_4000);
+/// FX3 system RAM, as a half-open address range.
+const FX3_SYSMEM: (u32, u32) = (0x4000_0000, 0x4008_0000);
+
+const TIMEOUT: Duration = Duration::from_secs(3);
+
+/// One section of an FX3 firmware image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fx3Section<'a> {
+    /// RAM address the section is loaded to.
+    pub address: u32,
+    /// Section contents, a whole number of 32-bit words.
+    pub data: &'a [u8],
+}
+
+/// A parsed and validated FX3 firmware image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fx3Image<'a> {
+    /// Sections in image order.
+    pub sections: Vec<Fx3Section<'a>>,
+    /// Address execution starts at after the download.
+    pub entry_point: u32,
+}
+
+/// Returns the FX3 image checksum of `data`: the wrapping sum of its
+/// little-endian 32-bit words. Trailing bytes that do not fill a word are
+/// ignored.
+pub fn fx3_checksum(data: &[u8]) -> u32 {
+    data.chunks_exact(4)
+        .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+        .fold(0, u32::wrapping_add)
+}
+
+fn is_fx3_ram(address: u32, len: usize) -> bool {
+    let Some(end) = u32::try_from(len)
+        .ok()
+        .and_then(|len| address.checked_add(len))
+    else {
+        return false;
+    };
+    [FX3_ITCM, FX3_SYSMEM]
+        .iter()
+        .any(|&(start, limit)| address >= start && address < limit && end <= limit)
+}
+
+fn truncated(offset: usize) -> Error {
+    Error::Argument(format!("FX3 image truncated at byte {offset}"))
+}
+
+fn read_u32(img: &[u8], offset: usize) -> Result<u32> {
+    offset
+        .checked_add(4)
+        .and_then(|end| img.get(offset..end))
+        .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+        .ok_or_else(|| truncated(offset))
+}
+
+impl<'a> Fx3Image<'a> {
+    /// Parses an FX3 firmware image and checks it before anything is written
+    /// to the device.
+    ///
+    /// Returns `Error::Argument` if the magic or image type is wrong, the
+    /// image is not executable, a section or the entry point lies outside
+    /// FX3 RAM, the image is truncated or has trailing data, or the checksum
+    /// does not match.
+    pub fn parse(img: &'a [u8]) -> Result<Self> {
+        if img.len() < 4 || img[..2] != FX3_IMAGE_MAGIC {
+            return Err(Error::Argument(
+                "not an FX3 image: missing \"CY\" magic".into(),
+            ));
+        }
+        if img[2] & 0x01 != 0 {
+            return Err(Error::Argument("FX3 image is not executable".into()));
+        }
+        if img[3] != FX3_IMAGE_TYPE_CHECKSUM {
+            return Err(Error::Argument(format!(
+                "unsupported FX3 image type {:#04x}",
+                img[3]
+            )));
+        }
+
+        let mut sections = Vec::new();
+        let mut checksum = 0u32;
+        let mut offset = 4;
+        let entry_point = loop {
+            let words = read_u32(img, offset)? as usize;
+            let address = read_u32(img, offset + 4)?;
+            offset += 8;
+            if words == 0 {
+                break address;
+            }
+            let len = words.checked_mul(4).ok_or_else(|| truncated(offset))?;
+            let data = offset
+                .checked_add(len)
+                .and_then(|end| img.get(offset..end))
+                .ok_or_else(|| truncated(offset))?;
+            if !is_fx3_ram(address, len) {
+                return Err(Error::Argument(format!(
+                    "FX3 image section at {address:#010x} ({len} bytes) is outside FX3 RAM"
+                )));
+            }
+            checksum = checksum.wrapping_add(fx3_checksum(data));
+            sections.push(Fx3Section { address, data });
+            offset += len;
+        };
+        if !is_fx3_ram(entry_point, 0) {
+            return Err(Error::Argument(format!(
+                "FX3 image entry point {entry_point:#010x} is outside FX3 RAM"
+            )));
+        }
+
+        let expected = read_u32(img, offset)?;
+        if offset + 4 != img.len() {
+            return Err(Error::Argument(format!(
+                "FX3 image has {} bytes after the checksum",
+                img.len() - offset - 4
+            )));
+        }
+        if checksum != expected {
+            return Err(Error::Argument(format!(
+                "FX3 image checksum {checksum:#010x} does not match {expected:#010x}"
+            )));
+        }
+        Ok(Self {
+            sections,
+            entry_point,
+        })
+    }
+}
+
+/// Handle to a BladeRF1 whose FX3 is running its ROM bootloader.
+pub struct BladeRfBoot {
+    interface: Interface,
+}
+
+impl BladeRfBoot {
+    /// Opens the first FX3 bootloader device found on the bus.
+    ///
+    /// Any Cypress FX3 in bootloader mode matches, since the bootloader has
+    /// no BladeRF-specific descriptors.
+    ///
+    /// Returns `Error::NotFound` if no bootloader device is connected. Not
+    /// available on Android, where USB enumeration is not permitted.
+    #[cfg(not(target_os = "android"))]
+    pub fn from_first() -> Result<Self> {
+        let device = nusb::list_devices()
+            .wait()?
+            .find(|dev| {
+                dev.vendor_id() == FX3_BOOTLOADER_USB_VID
+                    && dev.product_id() == FX3_BOOTLOADER_USB_PID
+            })
+            .ok_or(Error::NotFound)?
+            .open()
+            .wait()?;
+        Self::from_device(device)
+    }
+
+    /// Opens an FX3 bootloader device from a pre-opened file descriptor.
+    ///
+    /// Available on Linux and Android.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn from_fd(fd: std::os::fd::OwnedFd) -> Result<Self> {
+        Self::from_device(nusb::Device::from_fd(fd).wait()?)
+    }
+
+    fn from_device(device: nusb::Device) -> Result<Self> {
+        Ok(Self {
+            interface: device.detach_and_claim_interface(0).wait()?,
+        })
+    }
+
+    /// Downloads the FX3 firmware image `img` into RAM and starts it.
+    ///
+    /// The image is parsed and checked with [`Fx3Image::parse`] first, so a
+    /// malformed image is rejected before any write. Each section is written
+    /// in chunks of at most `FX3_BOOTLOADER_MAX_LOAD_LEN` bytes, and every
+    /// chunk is read back and compared. Finally the bootloader jumps to the
+    /// entry point, after which the device re-enumerates as a BladeRF1 and
+    /// this handle is no longer usable. A transient error from the jump is
+    /// ignored, since the bootloader may leave the bus before acknowledging
+    /// it. The firmware runs from RAM only; use a `FlashSession` to make it
+    /// persistent.
+    ///
+    /// Returns `Error::Argument` for an invalid image and
+    /// `Error::BootloaderVerificationFailed` if a chunk reads back differently.
+    pub fn download_firmware(&self, img: &[u8]) -> Result<()> {
+        let image = Fx3Image::parse(img)?;
+        for section in &image.sections {
+            log::debug!(
+                target: log_target::USB,
+                "Loading {} bytes to FX3 RAM at {:#010x}",
+                section.data.len(),
+                section.address
+            );
+            for (index, chunk) in section.data.chunks(FX3_BOOTLOADER_MAX_LOAD_LEN).enumerate() {
+                let address = section.address + (index * FX3_BOOTLOADER_MAX_LOAD_LEN) as u32;
+                self.write_ram(address, chunk)?;
+                let readback = self.read_ram(address, chunk.len())?;
+                if let Some(pos) = chunk.iter().zip(&readback).position(|(a, b)| a != b) {
+                    return Err(Error::BootloaderVerificationFailed {
+                        address: address + pos as u32,
+                        expected: chunk[pos],
+                        actual: readback[pos],
+                    });
+                }
+            }
+        }
+        log::debug!(
+            target: log_target::USB,
+            "Starting FX3 firmware at {:#010x}",
+            image.entry_point
+        );
+        match self.write_ram(image.entry_point, &[]) {
+            Err(err) if !err.is_transient() => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    fn write_ram(&self, address: u32, data: &[u8]) -> Result<()> {
+        let pkt = ControlOut {
+            control_type: ControlType::Vendor,
+            recipient: Recipient::Device,
+            request: FX3_BOOTLOADER_LOAD_REQUEST,
+            value: (address & 0xFFFF) as u16,
+            index: (address >> 16) as u16,
+            data,
+        };
+        self.interface.control_out(pkt, TIMEOUT).wait()?;
+        Ok(())
+    }
+
+    fn read_ram(&self, address: u32, len: usize) -> Result<Vec<u8>> {
+        let pkt = ControlIn {
+            control_type: ControlType::Vendor,
+            recipient: Recipient::Device,
+            request: FX3_BOOTLOADER_LOAD_REQUEST,
+            value: (address & 0xFFFF) as u16,
+            index: (address >> 16) as u16,
+            length: len as u16,
+        };
+        let data = self.interface.control_in(pkt, TIMEOUT).wait()?;
+        if data.len() < len {
+            return Err(Error::UsbControlResponseTooShort {
+                expected: len,
+                actual: data.len(),
+            });
+        }
+        Ok(data)
+    }
+}
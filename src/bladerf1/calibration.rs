@@ -1,12 +1,20 @@
 //! DC calibration table management — loading, saving, and looking up per-frequency
 //! DC offset correction values.  Tables are JSON files named `<serial>_dc_rx.json`
-//! and `<serial>_dc_tx.json`, auto-loaded at device open.
+//! and `<serial>_dc_tx.json`, auto-loaded at device open.  Binary `.tbl` tables
+//! written by libbladeRF can be imported with [`DcCalTable::from_bytes`].
 
 use crate::bladerf1::hardware::lms6002d::dc_calibration::DcCals;
 use crate::bladerf1::hardware::lms6002d::dc_calibration::{AgcDcCorrection, DcPair};
-use crate::error::Result;
+use crate::error::{Error, Result};
 use std::path::Path;
 
+/// Magic value at the start of a libbladeRF binary DC calibration table.
+pub const DC_CAL_TBL_MAGIC: u16 = 0x1ab1;
+
+/// Size of the binary table header: magic, version, entry count and ten
+/// LMS6002D register bytes.
+const DC_CAL_TBL_META_SIZE: usize = 20;
+
 /// Single calibration entry with frequency, DC offset I/Q pair, and AGC sub-ranges.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct DcCalEntry {
@@ -78,6 +86,97 @@ impl DcCalTable {
         Ok(serde_json::from_str(&buf)?)
     }
 
+    /// Load a libbladeRF binary calibration table (`.tbl`) from a file.
+    pub fn load_tbl(path: &Path) -> Result<Self> {
+        let buf = std::fs::read(path)?;
+        Self::from_bytes(&buf)
+    }
+
+    /// Parse a libbladeRF binary calibration table.
+    ///
+    /// All fields are little-endian: `u16` magic ([`DC_CAL_TBL_MAGIC`]), `u32`
+    /// version, `u32` entry count, then one byte each for the LMS6002D register
+    /// values in [`DcCals`] field order.  Version 1 entries are a `u32`
+    /// frequency followed by `i16` DC I and Q; version 2 entries append `i16`
+    /// I/Q pairs for the max, mid and min AGC gain settings.
+    ///
+    /// Entries are sorted by frequency so that lookups can binary-search.
+    ///
+    /// # Errors
+    /// Returns `Error::Argument` if the magic or version is not recognized,
+    /// or if `buf` is too short for the advertised number of entries.
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        if buf.len() < DC_CAL_TBL_META_SIZE {
+            return Err(Error::Argument(format!(
+                "DC cal table too short: {} bytes",
+                buf.len()
+            )));
+        }
+        let magic = u16::from_le_bytes([buf[0], buf[1]]);
+        if magic != DC_CAL_TBL_MAGIC {
+            return Err(Error::Argument(format!(
+                "invalid DC cal table magic: {magic:#06x}"
+            )));
+        }
+        let version = u32::from_le_bytes([buf[2], buf[3], buf[4], buf[5]]);
+        let entry_size = match version {
+            1 => 8,
+            2 => 20,
+            _ => {
+                return Err(Error::Argument(format!(
+                    "unsupported DC cal table version: {version}"
+                )));
+            }
+        };
+        let n_entries = u32::from_le_bytes([buf[6], buf[7], buf[8], buf[9]]) as usize;
+        let expected = n_entries
+            .checked_mul(entry_size)
+            .and_then(|n| n.checked_add(DC_CAL_TBL_META_SIZE));
+        if expected.is_none_or(|n| buf.len() < n) {
+            return Err(Error::Argument(format!(
+                "DC cal table too short for {n_entries} entries: {} bytes",
+                buf.len()
+            )));
+        }
+
+        let r = &buf[10..DC_CAL_TBL_META_SIZE];
+        let reg_vals = DcCals::new(
+            r[0] as i16,
+            r[1] as i16,
+            r[2] as i16,
+            r[3] as i16,
+            r[4] as i16,
+            r[5] as i16,
+            r[6] as i16,
+            r[7] as i16,
+            r[8] as i16,
+            r[9] as i16,
+        );
+
+        let pair = |b: &[u8]| {
+            DcPair::new(
+                i16::from_le_bytes([b[0], b[1]]),
+                i16::from_le_bytes([b[2], b[3]]),
+            )
+        };
+        let mut entries: Vec<DcCalEntry> = buf[DC_CAL_TBL_META_SIZE..]
+            .chunks_exact(entry_size)
+            .take(n_entries)
+            .map(|e| {
+                let freq = u32::from_le_bytes([e[0], e[1], e[2], e[3]]);
+                let entry = DcCalEntry::new(freq, pair(&e[4..8]));
+                if version >= 2 {
+                    entry.with_agc(pair(&e[8..12]), pair(&e[12..16]), pair(&e[16..20]))
+                } else {
+                    entry
+                }
+            })
+            .collect();
+        entries.sort_by_key(|e| e.freq);
+
+        Ok(Self { reg_vals, entries })
+    }
+
     /// Serialize the calibration table to a JSON file.
     pub fn save(&self, path: &Path) -> Result<()> {
         let json = serde_json::to_string_pretty(self)?;
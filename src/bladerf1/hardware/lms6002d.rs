@@ -11,6 +11,7 @@ pub mod gain;
 pub mod loopback;
 use crate::channel::Channel;
 use crate::error::{Error, Result};
+use crate::log_target;
 use crate::nios_client::NiosCore;
 use crate::protocol::nios::NiosPkt8x8Target;
 pub use filters::LpfMode;
@@ -39,6 +40,21 @@ impl From<u32> for Band {
         Band::from(freq as u64)
     }
 }
+
+impl Band {
+    /// Returns `gpio` with the config GPIO band-select field of `channel` set to this band.
+    ///
+    /// Only bits `[4:3]` (TX) or `[6:5]` (RX) are modified; all other bits are preserved.
+    pub fn apply_to_config_gpio(self, channel: Channel, gpio: u32) -> u32 {
+        let band_value: u32 = match self {
+            Band::Low => 2,
+            Band::High => 1,
+        };
+        let shift = if channel == Channel::Tx { 3 } else { 5 };
+        let field_mask = 3 << shift;
+        (gpio & !field_mask) | (band_value << shift)
+    }
+}
 /// PLL tuning strategy.
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum Tune {
@@ -130,6 +146,18 @@ impl<'a> Lms6002d<'a> {
             .nios_write::<u8, u8>(NiosPkt8x8Target::Lms6, addr, data)
     }
 
+    /// Reads every register in `LMS_REG_DUMPSET` and returns
+    /// `(address, value)` pairs in that order.
+    ///
+    /// The NIOS protocol has no multi-register read, so this issues one
+    /// 8x8 packet per address.
+    pub fn dump_registers(&mut self) -> Result<Vec<(u8, u8)>> {
+        LMS_REG_DUMPSET
+            .iter()
+            .map(|&addr| Ok((addr, self.read(addr)?)))
+            .collect()
+    }
+
     pub(crate) fn set(&mut self, addr: u8, mask: u8) -> Result<()> {
         let data = self.read(addr)?;
         self.write(addr, data | mask)
@@ -161,9 +189,18 @@ impl<'a> Lms6002d<'a> {
         self.write(addr, data)
     }
 
+    pub(crate) fn is_rffe_enabled(&mut self, channel: Channel) -> Result<bool> {
+        let (addr, shift) = if channel == Channel::Tx {
+            (0x40u8, 1u8)
+        } else {
+            (0x70u8, 0u8)
+        };
+        Ok((self.read(addr)? & (1 << shift)) != 0)
+    }
+
     pub(crate) fn select_band(&mut self, channel: Channel, band: Band) -> Result<()> {
         if self.is_loopback_enabled()? {
-            log::debug!("Loopback enabled!");
+            log::debug!(target: log_target::LMS, "Loopback enabled!");
             return Ok(());
         }
         match channel {
@@ -14,6 +14,7 @@ use crate::bladerf1::hardware::lms6002d::gain::{
     GAIN_SPEC_LNA, GAIN_SPEC_RXVGA1, GAIN_SPEC_RXVGA2, LnaGainCode,
 };
 use crate::error::{Error, Result};
+use crate::log_target;
 use std::cmp::PartialEq;
 use std::fmt::{Display, Formatter};
 
@@ -376,12 +377,27 @@ impl<'a> Lms6002d<'a> {
             }
         }
         if !converged {
-            log::warn!("DC Calibration (module={module:?}) failed to converge.");
+            log::warn!(
+                target: log_target::LMS,
+                "DC Calibration (module={module:?}) failed to converge."
+            );
         }
         let _ = self.dc_cal_module_deinit(module);
         self.dc_cal_restore(module, &state)
     }
 
+    pub(crate) fn get_lpf_tuning(&mut self) -> Result<u8> {
+        Ok(self.read(0x55)? & 0x3f)
+    }
+
+    pub(crate) fn set_lpf_tuning(&mut self, value: u8) -> Result<()> {
+        for addr in [0x35, 0x55] {
+            let val = (self.read(addr)? & !0x3f) | (value & 0x3f);
+            self.write(addr, val)?;
+        }
+        Ok(())
+    }
+
     pub(crate) fn set_dc_cals(&mut self, dc_cals: DcCals) -> Result<()> {
         let cal_tx_lpf: bool = (dc_cals.tx_lpf_i >= 0) || (dc_cals.tx_lpf_q >= 0);
         let cal_rx_lpf: bool = (dc_cals.rx_lpf_i >= 0) || (dc_cals.rx_lpf_q >= 0);
@@ -469,7 +485,7 @@ impl<'a> Lms6002d<'a> {
     }
 
     fn dc_cal_loop(&mut self, base: u8, cal_address: u8, dc_cntval: u8) -> Result<u8> {
-        log::debug!("Calibrating module {base:#x}:{cal_address:#x}");
+        log::debug!(target: log_target::LMS, "Calibrating module {base:#x}:{cal_address:#x}");
         let mut val = self.read(base + 0x03)?;
         val &= !0x07;
         val |= cal_address & 0x07;
@@ -487,11 +503,11 @@ impl<'a> Lms6002d<'a> {
             let val = self.read(base + 0x01)?;
             if ((val >> 1) & 1) == 0 {
                 let dc_regval = self.read(base)? & 0x3f;
-                log::debug!("DC_REGVAL: {dc_regval}");
+                log::debug!(target: log_target::LMS, "DC_REGVAL: {dc_regval}");
                 return Ok(dc_regval);
             }
         }
-        log::warn!("DC calibration loop did not converge.");
+        log::warn!(target: log_target::LMS, "DC calibration loop did not converge.");
         Err(Error::CalibrationFailed("loop did not converge"))
     }
 
@@ -579,10 +595,13 @@ impl<'a> Lms6002d<'a> {
         let base = module.base_addr();
         let mut dc_regval = self.dc_cal_loop(base, submodule, 31)?;
         if dc_regval == 31 {
-            log::debug!("DC_REGVAL suboptimal value - retrying DC cal loop.");
+            log::debug!(
+                target: log_target::LMS,
+                "DC_REGVAL suboptimal value - retrying DC cal loop."
+            );
             dc_regval = self.dc_cal_loop(base, submodule, 0)?;
             if dc_regval == 0 {
-                log::debug!("Bad DC_REGVAL detected. DC cal failed.");
+                log::debug!(target: log_target::LMS, "Bad DC_REGVAL detected. DC cal failed.");
                 return Ok(converged);
             }
         }
@@ -613,7 +632,10 @@ impl<'a> Lms6002d<'a> {
             DcCalModule::RxLpf => {
                 if state.rxvga1_curr_gain > GAIN_SPEC_RXVGA1.min as i32 {
                     state.rxvga1_curr_gain -= 1;
-                    log::debug!("Retrying DC cal with RXVGA1={}", state.rxvga1_curr_gain);
+                    log::debug!(
+                        target: log_target::LMS,
+                        "Retrying DC cal with RXVGA1={}", state.rxvga1_curr_gain
+                    );
                     self.rxvga1_set_gain((state.rxvga1_curr_gain as i8).into())?;
                 } else {
                     limit_reached = true;
@@ -622,11 +644,17 @@ impl<'a> Lms6002d<'a> {
             DcCalModule::RxVga2 => {
                 if state.rxvga1_curr_gain > GAIN_SPEC_RXVGA1.min as i32 {
                     state.rxvga1_curr_gain -= 1;
-                    log::debug!("Retrying DC cal with RXVGA1={}", state.rxvga1_curr_gain);
+                    log::debug!(
+                        target: log_target::LMS,
+                        "Retrying DC cal with RXVGA1={}", state.rxvga1_curr_gain
+                    );
                     self.rxvga1_set_gain((state.rxvga1_curr_gain as i8).into())?;
                 } else if state.rxvga2_curr_gain > GAIN_SPEC_RXVGA2.min as i32 {
                     state.rxvga2_curr_gain -= 3;
-                    log::debug!("Retrying DC cal with RXVGA2={}", state.rxvga2_curr_gain);
+                    log::debug!(
+                        target: log_target::LMS,
+                        "Retrying DC cal with RXVGA2={}", state.rxvga2_curr_gain
+                    );
                     self.rxvga2_set_gain((state.rxvga2_curr_gain as i8).into())?;
                 } else {
                     limit_reached = true;
@@ -637,7 +665,7 @@ impl<'a> Lms6002d<'a> {
             }
         }
         if limit_reached {
-            log::debug!("DC Cal retry limit reached");
+            log::debug!(target: log_target::LMS, "DC Cal retry limit reached");
         }
         Ok(limit_reached)
     }
@@ -666,6 +694,10 @@ impl<'a> Lms6002d<'a> {
 
     fn dc_cal_restore(&mut self, module: DcCalModule, state: &DcCalState) -> Result<()> {
         self.write(0x09, state.clk_en)?;
+        if module == DcCalModule::LpfTuning {
+            let dc_regval = self.read(0x00)?;
+            self.set_lpf_tuning(dc_regval)?;
+        }
         if module == DcCalModule::RxLpf || module == DcCalModule::RxVga2 {
             self.write(0x72, state.reg0x72)?;
             self.lna_set_gain(state.lna_gain.into())?;
@@ -4,6 +4,7 @@
 //! pass the full signal chain, or disabled entirely.
 
 use crate::bladerf1::hardware::lms6002d::Lms6002d;
+use crate::log_target;
 use crate::{Channel, Error};
 
 /// LPF operating mode.
@@ -45,7 +46,10 @@ impl<'a> Lms6002d<'a> {
             (false, true) => Ok(LpfMode::Bypassed),
             (false, false) => Ok(LpfMode::Disabled),
             (true, true) => {
-                log::error!("Invalid LPF configuration: {data_l:x}, {data_h:x}");
+                log::error!(
+                    target: log_target::LMS,
+                    "Invalid LPF configuration: {data_l:x}, {data_h:x}"
+                );
                 Err(Error::BoardState("LPF enabled and bypassed simultaneously"))
             }
         }
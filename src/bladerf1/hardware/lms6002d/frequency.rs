@@ -15,7 +15,8 @@ use crate::bladerf1::hardware::lms6002d::{
     VTUNE_DELAY_SMALL, VTUNE_MAX_ITERATIONS, VcoState,
 };
 use crate::channel::Channel;
-use crate::error::Error;
+use crate::error::{Error, ResultExt};
+use crate::log_target;
 use std::thread::sleep;
 use std::time::Duration;
 /// Minimum frequency with XB-200 expansion board enabled.
@@ -235,10 +236,13 @@ impl TryFrom<u64> for LmsFreq {
             let f_diff: f32 = (f_target - f_low) as f32;
             let vcocap = (num / denom * f_diff) + 0.5 + VCOCAP_EST_MIN as f32;
             if vcocap > VCOCAP_MAX_VALUE as f32 {
-                log::debug!("Clamping VCOCAP estimate from {vcocap} to {VCOCAP_MAX_VALUE}");
+                log::debug!(
+                    target: log_target::LMS,
+                    "Clamping VCOCAP estimate from {vcocap} to {VCOCAP_MAX_VALUE}"
+                );
                 VCOCAP_MAX_VALUE
             } else {
-                log::debug!("VCOCAP estimate: {vcocap}");
+                log::debug!(target: log_target::LMS, "VCOCAP estimate: {vcocap}");
                 vcocap as u8
             }
         }
@@ -250,16 +254,16 @@ impl TryFrom<u64> for LmsFreq {
                 "Could not determine frequency range".into(),
             ))?;
         let freqsel = freq_range.value;
-        log::trace!("freqsel: {freqsel}");
+        log::trace!(target: log_target::LMS, "freqsel: {freqsel}");
         let vcocap = estimate_vcocap(freq as u32, freq_range.low as u32, freq_range.high as u32);
-        log::trace!("vcocap: {vcocap}");
+        log::trace!(target: log_target::LMS, "vcocap: {vcocap}");
         let vco_x = 1u64 << ((freqsel & 7) - 3);
-        log::trace!("vco_x: {vco_x}");
+        log::trace!(target: log_target::LMS, "vco_x: {vco_x}");
         if vco_x > u8::MAX as u64 {
             return Err(Error::BoardState("VCO divider out of u8 range"));
         }
         let x = vco_x as u8;
-        log::trace!("x: {x}");
+        log::trace!(target: log_target::LMS, "x: {x}");
         let mut temp = (vco_x * freq) / LMS_REFERENCE_HZ as u64;
         if temp > u16::MAX as u64 {
             return Err(Error::Argument(
@@ -267,20 +271,20 @@ impl TryFrom<u64> for LmsFreq {
             ));
         }
         let nint = temp as u16;
-        log::trace!("nint: {nint}");
+        log::trace!(target: log_target::LMS, "nint: {nint}");
         let nfrac_num = (1u64 << 23) * (vco_x * freq - nint as u64 * LMS_REFERENCE_HZ as u64);
         temp = (nfrac_num + LMS_REFERENCE_HZ as u64 / 2) / LMS_REFERENCE_HZ as u64;
         if temp > u32::MAX as u64 {
             return Err(Error::BoardState("nfrac exceeds u32 range"));
         }
         let nfrac = temp as u32;
-        log::trace!("nfrac: {nfrac}");
+        log::trace!(target: log_target::LMS, "nfrac: {nfrac}");
         let flags = if Band::from(freq) == Band::Low {
             LMS_FREQ_FLAGS_LOW_BAND
         } else {
             0
         };
-        log::trace!("flags: {flags}");
+        log::trace!(target: log_target::LMS, "flags: {flags}");
         Ok(LmsFreq {
             freqsel,
             vcocap,
@@ -304,29 +308,49 @@ pub const fn get_frequency_max() -> u32 {
     BLADERF_FREQUENCY_MAX
 }
 
+/// Returns the RF frequency span of one VCOCAP step in Hz at `frequency`.
+///
+/// This is the width of the VCO band containing `frequency` divided by the
+/// VCOCAP estimation range, i.e. the slope used to estimate VCOCAP from the
+/// target frequency. Frequencies outside the LMS6002D range are clamped.
+pub fn vcocap_step_hz(frequency: u64) -> u64 {
+    let freq = frequency.clamp(BLADERF_FREQUENCY_MIN as u64, BLADERF_FREQUENCY_MAX as u64);
+    BANDS
+        .iter()
+        .find(|r| freq >= r.low && freq <= r.high)
+        .map_or(0, |r| (r.high - r.low) / VCOCAP_EST_RANGE as u64)
+}
+
 use super::Lms6002d;
 impl<'a> Lms6002d<'a> {
     pub(crate) fn config_charge_pumps(&mut self, channel: Channel) -> crate::Result<()> {
         let base: u8 = if channel == Channel::Rx { 0x20 } else { 0x10 };
-        let mut data = self.read(base + 6)?;
+        let mut data = self.read(base + 6).context("reading charge pump current")?;
         data &= !0x1f;
         data |= 0x0c;
-        self.write(base + 6, data)?;
-        let mut data = self.read(base + 7)?;
+        self.write(base + 6, data)
+            .context("writing charge pump current")?;
+        let mut data = self
+            .read(base + 7)
+            .context("reading charge pump offset up")?;
         data &= !0x1f;
         data |= 0x03;
-        self.write(base + 7, data)?;
-        let mut data = self.read(base + 8)?;
+        self.write(base + 7, data)
+            .context("writing charge pump offset up")?;
+        let mut data = self
+            .read(base + 8)
+            .context("reading charge pump offset down")?;
         data &= !0x1f;
         data |= 0x03;
         self.write(base + 8, data)
+            .context("writing charge pump offset down")
     }
 
     fn write_vcocap(&mut self, base: u8, vcocap: u8, vcocap_reg_state: u8) -> crate::Result<()> {
         if vcocap > VCOCAP_MAX_VALUE {
             return Err(Error::Argument("vcocap exceeds maximum value".into()));
         }
-        log::trace!("Writing VCOCAP={vcocap}");
+        log::trace!(target: log_target::LMS, "Writing VCOCAP={vcocap}");
         self.write(base + 9, vcocap | vcocap_reg_state)
     }
 
@@ -346,35 +370,44 @@ impl<'a> Lms6002d<'a> {
         let base: u8 = if channel == Channel::Rx { 0x20 } else { 0x10 };
         let pll_base: u8 = base | 0x80;
         f.vcocap_result = 0xff;
-        let mut data = self.read(0x09)?;
+        let mut data = self.read(0x09).context("reading clock enables")?;
         data |= 0x05;
-        self.write(0x09, data)?;
-        let vcocap_reg_state = match self.read(base + 9) {
+        self.write(0x09, data).context("enabling DSM clocks")?;
+        let vcocap_reg_state = match self.read(base + 9).context("reading VCOCAP") {
             Ok(v) => v,
             Err(e) => {
                 self.turn_off_dsms()?;
                 log::error!(
+                    target: log_target::LMS,
                     "Failed to read vcocap regstate! Device requires re-initialization (call initialize()) to restore DSM state."
                 );
                 return Err(e);
             }
         };
         let vcocap_reg_state = vcocap_reg_state & !0x3f;
-        if let Err(e) = self.write_vcocap(base, f.vcocap, vcocap_reg_state) {
+        if let Err(e) = self
+            .write_vcocap(base, f.vcocap, vcocap_reg_state)
+            .context("writing VCOCAP")
+        {
             self.turn_off_dsms()?;
             log::error!(
+                target: log_target::LMS,
                 "Failed to write vcocap_reg_state! Device requires re-initialization (call initialize()) to restore DSM state."
             );
             return Err(e);
         }
         let low_band = (f.flags & LMS_FREQ_FLAGS_LOW_BAND) != 0;
-        let lben_lbrfen = self.read(0x08)?;
-        let loopbben = self.read(0x46)?;
+        let lben_lbrfen = self.read(0x08).context("reading loopback enables")?;
+        let loopbben = self.read(0x46).context("reading baseband loopback")?;
         let lb_enabled = matches!(lben_lbrfen & 0x7, 1..=3)
             || ((lben_lbrfen & 0x70) != 0 && (loopbben & 0x0c) != 0);
-        if let Err(e) = self.write_pll_config(channel, f.freqsel, low_band, lb_enabled) {
+        if let Err(e) = self
+            .write_pll_config(channel, f.freqsel, low_band, lb_enabled)
+            .context("writing freqsel")
+        {
             self.turn_off_dsms()?;
             log::error!(
+                target: log_target::LMS,
                 "Failed to write pll_config! Device requires re-initialization (call initialize()) to restore DSM state."
             );
             return Err(e);
@@ -385,9 +418,13 @@ impl<'a> Lms6002d<'a> {
         freq_data[2] = ((f.nfrac >> 8) & 0xff) as u8;
         freq_data[3] = (f.nfrac & 0xff) as u8;
         for (idx, value) in freq_data.iter().enumerate() {
-            if let Err(e) = self.write(pll_base + idx as u8, *value) {
+            if let Err(e) = self
+                .write(pll_base + idx as u8, *value)
+                .context("writing NINT/NFRAC")
+            {
                 self.turn_off_dsms()?;
                 log::error!(
+                    target: log_target::LMS,
                     "Failed to write pll {}! Device requires re-initialization (call initialize()) to restore DSM state.",
                     pll_base + idx as u8
                 );
@@ -397,15 +434,17 @@ impl<'a> Lms6002d<'a> {
         if (f.flags & LMS_FREQ_FLAGS_FORCE_VCOCAP) != 0 {
             f.vcocap_result = f.vcocap;
         } else {
-            log::trace!("Tuning VCOCAP...");
-            f.vcocap_result = self.tune_vcocap(f.vcocap, base, vcocap_reg_state)?;
+            log::trace!(target: log_target::LMS, "Tuning VCOCAP...");
+            f.vcocap_result = self
+                .tune_vcocap(f.vcocap, base, vcocap_reg_state)
+                .context("tuning VCOCAP")?;
         }
         Ok(())
     }
 
     pub(crate) fn set_frequency(&mut self, channel: Channel, freq: u64) -> crate::Result<()> {
         let mut f = freq.try_into()?;
-        log::trace!("{f:?}");
+        log::trace!(target: log_target::LMS, "{f:?}");
         self.set_precalculated_frequency(channel, &mut f)
     }
 
@@ -435,6 +474,9 @@ impl<'a> Lms6002d<'a> {
         Ok(f)
     }
 
+    /// Powers the TX peak detector up or down (register 0x44, bit 0 clear
+    /// when enabled). Its output is analog and only reaches the host through
+    /// the RX ADC; there is no register holding a detected value.
     #[allow(dead_code)]
     pub(crate) fn peakdetect_enable(&mut self, enable: bool) -> crate::Result<()> {
         let mut data = self.read(0x44)?;
@@ -511,18 +553,18 @@ impl<'a> Lms6002d<'a> {
     ) -> crate::Result<u8> {
         for _ in 0..VTUNE_MAX_ITERATIONS {
             if vcocap >= VCOCAP_MAX_VALUE {
-                log::trace!("vtune_high_to_norm: VCOCAP hit max value.");
+                log::trace!(target: log_target::LMS, "vtune_high_to_norm: VCOCAP hit max value.");
                 return Ok(VCOCAP_MAX_VALUE);
             }
             vcocap += 1;
             self.write_vcocap(base, vcocap, vcocap_reg_state)?;
             let vtune = self.get_vtune(base, VTUNE_DELAY_SMALL)?;
             if vtune == VcoState::Norm {
-                log::trace!("VTUNE NORM @ VCOCAP={vcocap}");
+                log::trace!(target: log_target::LMS, "VTUNE NORM @ VCOCAP={vcocap}");
                 return Ok(vcocap - 1);
             }
         }
-        log::error!("VTUNE High->Norm loop failed to converge.");
+        log::error!(target: log_target::LMS, "VTUNE High->Norm loop failed to converge.");
         Err(Error::CalibrationFailed(
             "VTUNE High->Norm loop failed to converge",
         ))
@@ -535,21 +577,24 @@ impl<'a> Lms6002d<'a> {
         vcocap_reg_state: u8,
     ) -> crate::Result<u8> {
         for _ in 0..VTUNE_MAX_ITERATIONS {
-            log::trace!("base: {base}, vcocap: {vcocap}, vcocap_reg_state: {vcocap_reg_state}");
+            log::trace!(
+                target: log_target::LMS,
+                "base: {base}, vcocap: {vcocap}, vcocap_reg_state: {vcocap_reg_state}"
+            );
             if vcocap == 0 {
-                log::debug!("vtune_norm_to_high: VCOCAP hit min value.");
+                log::debug!(target: log_target::LMS, "vtune_norm_to_high: VCOCAP hit min value.");
                 return Ok(0);
             }
             vcocap -= 1;
             self.write_vcocap(base, vcocap, vcocap_reg_state)?;
             let vtune = self.get_vtune(base, VTUNE_DELAY_SMALL)?;
-            log::trace!("vtune: {vtune:?}");
+            log::trace!(target: log_target::LMS, "vtune: {vtune:?}");
             if vtune == VcoState::High {
-                log::debug!("VTUNE HIGH @ VCOCAP={vcocap}");
+                log::debug!(target: log_target::LMS, "VTUNE HIGH @ VCOCAP={vcocap}");
                 return Ok(vcocap);
             }
         }
-        log::error!("VTUNE Norm->High loop failed to converge.");
+        log::error!(target: log_target::LMS, "VTUNE Norm->High loop failed to converge.");
         Err(Error::CalibrationFailed(
             "VTUNE Norm->High loop failed to converge",
         ))
@@ -563,18 +608,18 @@ impl<'a> Lms6002d<'a> {
     ) -> crate::Result<u8> {
         for _ in 0..VTUNE_MAX_ITERATIONS {
             if vcocap == 0 {
-                log::debug!("vtune_low_to_norm: VCOCAP hit min value.");
+                log::debug!(target: log_target::LMS, "vtune_low_to_norm: VCOCAP hit min value.");
                 return Ok(0);
             }
             vcocap -= 1;
             self.write_vcocap(base, vcocap, vcocap_reg_state)?;
             let vtune = self.get_vtune(base, VTUNE_DELAY_SMALL)?;
             if vtune == VcoState::Norm {
-                log::debug!("VTUNE NORM @ VCOCAP={vcocap}");
+                log::debug!(target: log_target::LMS, "VTUNE NORM @ VCOCAP={vcocap}");
                 return Ok(vcocap + 1);
             }
         }
-        log::error!("VTUNE Low->Norm loop failed to converge.");
+        log::error!(target: log_target::LMS, "VTUNE Low->Norm loop failed to converge.");
         Err(Error::CalibrationFailed(
             "VTUNE Low->Norm loop failed to converge",
         ))
@@ -601,24 +646,39 @@ impl<'a> Lms6002d<'a> {
         for i in 0..MAX_RETRIES {
             let vtune = self.get_vtune(base, 0)?;
             if vtune == target_value {
-                log::debug!("VTUNE reached {target_value:?} at iteration {i}");
+                log::debug!(
+                    target: log_target::LMS,
+                    "VTUNE reached {target_value:?} at iteration {i}"
+                );
                 return Ok(());
             } else {
-                log::trace!("VTUNE was {vtune:?}. Waiting and retrying...");
+                log::trace!(
+                    target: log_target::LMS,
+                    "VTUNE was {vtune:?}. Waiting and retrying..."
+                );
                 sleep(Duration::from_micros(10));
             }
         }
-        log::trace!("Timed out while waiting for VTUNE={target_value:?}. Walking VCOCAP...");
+        log::trace!(
+            target: log_target::LMS,
+            "Timed out while waiting for VTUNE={target_value:?}. Walking VCOCAP..."
+        );
         while *vcocap != limit {
             *vcocap = (*vcocap as i8 + inc) as u8;
             self.write_vcocap(base, *vcocap, vcocap_reg_state)?;
             let vtune = self.get_vtune(base, VTUNE_DELAY_SMALL)?;
             if vtune == target_value {
-                log::debug!("VTUNE={vtune:?} reached with VCOCAP={vcocap}");
+                log::debug!(
+                    target: log_target::LMS,
+                    "VTUNE={vtune:?} reached with VCOCAP={vcocap}"
+                );
                 return Ok(());
             }
         }
-        log::debug!("VTUNE did not reach {target_value:?}. Tuning may not be nominal.");
+        log::debug!(
+            target: log_target::LMS,
+            "VTUNE did not reach {target_value:?}. Tuning may not be nominal."
+        );
         Ok(())
     }
 
@@ -629,15 +689,15 @@ impl<'a> Lms6002d<'a> {
         let mut vtune = self.get_vtune(base, VTUNE_DELAY_LARGE)?;
         match vtune {
             VcoState::High => {
-                log::trace!("Estimate HIGH: Walking down to NORM.");
+                log::trace!(target: log_target::LMS, "Estimate HIGH: Walking down to NORM.");
                 vtune_high_limit = self.vtune_high_to_norm(base, vcocap, vcocap_reg_state)?;
             }
             VcoState::Norm => {
-                log::trace!("Estimate NORM: Walking up to HIGH.");
+                log::trace!(target: log_target::LMS, "Estimate NORM: Walking up to HIGH.");
                 vtune_high_limit = self.vtune_norm_to_high(base, vcocap, vcocap_reg_state)?;
             }
             VcoState::Low => {
-                log::trace!("Estimate LOW: Walking down to NORM.");
+                log::trace!(target: log_target::LMS, "Estimate LOW: Walking down to NORM.");
                 vtune_low_limit = self.vtune_low_to_norm(base, vcocap, vcocap_reg_state)?;
             }
         }
@@ -648,18 +708,18 @@ impl<'a> Lms6002d<'a> {
                         vcocap = vtune_high_limit + VCOCAP_MAX_LOW_HIGH;
                     } else {
                         vcocap = VCOCAP_MAX_VALUE;
-                        log::debug!("Clamping VCOCAP to {vcocap}.");
+                        log::debug!(target: log_target::LMS, "Clamping VCOCAP to {vcocap}.");
                     }
                 }
                 _ => {
-                    log::error!("Invalid state");
+                    log::error!(target: log_target::LMS, "Invalid state");
                     return Err(Error::BoardState("VTUNE state mismatch after high_limit"));
                 }
             }
             self.write_vcocap(base, vcocap, vcocap_reg_state)?;
-            log::trace!("Waiting for VTUNE LOW @ VCOCAP={vcocap}");
+            log::trace!(target: log_target::LMS, "Waiting for VTUNE LOW @ VCOCAP={vcocap}");
             self.wait_for_vtune_value(base, VcoState::Low, &mut vcocap, vcocap_reg_state)?;
-            log::trace!("Walking VTUNE LOW to NORM from VCOCAP={vcocap}");
+            log::trace!(target: log_target::LMS, "Walking VTUNE LOW to NORM from VCOCAP={vcocap}");
             vtune_low_limit = self.vtune_low_to_norm(base, vcocap, vcocap_reg_state)?;
         } else {
             match vtune {
@@ -668,29 +728,32 @@ impl<'a> Lms6002d<'a> {
                         vcocap = vtune_low_limit - VCOCAP_MAX_LOW_HIGH;
                     } else {
                         vcocap = 0;
-                        log::debug!("Clamping VCOCAP to {vcocap}.");
+                        log::debug!(target: log_target::LMS, "Clamping VCOCAP to {vcocap}.");
                     }
                 }
                 _ => {
-                    log::error!("Invalid state");
+                    log::error!(target: log_target::LMS, "Invalid state");
                     return Err(Error::BoardState("VTUNE state mismatch after low_limit"));
                 }
             }
             self.write_vcocap(base, vcocap, vcocap_reg_state)?;
-            log::trace!("Waiting for VTUNE HIGH @ VCOCAP={vcocap}");
+            log::trace!(target: log_target::LMS, "Waiting for VTUNE HIGH @ VCOCAP={vcocap}");
             self.wait_for_vtune_value(base, VcoState::High, &mut vcocap, vcocap_reg_state)?;
-            log::trace!("Walking VTUNE HIGH to NORM from VCOCAP={vcocap}");
+            log::trace!(target: log_target::LMS, "Walking VTUNE HIGH to NORM from VCOCAP={vcocap}");
             vtune_high_limit = self.vtune_high_to_norm(base, vcocap, vcocap_reg_state)?;
         }
         vcocap = vtune_high_limit + (vtune_low_limit - vtune_high_limit) / 2;
-        log::trace!("VTUNE LOW:   {vtune_low_limit}");
-        log::trace!("VTUNE NORM:  {vcocap}");
-        log::trace!("VTUNE Est:   {vcocap_est}");
-        log::trace!("VTUNE HIGH:  {vtune_high_limit}");
+        log::trace!(target: log_target::LMS, "VTUNE LOW:   {vtune_low_limit}");
+        log::trace!(target: log_target::LMS, "VTUNE NORM:  {vcocap}");
+        log::trace!(target: log_target::LMS, "VTUNE Est:   {vcocap_est}");
+        log::trace!(target: log_target::LMS, "VTUNE HIGH:  {vtune_high_limit}");
         self.write_vcocap(base, vcocap, vcocap_reg_state)?;
         vtune = self.get_vtune(base, VTUNE_DELAY_SMALL)?;
         if vtune != VcoState::Norm {
-            log::error!("Final VCOCAP={vcocap} is not in VTUNE NORM region.");
+            log::error!(
+                target: log_target::LMS,
+                "Final VCOCAP={vcocap} is not in VTUNE NORM region."
+            );
             return Err(Error::TuningFailed);
         }
         Ok(vcocap)
@@ -6,11 +6,16 @@
 
 use crate::Error;
 use crate::bladerf1::hardware::lms6002d::Lms6002d;
+use crate::channel::Channel;
+use crate::log_target;
 use crate::range::{Range, RangeItem};
 
-/// RX gain offset applied when converting between dB FS and dBm.
+/// Offset from the sum of the RX stage gains to the RX system gain, in dB.
 pub const BLADERF1_RX_GAIN_OFFSET: f32 = -6.0;
-/// TX gain offset applied when converting between dB FS and dBm.
+/// Offset from the sum of the TX stage gains to the TX system gain, in dB.
+///
+/// Chosen by libbladeRF so that a TX system gain of about 60 dB gives
+/// roughly 0 dBm at the antenna port.
 pub const BLADERF1_TX_GAIN_OFFSET: f32 = 52.0;
 
 /// LMS6002D power amplifier selection.
@@ -97,7 +102,7 @@ pub const GAIN_SPEC_TXVGA1: GainSpec = GainSpec::new(-35, -4, 1);
 /// TX VGA2 gain specification: 0–25 dB in 1 dB steps.
 pub const GAIN_SPEC_TXVGA2: GainSpec = GainSpec::new(0, 25, 1);
 /// Gain value in decibels.
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub struct GainDb {
     db: i8,
 }
@@ -106,6 +111,25 @@ impl GainDb {
     pub fn db(&self) -> i8 {
         self.db
     }
+
+    /// Converts a linear (voltage) gain to dB using `20 * log10(linear)`.
+    ///
+    /// The result is rounded to the nearest whole dB and clamped to the `i8` range.
+    /// Non-positive and NaN inputs map to the minimum representable gain.
+    pub fn from_linear(linear: f32) -> Self {
+        if linear <= 0.0 || linear.is_nan() {
+            return Self { db: i8::MIN };
+        }
+        let db = (20.0 * linear.log10()).round();
+        Self {
+            db: db.clamp(i8::MIN as f32, i8::MAX as f32) as i8,
+        }
+    }
+
+    /// Returns the gain as a linear (voltage) ratio, `10^(dB / 20)`.
+    pub fn linear(&self) -> f32 {
+        10f32.powf(self.db as f32 / 20.0)
+    }
 }
 impl From<i8> for GainDb {
     fn from(db: i8) -> Self {
@@ -139,7 +163,7 @@ impl TryFrom<u8> for LnaGainCode {
             2 => Ok(LnaGainCode::MidAllLnas),
             3 => Ok(LnaGainCode::MaxAllLnas),
             _ => {
-                log::error!("Unsupported Gain Code {value}");
+                log::error!(target: log_target::LMS, "Unsupported Gain Code {value}");
                 Err(())
             }
         }
@@ -279,7 +303,7 @@ impl From<GainDb> for Txvga2GainCode {
     }
 }
 /// Identifies a specific gain stage in the LMS6002D signal chain.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum GainStage {
     /// RX low-noise amplifier (0–6 dB, step 3).
     Lna,
@@ -301,6 +325,26 @@ impl GainStage {
     pub const fn is_tx(&self) -> bool {
         matches!(self, GainStage::TxVga1 | GainStage::TxVga2)
     }
+    /// Returns `true` if this stage belongs to the path of `channel`.
+    pub const fn belongs_to(&self, channel: Channel) -> bool {
+        match channel {
+            Channel::Rx => self.is_rx(),
+            Channel::Tx => self.is_tx(),
+        }
+    }
+    /// Looks up a stage of `channel` by its libbladeRF name (case-insensitive).
+    ///
+    /// Returns `Error::Argument` for unknown names and for stages of the
+    /// other direction, e.g. `"txvga1"` on `Channel::Rx`.
+    pub fn for_channel(channel: Channel, name: &str) -> crate::error::Result<Self> {
+        let stage = Self::try_from(name)?;
+        if !stage.belongs_to(channel) {
+            return Err(Error::Argument(format!(
+                "gain stage {name:?} does not belong to {channel:?}"
+            )));
+        }
+        Ok(stage)
+    }
     /// Returns the gain range (min, max, step) for this stage.
     pub fn gain_range(self) -> Range {
         let spec = match self {
@@ -317,6 +361,38 @@ impl GainStage {
             1.0,
         )])
     }
+    /// Returns the register field value this stage is programmed with for
+    /// `gain`, clamped to the stage's range.
+    ///
+    /// The value is the gain field alone, before it is shifted into its
+    /// register: the LNA mode (1–3), the RXVGA1 code (0–127), the RXVGA2,
+    /// TXVGA1 and TXVGA2 step counts.
+    pub fn gain_code(self, gain: GainDb) -> u8 {
+        match self {
+            Self::Lna => LnaGainCode::from(gain).into(),
+            Self::RxVga1 => Rxvga1GainCode::from(gain).code,
+            Self::RxVga2 => Rxvga2GainCode::from(gain).code,
+            Self::TxVga1 => Txvga1GainCode::from(gain).code,
+            Self::TxVga2 => Txvga2GainCode::from(gain).code >> 3,
+        }
+    }
+    /// Returns the gain of this stage for the register field value `code`,
+    /// the inverse of `gain_code()`.
+    ///
+    /// RXVGA1 codes above 120 read as 120, as on the LMS6002D.
+    ///
+    /// Returns `Error::Argument` for an LNA mode other than 1–3.
+    pub fn gain_from_code(self, code: u8) -> crate::error::Result<GainDb> {
+        Ok(match self {
+            Self::Lna => LnaGainCode::try_from(code)
+                .map_err(|_| Error::Argument(format!("invalid LNA gain code {code}")))?
+                .into(),
+            Self::RxVga1 => Rxvga1GainCode::from((code & 0x7f).min(120)).into(),
+            Self::RxVga2 => Rxvga2GainCode::from(code).into(),
+            Self::TxVga1 => Txvga1GainCode::from(code).into(),
+            Self::TxVga2 => Txvga2GainCode::from((code & 0x1f) << 3).into(),
+        })
+    }
 }
 impl From<GainStage> for &'static str {
     fn from(stage: GainStage) -> Self {
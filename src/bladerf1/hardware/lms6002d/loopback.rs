@@ -47,7 +47,7 @@ pub enum LmsLoopbackPath {
 }
 
 /// Supported loopback modes.
-#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub enum Loopback {
     /// No loopback; normal RX/TX operation.
     None = 0,
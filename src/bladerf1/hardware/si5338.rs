@@ -7,12 +7,13 @@
 
 use crate::channel::Channel;
 use crate::error::{Error, Result};
+use crate::log_target;
 use crate::nios_client::NiosCore;
 use crate::protocol::nios::NiosPkt8x8Target;
 use crate::range::{Range, RangeItem};
 
 /// Rational rate with integer part and numerator/denominator for fractional-N synthesis.
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct RationalRate {
     /// Integer component of the rate.
     integer: u64,
@@ -37,6 +38,15 @@ impl RationalRate {
     pub fn denominator(&self) -> u64 {
         self.den
     }
+
+    /// Returns the rate rounded to the nearest integer, with halves rounded up.
+    pub fn round(&self) -> u64 {
+        if self.den != 0 && self.num.saturating_mul(2) >= self.den {
+            self.integer + 1
+        } else {
+            self.integer
+        }
+    }
 }
 
 const SI5338_F_VCO: u64 = 38_400_000 * 66;
@@ -64,6 +74,28 @@ pub enum SmbMode {
     Unavailable,
 }
 
+/// Si5338 register addresses captured by `dump_registers()`.
+///
+/// Covers the interrupt mask (6), input, R divider, output driver and PLL
+/// configuration (27–52), MultiSynth 0–3 parameters (53–62, 64–73, 75–84,
+/// 86–95), the PLL feedback MultiSynth (97–106), the status registers (218,
+/// 247) and the output enables (230).
+pub const SI5338_REG_DUMPSET: [u8; 80] = [
+    6, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49,
+    50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 75,
+    76, 77, 78, 79, 80, 81, 82, 83, 84, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 97, 98, 99, 100,
+    101, 102, 103, 104, 105, 106, 218, 230, 247,
+];
+
+/// Status registers in `SI5338_REG_DUMPSET` that `restore_registers()` skips.
+pub const SI5338_VOLATILE_REGS: [u8; 2] = [218, 247];
+
+/// Returns `true` if `restore_registers()` writes Si5338 register `addr`,
+/// i.e. it is not one of the `SI5338_VOLATILE_REGS`.
+pub fn si5338_reg_is_restorable(addr: u8) -> bool {
+    !SI5338_VOLATILE_REGS.contains(&addr)
+}
+
 const DEFAULT_CONFIG: &[(u8, u8)] = &[
     (6, 0x08),
     (28, 0x0b),
@@ -121,7 +153,7 @@ impl<'a> Si5338<'a> {
     fn read_multisynth(&mut self, ms: &mut Multisynth) -> Result<()> {
         let val = self.read(36 + ms.index)?;
         ms.enable = val & 7;
-        log::trace!("Read enable register: {val:x}");
+        log::trace!(target: log_target::SI5338, "Read enable register: {val:x}");
         for i in 0..ms.regs.len() {
             ms.regs[i] = self.read(ms.base as u8 + i as u8)?
         }
@@ -135,19 +167,44 @@ impl<'a> Si5338<'a> {
     fn write_multisynth(&mut self, ms: &Multisynth) -> Result<()> {
         let mut val = self.read(36 + ms.index)?;
         val |= ms.enable;
-        log::trace!("Wrote enable register: {val:x}");
+        log::trace!(target: log_target::SI5338, "Wrote enable register: {val:x}");
         self.write(36 + ms.index, val)?;
         for i in 0..ms.regs.len() {
             self.write((ms.base + i as u16) as u8, ms.regs[i])?;
-            log::trace!("Wrote regs[{i}]: {}", ms.regs[i]);
+            log::trace!(target: log_target::SI5338, "Wrote regs[{i}]: {}", ms.regs[i]);
         }
         let r_power = ms.r.checked_ilog2().unwrap_or(0) as u8;
         let mut val = 0xc0;
         val |= r_power << 2;
-        log::trace!("Wrote r register: {val:x}");
+        log::trace!(target: log_target::SI5338, "Wrote r register: {val:x}");
         self.write(ms.index + 31, val)
     }
 
+    /// Reads every register in `SI5338_REG_DUMPSET` and returns
+    /// `(address, value)` pairs in that order.
+    pub fn dump_registers(&mut self) -> Result<Vec<(u8, u8)>> {
+        SI5338_REG_DUMPSET
+            .iter()
+            .map(|&addr| Ok((addr, self.read(addr)?)))
+            .collect()
+    }
+
+    /// Writes back registers captured by `dump_registers()`, in order.
+    ///
+    /// Status registers (see `si5338_reg_is_restorable()`) are skipped. The
+    /// PLL is not soft-reset afterwards, so restoring a different PLL
+    /// configuration may leave it unlocked; MultiSynth and output settings,
+    /// as changed by the sample rate and SMB clock calls, take effect directly.
+    pub fn restore_registers(&mut self, regs: &[(u8, u8)]) -> Result<()> {
+        for &(addr, data) in regs
+            .iter()
+            .filter(|(addr, _)| si5338_reg_is_restorable(*addr))
+        {
+            self.write(addr, data)?;
+        }
+        Ok(())
+    }
+
     /// Sets the sample rate for the given channel and returns the actual configured rate.
     ///
     /// May return a fractional rate internally; the returned `u32` is the integer part.
@@ -158,31 +215,38 @@ impl<'a> Si5338<'a> {
             num: 0,
             den: 1,
         };
-        log::trace!("Setting integer sample rate: {rate}");
+        log::trace!(target: log_target::SI5338, "Setting integer sample rate: {rate}");
         let act = self.set_rational_sample_rate(channel, &mut req)?;
         if act.numerator() != 0 {
-            log::debug!("Non-integer sample rate set from integer sample rate, truncating output.");
+            log::debug!(
+                target: log_target::SI5338,
+                "Non-integer sample rate set from integer sample rate, truncating output."
+            );
         }
         if act.integer() > u32::MAX as u64 {
             return Err(Error::BoardState("actual sample rate exceeds u32 range"));
         }
-        log::trace!("Set actual integer sample rate: {}", act.integer());
+        log::trace!(
+            target: log_target::SI5338,
+            "Set actual integer sample rate: {}", act.integer()
+        );
         Ok(act.integer() as u32)
     }
 
     /// Returns the current integer sample rate for the given channel.
     ///
-    /// Truncates any fractional component.
+    /// Rounds any fractional component to the nearest integer.
     /// Returns `Error::BoardState` if the actual rate exceeds `u32::MAX`.
     pub fn get_sample_rate(&mut self, channel: Channel) -> Result<u32> {
         let actual = self.get_rational_sample_rate(channel)?;
         if actual.numerator() != 0 {
-            log::debug!("Fractional sample rate truncated during integer sample rate retrieval");
-        }
-        if actual.integer() > u32::MAX as u64 {
-            return Err(Error::BoardState("actual sample rate exceeds u32 range"));
+            log::debug!(
+                target: log_target::SI5338,
+                "Fractional sample rate rounded during integer sample rate retrieval"
+            );
         }
-        Ok(actual.integer() as u32)
+        u32::try_from(actual.round())
+            .map_err(|_| Error::BoardState("actual sample rate exceeds u32 range"))
     }
 
     /// Returns the range of supported sample rates.
@@ -198,16 +262,14 @@ impl<'a> Si5338<'a> {
     /// Sets a rational sample rate and returns the actual configured rate.
     ///
     /// Reduces the input fraction before programming the MultiSynth.
-    /// Returns `Error::InvalidSampleRate` if the rate is below the minimum.
+    /// Returns `Error::InvalidSampleRate` if the denominator is zero or the
+    /// rate lies outside `BLADERF_SAMPLERATE_MIN..=BLADERF_SAMPLERATE_REC_MAX`.
     pub fn set_rational_sample_rate(
         &mut self,
         channel: Channel,
         rate: &mut RationalRate,
     ) -> Result<RationalRate> {
-        rate.reduce();
-        if rate.integer() < BLADERF_SAMPLERATE_MIN as u64 {
-            return Err(Error::InvalidSampleRate("sample rate below minimum"));
-        }
+        check_rational_rate(rate)?;
         let index: u8 = if channel == Channel::Rx { 0x1 } else { 0x2 };
         let mut si_channel: u8 = SI5338_EN_A;
         if channel == Channel::Tx {
@@ -235,10 +297,10 @@ impl<'a> Si5338<'a> {
     pub fn set_rational_smb_freq(&mut self, mut rate: RationalRate) -> Result<RationalRate> {
         rate.reduce();
         if rate.integer() < BLADERF_SMB_FREQUENCY_MIN as u64 {
-            log::error!("provided SMB freq violates minimum");
+            log::error!(target: log_target::SI5338, "provided SMB freq violates minimum");
             return Err(Error::Argument("SMB frequency below minimum".into()));
         } else if rate.integer() > BLADERF_SMB_FREQUENCY_MAX as u64 {
-            log::error!("provided SMB freq violates maximum");
+            log::error!(target: log_target::SI5338, "provided SMB freq violates maximum");
             return Err(Error::Argument("SMB frequency above maximum".into()));
         }
         self.rational_multisynth(3, SI5338_EN_A, &mut rate)
@@ -250,15 +312,21 @@ impl<'a> Si5338<'a> {
     /// if the actual frequency exceeds `u32::MAX`.
     pub fn set_smb_freq(&mut self, rate: u32) -> Result<u32> {
         let req = RationalRate::new(rate as u64, 0, 1);
-        log::trace!("Setting integer SMB frequency: {rate}");
+        log::trace!(target: log_target::SI5338, "Setting integer SMB frequency: {rate}");
         let act = self.set_rational_smb_freq(req)?;
         if act.numerator() != 0 {
-            log::trace!("Non-integer SMB frequency set from integer frequency, truncating output.");
+            log::trace!(
+                target: log_target::SI5338,
+                "Non-integer SMB frequency set from integer frequency, truncating output."
+            );
         }
         if act.integer() > u32::MAX as u64 {
             return Err(Error::BoardState("actual SMB frequency exceeds u32 range"));
         }
-        log::trace!("Set actual integer SMB frequency: {}", act.integer());
+        log::trace!(
+            target: log_target::SI5338,
+            "Set actual integer SMB frequency: {}", act.integer()
+        );
         Ok(act.integer() as u32)
     }
 
@@ -281,6 +349,7 @@ impl<'a> Si5338<'a> {
         let actual = self.get_rational_smb_freq()?;
         if actual.numerator() != 0 {
             log::trace!(
+                target: log_target::SI5338,
                 "Fractional SMB frequency truncated during integer SMB frequency retrieval"
             );
         }
@@ -371,6 +440,53 @@ impl<'a> Si5338<'a> {
     }
 }
 
+/// Reduces `rate` and checks it the way `set_rational_sample_rate()` does.
+///
+/// Returns `Error::InvalidSampleRate` if the denominator is zero or the rate
+/// lies outside `BLADERF_SAMPLERATE_MIN..=BLADERF_SAMPLERATE_REC_MAX`.
+fn check_rational_rate(rate: &mut RationalRate) -> Result<()> {
+    if rate.den == 0 {
+        return Err(Error::InvalidSampleRate(
+            "sample rate denominator must be non-zero",
+        ));
+    }
+    rate.reduce();
+    if rate.integer() < BLADERF_SAMPLERATE_MIN as u64 {
+        return Err(Error::InvalidSampleRate("sample rate below minimum"));
+    }
+    let max = BLADERF_SAMPLERATE_REC_MAX as u64;
+    if rate.integer() > max || (rate.integer() == max && rate.numerator() != 0) {
+        return Err(Error::InvalidSampleRate("sample rate above maximum"));
+    }
+    Ok(())
+}
+
+/// Computes the MultiSynth parameter registers for a sample rate on `channel`.
+///
+/// Returns the ten P1/P2/P3 register bytes that `set_rational_sample_rate()`
+/// programs, together with the rate they actually produce. No device I/O is
+/// performed.
+///
+/// Returns `Error::InvalidSampleRate` for the rates `set_rational_sample_rate()`
+/// rejects, and for fractions whose denominator is too large to scale to the
+/// VCO frequency.
+pub fn sample_rate_multisynth_regs(
+    channel: Channel,
+    rate: &RationalRate,
+) -> Result<([u8; 10], RationalRate)> {
+    let mut rate = *rate;
+    check_rational_rate(&mut rate)?;
+    let mut ms = Multisynth {
+        index: if channel == Channel::Rx { 1 } else { 2 },
+        ..Default::default()
+    };
+    ms.update_base();
+    ms.calculate(&rate)?;
+    let mut actual = RationalRate::default();
+    ms.calculate_freq(&mut actual);
+    Ok((ms.regs, actual))
+}
+
 fn gcd(mut a: u64, mut b: u64) -> u64 {
     let mut t: u64;
     while b != 0 {
@@ -395,10 +511,12 @@ impl RationalRate {
         }
     }
 
-    fn double(&mut self) {
-        self.integer *= 2;
-        self.num *= 2;
+    fn double(&mut self) -> Result<()> {
+        let overflow = || Error::InvalidSampleRate("sample rate fraction too large");
+        self.integer = self.integer.checked_mul(2).ok_or_else(overflow)?;
+        self.num = self.num.checked_mul(2).ok_or_else(overflow)?;
         self.reduce();
+        Ok(())
     }
 }
 
@@ -438,7 +556,10 @@ impl Multisynth {
         }
         self.p2 = temp as u32;
         self.p3 = self.c;
-        log::trace!("{:016x} {:016x} {:016x}", self.p1, self.p2, self.p3);
+        log::trace!(
+            target: log_target::SI5338,
+            "{:016x} {:016x} {:016x}", self.p1, self.p2, self.p3
+        );
         self.regs[0] = self.p1 as u8;
         self.regs[1] = (self.p1 >> 8) as u8;
         self.regs[2] = (((self.p2 & 0x3f) << 2) | ((self.p1 >> 16) & 0x3)) as u8;
@@ -485,11 +606,11 @@ impl Multisynth {
             den: rate.den,
         };
         if self.index == 1 || self.index == 2 {
-            req.double();
+            req.double()?;
         }
         let mut r_value = 1;
         while req.integer < 5_000_000 && r_value < 32 {
-            req.double();
+            req.double()?;
             r_value <<= 1;
         }
         if r_value == 32 && req.integer < 5_000_000 {
@@ -497,13 +618,21 @@ impl Multisynth {
                 "sample rate too low for SI5338 multisynth",
             ));
         }
+        let overflow = || Error::InvalidSampleRate("sample rate fraction too large");
         let mut abc = RationalRate {
             integer: 0,
-            num: SI5338_F_VCO * req.den,
-            den: req.integer * req.den + req.num,
+            num: SI5338_F_VCO.checked_mul(req.den).ok_or_else(overflow)?,
+            den: req
+                .integer
+                .checked_mul(req.den)
+                .and_then(|whole| whole.checked_add(req.num))
+                .ok_or_else(overflow)?,
         };
         abc.reduce();
-        log::trace!("MSx a + b/c: {} + {}/{}", abc.integer, abc.num, abc.den);
+        log::trace!(
+            target: log_target::SI5338,
+            "MSx a + b/c: {} + {}/{}", abc.integer, abc.num, abc.den
+        );
         if abc.integer < 8 && abc.integer != 4 && abc.integer != 6 {
             return Err(Error::InvalidSampleRate(
                 "SI5338 multisynth integer part too low",
@@ -516,6 +645,7 @@ impl Multisynth {
         }
         while abc.num > (1 << 30) || abc.den > (1 << 30) {
             log::debug!(
+                target: log_target::SI5338,
                 "Loss of precision in reducing fraction from {}/{} to {}/{}",
                 abc.num,
                 abc.den,
@@ -1,10 +1,12 @@
 //! SPI flash access model.
 //!
 //! Provides buffered page read/write, sector erase, multi-page operations,
-//! and block verification through USB vendor commands routed through NIOS.
+//! byte-addressed access, and block verification through USB vendor commands
+//! routed through NIOS.
 
 use crate::bladerf1::board::FlashSession;
 use crate::error::{Error, Result};
+use crate::flash::{flash_units, pad_to_page};
 use crate::usb::{UsbInterfaceCommands, VendorRequest};
 use nusb::Speed;
 
@@ -182,6 +184,60 @@ impl FlashSession<'_> {
         Ok(())
     }
 
+    /// Reads `len` bytes of flash starting at byte address `addr`.
+    ///
+    /// The address need not be page-aligned; the covering pages are read and
+    /// the requested bytes are returned.
+    /// Returns `Error::Argument` if the range extends past the end of flash.
+    pub fn read(&mut self, addr: u32, len: usize) -> Result<Vec<u8>> {
+        let offset = addr as usize % BLADERF_FLASH_PAGE_SIZE;
+        let first = addr - offset as u32;
+        let span = (offset + len).next_multiple_of(BLADERF_FLASH_PAGE_SIZE);
+        let (page_start, page_count) = flash_units(
+            first,
+            span,
+            BLADERF_FLASH_PAGE_SIZE,
+            self.flash_meta.flash_size_bytes,
+        )?;
+        let mut buf = vec![0u8; span];
+        self.read_pages(page_start, page_count as usize, &mut buf)?;
+        buf.truncate(offset + len);
+        buf.drain(..offset);
+        Ok(buf)
+    }
+
+    /// Writes `data` to flash starting at the page-aligned byte address `addr`.
+    ///
+    /// Each page is staged in the FX3 page buffer and then committed. A
+    /// partial final page is padded with 0xFF, which leaves the remaining
+    /// bytes erased. The target sectors must be erased before writing.
+    /// Returns `Error::Argument` if `addr` is not page-aligned or the range
+    /// extends past the end of flash.
+    pub fn write(&mut self, addr: u32, data: &[u8]) -> Result<()> {
+        let padded = pad_to_page(data);
+        let (page_start, page_count) = flash_units(
+            addr,
+            padded.len(),
+            BLADERF_FLASH_PAGE_SIZE,
+            self.flash_meta.flash_size_bytes,
+        )?;
+        self.write_pages(page_start, page_count as usize, &padded)
+    }
+
+    /// Erases `len` bytes of flash starting at byte address `addr`.
+    ///
+    /// Returns `Error::Argument` if `addr` or `len` is not a multiple of the
+    /// 64 KB erase block size, or the range extends past the end of flash.
+    pub fn erase(&mut self, addr: u32, len: usize) -> Result<()> {
+        let (start, count) = flash_units(
+            addr,
+            len,
+            BLADERF_FLASH_ERASE_BLOCK_SIZE,
+            self.flash_meta.flash_size_bytes,
+        )?;
+        self.erase_sectors(start, count)
+    }
+
     /// Reads flash pages and verifies each against the expected data.
     ///
     /// Returns `Error::FlashVerificationFailed` on the first mismatch.
@@ -87,6 +87,10 @@ pub fn nios_encode_retune(
 }
 
 /// Decodes a retune response from the device.
+///
+/// Returns `Error::NiosPacket` if the response fails
+/// [`NiosPktRetuneResponse::validate`].
 pub fn nios_decode_retune(response: &[u8]) -> Result<NiosPktRetuneResponse<'_>> {
+    NiosPktRetuneResponse::validate(response)?;
     NiosPktRetuneResponse::new(response)
 }
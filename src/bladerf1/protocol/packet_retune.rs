@@ -200,6 +200,7 @@ pub struct NiosPktRetuneResponse<'a> {
 }
 impl<'a> NiosPktRetuneResponse<'a> {
     const NIOS_PKT_SIZE: usize = 16;
+    const IDX_MAGIC: usize = 0;
     const IDX_TIMESTAMP: usize = 1;
     const IDX_VCOCAP: usize = 9;
     const IDX_FLAGS: usize = 10;
@@ -208,15 +209,39 @@ impl<'a> NiosPktRetuneResponse<'a> {
     const FLAG_SUCCESS: u8 = 0x2;
     /// Creates a new retune response decoder from a buffer.
     ///
-    /// Requires `buf` to be at least 16 bytes.
+    /// Requires `buf` to be at least 16 bytes and to start with the retune
+    /// magic byte.
     pub fn new(buf: &'a [u8]) -> Result<Self> {
         if buf.len() < Self::NIOS_PKT_SIZE {
             return Err(NiosPacketError::InvalidSize(buf.len()).into());
         }
+        if buf[Self::IDX_MAGIC] != NIOS_PKT_RETUNE_MAGIC {
+            return Err(
+                NiosPacketError::InvalidMagic(NIOS_PKT_RETUNE_MAGIC, buf[Self::IDX_MAGIC]).into(),
+            );
+        }
         Ok(Self {
             buf: &buf[..Self::NIOS_PKT_SIZE],
         })
     }
+    /// Checks that `buf` is a well-formed retune response: exactly 16 bytes,
+    /// the retune magic byte, and a vcocap field within its 6-bit range.
+    pub fn validate(buf: &[u8]) -> std::result::Result<(), NiosPacketError> {
+        if buf.len() != Self::NIOS_PKT_SIZE {
+            return Err(NiosPacketError::InvalidSize(buf.len()));
+        }
+        if buf[Self::IDX_MAGIC] != NIOS_PKT_RETUNE_MAGIC {
+            return Err(NiosPacketError::InvalidMagic(
+                NIOS_PKT_RETUNE_MAGIC,
+                buf[Self::IDX_MAGIC],
+            ));
+        }
+        let vcocap = buf[Self::IDX_VCOCAP];
+        if vcocap > Self::MASK_VCOCAP {
+            return Err(NiosPacketError::VcocapOverflow(vcocap, Self::MASK_VCOCAP));
+        }
+        Ok(())
+    }
     /// Returns the retune duration in clock ticks.
     pub fn duration(&self) -> u64 {
         let mut bytes = [0u8; 8];
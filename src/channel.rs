@@ -14,6 +14,46 @@ pub enum Channel {
 }
 
 impl Channel {
+    /// Returns the RX channel with the given index.
+    ///
+    /// BladeRF1 has a single RX channel, so only index 0 is valid; other
+    /// indices return `Error::Argument`.
+    pub fn rx(index: u8) -> Result<Self> {
+        Self::check_index(index)?;
+        Ok(Channel::Rx)
+    }
+
+    /// Returns the TX channel with the given index.
+    ///
+    /// BladeRF1 has a single TX channel, so only index 0 is valid; other
+    /// indices return `Error::Argument`.
+    pub fn tx(index: u8) -> Result<Self> {
+        Self::check_index(index)?;
+        Ok(Channel::Tx)
+    }
+
+    fn check_index(index: u8) -> Result<()> {
+        if index != 0 {
+            return Err(Error::Argument(format!(
+                "channel index {index} out of range, BladeRF1 has one channel per direction"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the index of this channel within its direction, always 0 on
+    /// BladeRF1.
+    pub fn index(&self) -> u8 {
+        0
+    }
+
+    /// Returns the libbladeRF channel number, `(index << 1) | direction`
+    /// with RX as direction 0 and TX as 1, as by `BLADERF_CHANNEL_RX(n)` and
+    /// `BLADERF_CHANNEL_TX(n)`. The inverse of `Channel::try_from(u8)`.
+    pub fn as_module(&self) -> u8 {
+        (self.index() << 1) | *self as u8
+    }
+
     /// Returns `true` if this is the TX channel.
     pub fn is_tx(&self) -> bool {
         *self == Channel::Tx
@@ -25,13 +65,26 @@ impl Channel {
     }
 }
 
+/// Converts a raw module number (`0` = RX, `1` = TX) as used by libbladeRF.
+///
+/// Returns `Error::Argument` for any other value, including channel numbers
+/// with an index other than 0.
 impl TryFrom<u8> for Channel {
     type Error = Error;
     fn try_from(value: u8) -> Result<Self> {
         match value {
             0 => Ok(Channel::Rx),
             1 => Ok(Channel::Tx),
-            _ => Err(Error::Argument("invalid channel value".into())),
+            _ => Err(Error::Argument(format!(
+                "invalid channel value {value:#04x}"
+            ))),
         }
     }
 }
+
+/// Converts to the libbladeRF channel number, see `Channel::as_module()`.
+impl From<Channel> for u8 {
+    fn from(channel: Channel) -> Self {
+        channel.as_module()
+    }
+}
@@ -1,4 +1,10 @@
+#[cfg(feature = "bladerf1")]
+use crate::bladerf1::board::BoardState;
+#[cfg(feature = "bladerf1")]
+use crate::bladerf1::hardware::lms6002d::dc_calibration::DcCalModule;
 use crate::protocol::nios::NiosPacketError;
+use crate::version::SemanticVersion;
+use std::time::Duration;
 
 /// Result type alias for this crate.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -16,11 +22,19 @@ pub enum Error {
 
     /// A USB error from the nusb transport layer.
     #[error("USB error: {0}")]
-    Nusb(#[from] nusb::Error),
+    Nusb(#[source] nusb::Error),
 
     /// A USB transfer error (submission or completion failure).
     #[error("USB transfer error: {0}")]
-    Transfer(#[from] nusb::transfer::TransferError),
+    Transfer(#[source] nusb::transfer::TransferError),
+
+    /// The device was disconnected from the USB bus.
+    ///
+    /// Produced from `nusb::Error` with `ErrorKind::Disconnected` and from
+    /// `TransferError::Disconnected`, so applications can detect unplug
+    /// events and trigger reconnection logic.
+    #[error("device disconnected")]
+    Disconnected,
 
     /// A USB descriptor query error.
     #[error("USB descriptor error: {0}")]
@@ -70,14 +84,59 @@ pub enum Error {
     #[error("calibration failed: {0}")]
     CalibrationFailed(&'static str),
 
+    /// A step of a multi-module DC calibration sequence failed.
+    #[cfg(feature = "bladerf1")]
+    #[error("DC calibration of {module:?} failed")]
+    DcCalibration {
+        module: DcCalModule,
+        #[source]
+        source: Box<Error>,
+    },
+
+    /// An operation failed; `msg` names the step that was being performed.
+    ///
+    /// Added with [`ResultExt::context`]. The underlying error is available
+    /// through `std::error::Error::source`, and the innermost one through
+    /// [`Error::root_cause`]; match on the latter to see variants such as
+    /// `Error::TuningFailed` through any number of context layers.
+    #[error("{msg}")]
+    Context {
+        msg: &'static str,
+        #[source]
+        source: Box<Error>,
+    },
+
     /// The device is not in the required state (e.g. not initialized).
     #[error("board state error: {0}")]
     BoardState(&'static str),
 
+    /// The operation requires the board to be in a different lifecycle state.
+    #[cfg(feature = "bladerf1")]
+    #[error("wrong board state: requires {required}, board is {actual}")]
+    WrongState {
+        required: BoardState,
+        actual: BoardState,
+    },
+
+    /// The operation requires a newer firmware or FPGA version than the device runs.
+    #[error("operation requires version {min} or later, device has {device}")]
+    NoApi {
+        device: SemanticVersion,
+        min: SemanticVersion,
+    },
+
+    /// The FX3 firmware reported a failure status for a vendor request.
+    #[error("FX3 firmware returned status {code:#x}")]
+    Fx3 { code: u32 },
+
     /// A USB control transfer returned fewer bytes than expected.
     #[error("USB control response too short: expected {expected} bytes, got {actual}")]
     UsbControlResponseTooShort { expected: usize, actual: usize },
 
+    /// A bulk transfer returned fewer bytes than a complete response.
+    #[error("transfer truncated: got {actual} of {expected} bytes")]
+    TransferTruncated { actual: usize, expected: usize },
+
     /// The requested sample rate is invalid for the current configuration.
     #[error("invalid sample rate: {0}")]
     InvalidSampleRate(&'static str),
@@ -96,11 +155,117 @@ pub enum Error {
         actual: u8,
     },
 
+    /// FX3 RAM read back differently from what the bootloader was sent.
+    #[error(
+        "FX3 RAM verification failed at {address:#010x}: expected 0x{expected:02x}, got 0x{actual:02x}"
+    )]
+    BootloaderVerificationFailed {
+        address: u32,
+        expected: u8,
+        actual: u8,
+    },
+
     /// The stream has already been closed or was never opened.
     #[error("stream already closed")]
     StreamClosed,
 
+    /// No transfer is in flight, so waiting for one would never return.
+    #[error("no transfers pending")]
+    NoTransfersPending,
+
     /// Cannot switch USB alt setting while streams are active.
     #[error("cannot switch mode while streams are active")]
     StreamsActive,
 }
+
+impl Error {
+    /// Returns `true` if retrying the operation may succeed, e.g. while a
+    /// device is still enumerating after a reset.
+    ///
+    /// USB errors of kind `Busy` or `Other`, disconnects and timeouts are
+    /// transient. `NotFound`, permission and unsupported errors, and all
+    /// errors raised by this crate's own checks are not. An `Error::Context`
+    /// is transient if the error it wraps is.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Error::Nusb(err) => {
+                matches!(err.kind(), nusb::ErrorKind::Busy | nusb::ErrorKind::Other)
+            }
+            Error::Disconnected | Error::Timeout => true,
+            Error::Context { source, .. } => source.is_transient(),
+            _ => false,
+        }
+    }
+
+    /// Returns the innermost error, looking through every `Error::Context`
+    /// layer. Any other error is returned as is.
+    pub fn root_cause(&self) -> &Error {
+        let mut err = self;
+        while let Error::Context { source, .. } = err {
+            err = source;
+        }
+        err
+    }
+}
+
+/// Extension trait for annotating errors with the step that failed.
+pub trait ResultExt<T> {
+    /// Wraps an error in `Error::Context` with the message `msg`
+    /// (e.g. `"writing VCOCAP"`). `Ok` values pass through unchanged.
+    fn context(self, msg: &'static str) -> Result<T>;
+}
+
+impl<T> ResultExt<T> for Result<T> {
+    fn context(self, msg: &'static str) -> Result<T> {
+        self.map_err(|source| Error::Context {
+            msg,
+            source: Box::new(source),
+        })
+    }
+}
+
+/// Runs `op` up to `attempts` times while it fails with a transient error
+/// (see [`Error::is_transient`]), sleeping between attempts.
+///
+/// The sleep starts at `delay` and doubles after every failed attempt. The
+/// last error is returned once `attempts` are used up; a non-transient error
+/// is returned immediately. `attempts` of zero is treated as one.
+pub fn retry_transient<T>(
+    attempts: u32,
+    delay: Duration,
+    mut op: impl FnMut() -> Result<T>,
+) -> Result<T> {
+    let mut delay = delay;
+    for attempt in 1.. {
+        match op() {
+            Err(err) if err.is_transient() && attempt < attempts => {
+                log::debug!(
+                    target: crate::log_target::BOARD,
+                    "attempt {attempt}/{attempts} failed ({err}), retrying in {delay:?}"
+                );
+                std::thread::sleep(delay);
+                delay = delay.saturating_mul(2);
+            }
+            result => return result,
+        }
+    }
+    unreachable!("attempt counter overflowed")
+}
+
+impl From<nusb::Error> for Error {
+    fn from(err: nusb::Error) -> Self {
+        match err.kind() {
+            nusb::ErrorKind::Disconnected => Error::Disconnected,
+            _ => Error::Nusb(err),
+        }
+    }
+}
+
+impl From<nusb::transfer::TransferError> for Error {
+    fn from(err: nusb::transfer::TransferError) -> Self {
+        match err {
+            nusb::transfer::TransferError::Disconnected => Error::Disconnected,
+            _ => Error::Transfer(err),
+        }
+    }
+}
@@ -1,4 +1,5 @@
-//! Flash size decode, FPGA size enum, BINKV encode/decode, and calibration region builder.
+//! Flash size decode, FPGA size enum, BINKV encode/decode, calibration region
+//! builder, and FPGA autoload metadata page.
 //!
 //! The BINKV format stores key-value pairs in binary form. Each field consists of
 //! a length byte, the ASCII key, the ASCII value (concatenated, no separator), and a
@@ -183,6 +184,25 @@ pub fn pad_to_page(data: &[u8]) -> Vec<u8> {
     out
 }
 
+/// Converts a byte range of flash into a `(first, count)` range of `unit`-sized
+/// pages or erase blocks.
+/// Returns `Error::Argument` if `addr` or `len` is not a multiple of `unit`, or if
+/// the range extends past `flash_size` bytes.
+pub fn flash_units(addr: u32, len: usize, unit: usize, flash_size: u32) -> Result<(u32, u32)> {
+    if !(addr as usize).is_multiple_of(unit) || !len.is_multiple_of(unit) {
+        return Err(Error::Argument(format!(
+            "flash range {addr:#x}+{len:#x} is not aligned to {unit:#x} bytes"
+        )));
+    }
+    let end = addr as u64 + len as u64;
+    if end > flash_size as u64 {
+        return Err(Error::Argument(format!(
+            "flash range {addr:#x}..{end:#x} exceeds flash size {flash_size:#x}"
+        )));
+    }
+    Ok((addr / unit as u32, (len / unit) as u32))
+}
+
 /// Decodes the flash memory size in bytes from the JEDEC manufacturer and device ID.
 /// Supports Macronix (0xC2), Winbond (0xEF), and Renesas (0x1F) devices.
 /// Returns `Error::Unsupported` for unknown manufacturer or device ID combinations.
@@ -218,3 +238,58 @@ pub fn make_cal_region(fpga_size: FpgaSize, dac_trim: u16) -> Result<[u8; 256]>
     binkv_add_field(&mut buf, "DAC", &dac_str)?;
     Ok(buf)
 }
+
+/// Extracts the factory VCTCXO DAC trim from a calibration region image.
+/// Parses the BINKV "DAC" field as a decimal u16. Returns `Error::BoardState`
+/// if the field is missing (e.g. blank flash) or not a valid u16.
+pub fn decode_dac_trim(buf: &[u8]) -> Result<u16> {
+    binkv_decode_field(buf, "DAC")?
+        .parse::<u16>()
+        .map_err(|_| Error::BoardState("failed to parse DAC trim from flash"))
+}
+
+/// Metadata of an FPGA bitstream stored in flash for autoloading.
+///
+/// The metadata page only records the bitstream length; the FPGA version
+/// is part of the bitstream and only readable once it has been loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FpgaImageInfo {
+    /// Bitstream length in bytes, from the BINKV "LEN" field.
+    pub length: usize,
+}
+
+impl FpgaImageInfo {
+    /// Returns the FPGA size the stored bitstream was built for, or `None`
+    /// if its length matches neither BladeRF1 variant.
+    pub fn fpga_size(&self) -> Option<FpgaSize> {
+        match self.length {
+            BLADERF_FLASH_FPGA_SIZE_40KLE => Some(FpgaSize::KLE40),
+            BLADERF_FLASH_FPGA_SIZE_115KLE => Some(FpgaSize::KLE115),
+            _ => None,
+        }
+    }
+}
+
+/// Builds the 256-byte metadata page written in front of an FPGA bitstream
+/// in flash, holding its length as the BINKV "LEN" field and padded with
+/// 0xFF bytes.
+pub fn make_fpga_meta_page(length: usize) -> Result<[u8; 256]> {
+    let mut buf = [0xFFu8; 256];
+    binkv_encode_field(&mut buf, 0, "LEN", &length.to_string())?;
+    Ok(buf)
+}
+
+/// Decodes the metadata page in front of the flash FPGA image.
+///
+/// Returns `None` if the page is blank (all 0xFF), i.e. no FPGA image is
+/// stored. Returns `Error::BoardState` if the page is written but holds no
+/// valid "LEN" field.
+pub fn decode_fpga_meta_page(buf: &[u8]) -> Result<Option<FpgaImageInfo>> {
+    if buf.iter().all(|&b| b == 0xFF) {
+        return Ok(None);
+    }
+    let length = binkv_decode_field(buf, "LEN")?
+        .parse::<usize>()
+        .map_err(|_| Error::BoardState("failed to parse FPGA image length from flash"))?;
+    Ok(Some(FpgaImageInfo { length }))
+}
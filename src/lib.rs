@@ -13,6 +13,7 @@
 //! | `xb100`     | yes     | XB-100 expansion board support         |
 //! | `xb200`     | yes     | XB-200 transverter board support       |
 //! | `xb300`     | yes     | XB-300 amplifier board support         |
+//! | `async`     | no      | Runtime-agnostic async streaming       |
 //!
 //! \* Enabled implicitly by the `xb100`, `xb200`, or `xb300` features.
 //!
@@ -48,13 +49,14 @@ pub mod bladerf2;
 pub mod channel;
 pub mod error;
 pub mod flash;
+pub mod log_target;
 pub mod nios_client;
 pub mod protocol;
 pub mod range;
 pub mod usb;
 pub mod version;
 pub use channel::Channel;
-pub use error::{Error, Result};
+pub use error::{Error, Result, ResultExt};
 pub use nusb::transfer::Buffer;
 pub use version::SemanticVersion;
 pub(crate) const fn khz(value: u32) -> u32 {
@@ -0,0 +1,37 @@
+//! `log` targets used by this crate.
+//!
+//! Every log record is emitted under one of the targets below instead of the
+//! Rust module path, so that logging can be filtered per subsystem. Loggers
+//! that match targets by prefix, such as `env_logger`, can select all of them
+//! with `bladerf`.
+//!
+//! Silence streaming while keeping LMS6002D trace output:
+//!
+//! ```
+//! use libbladerf_rs::log_target;
+//! use log::LevelFilter;
+//!
+//! env_logger::Builder::new()
+//!     .filter_level(LevelFilter::Warn)
+//!     .filter_module(log_target::LMS, LevelFilter::Trace)
+//!     .filter_module(log_target::STREAM, LevelFilter::Off)
+//!     .init();
+//! ```
+//!
+//! The same filter as an environment variable:
+//! `RUST_LOG=warn,bladerf::lms=trace,bladerf::stream=off`.
+
+/// Board-level control: open, initialization, and RF session operations.
+pub const BOARD: &str = "bladerf::board";
+/// LMS6002D transceiver: tuning, gain, filters, and calibration.
+pub const LMS: &str = "bladerf::lms";
+/// NIOS II register access and retune queue.
+pub const NIOS: &str = "bladerf::nios";
+/// Si5338 clock generator.
+pub const SI5338: &str = "bladerf::si5338";
+/// RX and TX sample streaming.
+pub const STREAM: &str = "bladerf::stream";
+/// USB transport and FX3 vendor requests.
+pub const USB: &str = "bladerf::usb";
+/// Expansion boards (XB-100, XB-200, XB-300).
+pub const XB: &str = "bladerf::xb";
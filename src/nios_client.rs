@@ -9,6 +9,7 @@ use crate::bladerf1::hardware::lms6002d::{Band, Tune};
 use crate::bladerf1::protocol::{nios_decode_retune, nios_encode_retune};
 use crate::channel::Channel;
 use crate::error::{Error, Result};
+use crate::log_target;
 use crate::protocol::nios::packet_generic::NiosNum;
 use crate::protocol::nios::targets::NiosPkt8x16AddrAgcCorr;
 use crate::protocol::nios::{
@@ -73,10 +74,8 @@ impl NiosCore {
         addr: A,
     ) -> Result<D> {
         let out_buf = self.transport.out_buffer()?;
-        log::trace!("nios_read: DMA buffer len = {} bytes", out_buf.len());
         nios_encode_read::<A, D>(out_buf, id.into(), addr)?;
         let response = self.transport.submit(None)?;
-        log::trace!("nios_read: response len = {} bytes", response.len());
         nios_decode_read::<A, D>(response)
     }
     /// Issues a generic NIOS register write.
@@ -132,7 +131,7 @@ impl NiosCore {
     /// Reads the FPGA version as a `SemanticVersion`.
     pub fn nios_get_fpga_version(&mut self) -> Result<SemanticVersion> {
         let regval = self.nios_read::<u8, u32>(NiosPkt8x32Target::Version, 0)?;
-        log::trace!("Read FPGA version word: {regval:#010x}");
+        log::trace!(target: log_target::NIOS, "Read FPGA version word: {regval:#010x}");
         // The FPGA builds this word as (major | minor << 8 | patch << 16), see
         // hdl/.../bladeRF_nios/src/fpga_version.h. The NIOS packet transmits it
         // little-endian, so `regval` (decoded via `from_le_bytes`) holds the
@@ -183,7 +182,10 @@ impl NiosCore {
     pub fn get_alt_setting(&self) -> UsbAltSetting {
         let raw = self.transport.interface().get_alt_setting();
         UsbAltSetting::try_from(raw).unwrap_or_else(|_| {
-            log::warn!("unknown USB alt setting {raw:#x}, treating as Null");
+            log::warn!(
+                target: log_target::NIOS,
+                "unknown USB alt setting {raw:#x}, treating as Null"
+            );
             UsbAltSetting::Null
         })
     }
@@ -206,8 +208,8 @@ impl NiosCore {
         tune: Tune,
         xb_gpio: u8,
     ) -> Result<crate::bladerf1::protocol::RetuneResult> {
-        if timestamp == crate::bladerf1::protocol::RetuneTimestamp::Now {
-            log::trace!("Clearing Retune Queue");
+        if timestamp == crate::bladerf1::protocol::RetuneTimestamp::ClearQueue {
+            log::trace!(target: log_target::NIOS, "Clearing Retune Queue");
         }
         let out_buf = self.transport.out_buffer()?;
         nios_encode_retune(
@@ -304,6 +306,10 @@ impl BladeRf1UsbInterfaceCommands for NiosCore {
     fn usb_device_reset(&self) -> Result<()> {
         self.transport.usb_device_reset()
     }
+    /// Makes the FX3 re-enumerate as its ROM bootloader.
+    fn usb_jump_to_bootloader(&self) -> Result<()> {
+        self.transport.usb_jump_to_bootloader()
+    }
     /// Returns `true` if the firmware has reported readiness.
     fn usb_is_firmware_ready(&self) -> Result<bool> {
         self.transport.usb_is_firmware_ready()
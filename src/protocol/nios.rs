@@ -3,13 +3,18 @@
 //! Defines the packet structure used to communicate with the NIOS II
 //! soft-core processor. Supports 8x8, 8x16, 8x32, 8x64, and 32x32
 //! address/data width combinations. Provides generic encode/decode
-//! functions for issuing read and write commands.
+//! functions for issuing read and write commands, and the retune2 packet.
 
 pub mod packet_generic;
+pub mod packet_retune2;
 pub mod targets;
 use crate::error::Error;
 use crate::protocol::nios::packet_generic::{NiosNum, NiosPktDecoder};
-pub use packet_generic::{NiosPacket, NiosPkt, NiosPktFlags, NiosPktStatus};
+pub use packet_generic::{
+    NiosPacket, NiosPkt, NiosPkt8x8, NiosPkt8x16, NiosPkt8x32, NiosPkt8x64, NiosPkt16x64,
+    NiosPkt32x32, NiosPktFlags, NiosPktStatus,
+};
+pub use packet_retune2::{NIOS_PKT_RETUNE2_MAGIC, NiosPktRetune2Request, NiosPktRetune2Response};
 pub use targets::{
     NiosPkt8x8Target, NiosPkt8x16AddrIqCorr, NiosPkt8x16Target, NiosPkt8x32Target,
     NiosPkt8x64Target, NiosPkt8x64TimestampAddr, NiosPkt32x32Target,
@@ -30,6 +35,9 @@ pub enum NiosPacketError {
     /// The packet buffer is not the expected 16 bytes.
     #[error("invalid packet size: expected 16 bytes, got {0}")]
     InvalidSize(usize),
+    /// The packet magic byte does not match the expected packet type.
+    #[error("invalid packet magic: expected {0:#04x}, got {1:#04x}")]
+    InvalidMagic(u8, u8),
     /// The requested address/data size combination has no defined magic byte.
     #[error("unsupported address/data size combination")]
     InvalidTypeCombination,
@@ -68,20 +76,19 @@ pub fn nios_encode_write<A: NiosNum, D: NiosNum>(
 /// Decodes the data payload from a NIOS read response.
 ///
 /// Extracts the response data from the packet at the offset determined
-/// by the address-type width `A` and data-type width `D`.
+/// by the address-type width `A` and data-type width `D`. Returns
+/// `TransferTruncated` if `response` is shorter than 16 bytes.
 pub fn nios_decode_read<A: NiosNum, D: NiosNum>(response: &[u8]) -> Result<D, Error> {
+    check_response_len(response)?;
     NiosPktDecoder::decode_data::<A, D>(response)
 }
 
 /// Decodes a NIOS write response and verifies success.
 ///
 /// Returns `Ok(())` if the success flag is set, or `WriteFailed` otherwise.
+/// Returns `TransferTruncated` if `response` is shorter than 16 bytes.
 pub fn nios_decode_write<A: NiosNum, D: NiosNum>(response: &[u8]) -> Result<(), Error> {
-    if response.len() < 16 {
-        return Err(Error::NiosPacket(NiosPacketError::InvalidSize(
-            response.len(),
-        )));
-    }
+    check_response_len(response)?;
     const IDX_FLAGS: usize = 2;
     if (response[IDX_FLAGS] & (NiosPktStatus::Success as u8)) != 0 {
         Ok(())
@@ -89,3 +96,14 @@ pub fn nios_decode_write<A: NiosNum, D: NiosNum>(response: &[u8]) -> Result<(),
         Err(Error::NiosPacket(NiosPacketError::WriteFailed))
     }
 }
+
+fn check_response_len(response: &[u8]) -> Result<(), Error> {
+    const NIOS_PKT_SIZE: usize = 16;
+    if response.len() < NIOS_PKT_SIZE {
+        return Err(Error::TransferTruncated {
+            actual: response.len(),
+            expected: NIOS_PKT_SIZE,
+        });
+    }
+    Ok(())
+}
@@ -113,6 +113,19 @@ pub struct NiosPkt<'a, A, D> {
     buf: &'a mut [u8],
     phantom: PhantomData<(A, D)>,
 }
+/// 8-bit address / 8-bit data packet (LMS6002D, Si5338, VCTCXO tamer).
+pub type NiosPkt8x8<'a> = NiosPkt<'a, u8, u8>;
+/// 8-bit address / 16-bit data packet (VCTCXO DAC, IQ corrections, AGC LUT).
+pub type NiosPkt8x16<'a> = NiosPkt<'a, u8, u16>;
+/// 8-bit address / 32-bit data packet (version, control, synthesizers).
+pub type NiosPkt8x32<'a> = NiosPkt<'a, u8, u32>;
+/// 8-bit address / 64-bit data packet (timestamps).
+pub type NiosPkt8x64<'a> = NiosPkt<'a, u8, u64>;
+/// 16-bit address / 64-bit data packet.
+pub type NiosPkt16x64<'a> = NiosPkt<'a, u16, u64>;
+/// 32-bit address / 32-bit data packet (expansion GPIO, Wishbone, ADI AXI).
+pub type NiosPkt32x32<'a> = NiosPkt<'a, u32, u32>;
+
 impl<'a, A: NiosNum, D: NiosNum> NiosPkt<'a, A, D> {
     const IDX_MAGIC: usize = 0;
     const IDX_TARGET: usize = 1;
@@ -150,6 +163,30 @@ impl<'a, A: NiosNum, D: NiosNum> NiosPkt<'a, A, D> {
             phantom: PhantomData,
         })
     }
+    /// Creates a read request for `addr` on `target` in `buf`.
+    ///
+    /// Requires `buf` to be at least 16 bytes.
+    pub fn read(buf: &'a mut [u8], target: impl Into<u8>, addr: A) -> Result<Self> {
+        let mut pkt = Self::new(buf)?;
+        pkt.prepare_read(target.into(), addr);
+        Ok(pkt)
+    }
+    /// Creates a write request of `data` to `addr` on `target` in `buf`.
+    ///
+    /// Requires `buf` to be at least 16 bytes.
+    pub fn write(buf: &'a mut [u8], target: impl Into<u8>, addr: A, data: D) -> Result<Self> {
+        let mut pkt = Self::new(buf)?;
+        pkt.prepare_write(target.into(), addr, data);
+        Ok(pkt)
+    }
+    /// Returns the data field of a response to a packet of this width.
+    ///
+    /// Returns `Error::NiosPacket` if the response is not 16 bytes or does
+    /// not carry this width's magic byte.
+    pub fn response_data(response: &[u8]) -> Result<D> {
+        NiosPktDecoder::validate::<A, D>(response)?;
+        NiosPktDecoder::decode_data::<A, D>(response)
+    }
     /// Populates the packet as a read request.
     ///
     /// Sets the magic byte, target, read flag, and address field.
@@ -232,6 +269,23 @@ impl<'a, A: NiosNum, D: NiosNum> NiosPacket for NiosPkt<'a, A, D> {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
 pub struct NiosPktDecoder;
 impl NiosPktDecoder {
+    /// Checks that `buf` is a well-formed `A`x`D` packet: exactly 16 bytes
+    /// and starting with the magic byte of that address/data width.
+    pub fn validate<A: NiosNum, D: NiosNum>(
+        buf: &[u8],
+    ) -> std::result::Result<(), NiosPacketError> {
+        let magic = NiosPkt::<A, D>::magic().ok_or(NiosPacketError::InvalidTypeCombination)?;
+        if buf.len() != NiosPkt::<A, D>::NIOS_PKT_SIZE {
+            return Err(NiosPacketError::InvalidSize(buf.len()));
+        }
+        if buf[NiosPkt::<A, D>::IDX_MAGIC] != magic {
+            return Err(NiosPacketError::InvalidMagic(
+                magic,
+                buf[NiosPkt::<A, D>::IDX_MAGIC],
+            ));
+        }
+        Ok(())
+    }
     /// Decodes the data field from a NIOS response buffer.
     ///
     /// Calculates the data offset from the address width `A::SIZE`
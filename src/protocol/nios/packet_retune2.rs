@@ -0,0 +1,193 @@
+//! Retune2 request and response packet builders.
+//!
+//! Provides `NiosPktRetune2Request` for encoding scheduled fast-lock
+//! profile retunes and `NiosPktRetune2Response` for decoding the device's
+//! response. Retune2 is the bladeRF2 (AD9361) counterpart of the BladeRF1
+//! retune packet: instead of raw PLL values it selects a NIOS and an RFFE
+//! fast-lock profile together with the RF port and external switch
+//! settings. It uses magic byte 0x55 and occupies the full 16-byte NIOS
+//! packet buffer. The BladeRF1 FPGA does not implement it.
+
+use crate::channel::Channel;
+use crate::error::Result;
+use crate::protocol::nios::NiosPacketError;
+use crate::protocol::nios::packet_generic::NiosPacket;
+
+/// Magic byte identifying a retune2 packet.
+pub const NIOS_PKT_RETUNE2_MAGIC: u8 = 0x55;
+
+/// Builder for a NIOS retune2 request packet.
+///
+/// Wraps a 16-byte buffer and provides `prepare()` to populate all
+/// fields: timestamp, NIOS profile, RFFE profile, RF port and SPDT
+/// switch settings. Also offers accessor methods to inspect the encoded
+/// values.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct NiosPktRetune2Request<'a> {
+    buf: &'a mut [u8],
+}
+impl<'a> NiosPacket for NiosPktRetune2Request<'a> {
+    fn as_slice(&self) -> &[u8] {
+        self.buf
+    }
+    fn as_slice_mut(&mut self) -> &mut [u8] {
+        self.buf
+    }
+}
+impl<'a> NiosPktRetune2Request<'a> {
+    const NIOS_PKT_SIZE: usize = 16;
+    const IDX_MAGIC: usize = 0;
+    const IDX_TIMESTAMP: usize = 1;
+    const IDX_NIOS_PROFILE: usize = 9;
+    const IDX_RFFE_PROFILE: usize = 11;
+    const IDX_RFFE_PORT: usize = 12;
+    const IDX_SPDT: usize = 13;
+    const IDX_RESERVED: usize = 14;
+    const FLAG_RX: u8 = 1 << 7;
+    /// Creates a new retune2 request packet from a buffer.
+    ///
+    /// Requires `buf` to be at least 16 bytes. Returns an error if the
+    /// buffer is too small.
+    pub fn new(buf: &'a mut [u8]) -> Result<Self> {
+        if buf.len() < Self::NIOS_PKT_SIZE {
+            return Err(NiosPacketError::InvalidSize(buf.len()).into());
+        }
+        Ok(Self {
+            buf: &mut buf[..Self::NIOS_PKT_SIZE],
+        })
+    }
+    /// Populates the packet with all retune2 parameters.
+    ///
+    /// Bit 7 of `port` is replaced by the channel direction (set for RX),
+    /// matching libbladeRF. The reserved bytes are filled with 0xff.
+    pub fn prepare(
+        &mut self,
+        channel: Channel,
+        timestamp: u64,
+        nios_profile: u16,
+        rffe_profile: u8,
+        port: u8,
+        spdt: u8,
+    ) {
+        self.set_magic();
+        self.set_timestamp(timestamp);
+        self.set_nios_profile(nios_profile);
+        self.set_rffe_profile(rffe_profile);
+        self.set_port(port, channel);
+        self.set_spdt(spdt);
+        self.buf[Self::IDX_RESERVED..].fill(0xff);
+    }
+    fn set_magic(&mut self) {
+        self.buf[Self::IDX_MAGIC] = NIOS_PKT_RETUNE2_MAGIC;
+    }
+    fn set_timestamp(&mut self, timestamp: u64) {
+        self.write_u64(Self::IDX_TIMESTAMP, timestamp);
+    }
+    fn set_nios_profile(&mut self, nios_profile: u16) {
+        self.write_u16(Self::IDX_NIOS_PROFILE, nios_profile);
+    }
+    fn set_rffe_profile(&mut self, rffe_profile: u8) {
+        self.buf[Self::IDX_RFFE_PROFILE] = rffe_profile;
+    }
+    fn set_port(&mut self, port: u8, channel: Channel) {
+        self.buf[Self::IDX_RFFE_PORT] = match channel {
+            Channel::Rx => port | Self::FLAG_RX,
+            Channel::Tx => port & !Self::FLAG_RX,
+        };
+    }
+    fn set_spdt(&mut self, spdt: u8) {
+        self.buf[Self::IDX_SPDT] = spdt;
+    }
+    /// Returns the timestamp field of the packet.
+    pub fn timestamp(&self) -> u64 {
+        self.read_u64(Self::IDX_TIMESTAMP)
+    }
+    /// Returns the NIOS fast-lock profile number of the packet.
+    pub fn nios_profile(&self) -> u16 {
+        self.read_u16(Self::IDX_NIOS_PROFILE)
+    }
+    /// Returns the RFFE fast-lock profile slot of the packet.
+    pub fn rffe_profile(&self) -> u8 {
+        self.buf[Self::IDX_RFFE_PROFILE]
+    }
+    /// Returns the RF port selection of the packet, without the RX flag.
+    pub fn port(&self) -> u8 {
+        self.buf[Self::IDX_RFFE_PORT] & !Self::FLAG_RX
+    }
+    /// Returns the channel direction encoded in the port field.
+    pub fn channel(&self) -> Channel {
+        if (self.buf[Self::IDX_RFFE_PORT] & Self::FLAG_RX) != 0 {
+            Channel::Rx
+        } else {
+            Channel::Tx
+        }
+    }
+    /// Returns the external SPDT switch settings of the packet.
+    pub fn spdt(&self) -> u8 {
+        self.buf[Self::IDX_SPDT]
+    }
+}
+
+/// Decoder for a NIOS retune2 response packet.
+///
+/// Provides access to the retune duration, validity flag and success
+/// status from the 16-byte response buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NiosPktRetune2Response<'a> {
+    buf: &'a [u8],
+}
+impl<'a> NiosPktRetune2Response<'a> {
+    const NIOS_PKT_SIZE: usize = 16;
+    const IDX_MAGIC: usize = 0;
+    const IDX_TIMESTAMP: usize = 1;
+    const IDX_FLAGS: usize = 9;
+    const FLAG_DURATION_VALID: u8 = 0x1;
+    const FLAG_SUCCESS: u8 = 0x2;
+    /// Creates a new retune2 response decoder from a buffer.
+    ///
+    /// Requires `buf` to be at least 16 bytes and to start with the
+    /// retune2 magic byte.
+    pub fn new(buf: &'a [u8]) -> Result<Self> {
+        if buf.len() < Self::NIOS_PKT_SIZE {
+            return Err(NiosPacketError::InvalidSize(buf.len()).into());
+        }
+        if buf[Self::IDX_MAGIC] != NIOS_PKT_RETUNE2_MAGIC {
+            return Err(NiosPacketError::InvalidMagic(
+                NIOS_PKT_RETUNE2_MAGIC,
+                buf[Self::IDX_MAGIC],
+            )
+            .into());
+        }
+        Ok(Self {
+            buf: &buf[..Self::NIOS_PKT_SIZE],
+        })
+    }
+    /// Checks that `buf` is a well-formed retune2 response: exactly 16
+    /// bytes and starting with the retune2 magic byte.
+    pub fn validate(buf: &[u8]) -> std::result::Result<(), NiosPacketError> {
+        if buf.len() != Self::NIOS_PKT_SIZE {
+            return Err(NiosPacketError::InvalidSize(buf.len()));
+        }
+        if buf[Self::IDX_MAGIC] != NIOS_PKT_RETUNE2_MAGIC {
+            return Err(NiosPacketError::InvalidMagic(
+                NIOS_PKT_RETUNE2_MAGIC,
+                buf[Self::IDX_MAGIC],
+            ));
+        }
+        Ok(())
+    }
+    /// Returns the retune duration in clock ticks.
+    pub fn duration(&self) -> u64 {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&self.buf[Self::IDX_TIMESTAMP..Self::IDX_TIMESTAMP + 8]);
+        u64::from_le_bytes(bytes)
+    }
+    /// Returns `true` if the duration field is valid.
+    pub fn duration_valid(&self) -> bool {
+        (self.buf[Self::IDX_FLAGS] & Self::FLAG_DURATION_VALID) != 0
+    }
+    /// Returns `true` if the retune2 operation succeeded.
+    pub fn is_success(&self) -> bool {
+        (self.buf[Self::IDX_FLAGS] & Self::FLAG_SUCCESS) != 0
+    }
+}
@@ -42,9 +42,9 @@ pub enum NiosPkt8x16Target {
     IqCorr = 0x01,
     /// AGC DC correction coefficients.
     AgcCorr = 0x02,
-    /// AD56x1 DAC bridge.
+    /// AD56x1 DAC bridge (bladeRF2 only).
     Ad56x1Dac = 0x03,
-    /// INA219 power monitor bridge.
+    /// INA219 power monitor bridge (bladeRF2 only).
     Ina219 = 0x04,
 }
 impl_from_for_u8!(NiosPkt8x16Target);
@@ -103,6 +103,19 @@ impl Range {
         self.max()
             .ok_or(crate::error::Error::BoardState("gain range missing max"))
     }
+    /// Checks that `value` lies between `min()` and `max()` inclusive.
+    ///
+    /// Returns `Error::Argument` naming `what` if it does not, or
+    /// `Error::BoardState` if the range is empty.
+    pub fn check(&self, value: f64, what: &str) -> crate::error::Result<()> {
+        let (min, max) = (self.min_checked()?, self.max_checked()?);
+        if value < min || value > max {
+            return Err(crate::error::Error::Argument(format!(
+                "{what} {value} outside supported range {min}..={max}"
+            )));
+        }
+        Ok(())
+    }
     /// Returns `true` if the value falls within any of the range items.
     /// For stepped ranges, checks that the value aligns with the step grid.
     /// Uses epsilon-aware comparison for floating-point equality.
@@ -170,7 +183,8 @@ impl Range {
                             continue;
                         }
                         if value >= *max {
-                            close = Some(closer(value, close, *max));
+                            let last = min + ((max - min) / step).floor() * step;
+                            close = Some(closer(value, close, last));
                             continue;
                         }
                         let mut v = min + ((value - min) / step).floor() * step;
@@ -184,6 +198,13 @@ impl Range {
             close
         }
     }
+    /// Snaps the value to the nearest one the range can represent.
+    /// Stepped items snap to their step grid, and a value between or outside
+    /// disjoint items snaps to the closest bound of the closest item.
+    /// An empty range returns the value unchanged.
+    pub fn snap(&self, value: f64) -> f64 {
+        self.closest(value).unwrap_or(value)
+    }
     /// Finds the smallest value within the range that is at least the target.
     /// If the target is already within the range, returns it as-is.
     /// Returns `None` if no valid value meets or exceeds the target.
@@ -299,3 +320,33 @@ impl Range {
         self.items.iter()
     }
 }
+
+impl std::fmt::Display for RangeItem {
+    /// Formats an interval as `[min .. max]`, a value as `value` and a
+    /// stepped range as `[min .. max] step N (scale S)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RangeItem::Interval(min, max) => write!(f, "[{min} .. {max}]"),
+            RangeItem::Value(value) => write!(f, "{value}"),
+            RangeItem::Step(min, max, step, scale) => {
+                write!(f, "[{min} .. {max}] step {step} (scale {scale})")
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Range {
+    /// Formats the items separated by `, `, or `[]` for an empty range.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.items.is_empty() {
+            return f.write_str("[]");
+        }
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{item}")?;
+        }
+        Ok(())
+    }
+}
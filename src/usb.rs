@@ -8,21 +8,50 @@
 
 use crate::channel::Channel;
 use crate::error::{Error, Result};
-use crate::protocol::nios::NiosPacketError;
+use crate::log_target;
+use nusb::descriptors::{InterfaceDescriptor, TransferType};
 use nusb::transfer::{Buffer, Bulk, ControlIn, ControlOut, ControlType, In, Out, Recipient};
 use nusb::{Device, Endpoint, Interface, MaybeFuture, Speed};
 use std::num::NonZero;
 use std::time::Duration;
 
 /// USB endpoint address for the control OUT bulk endpoint.
+///
+/// Endpoint 2 carries NIOS packets in the RF link alt setting, paired with
+/// `CONTROL_ENDPOINT_IN`.
 pub const CONTROL_ENDPOINT_OUT: u8 = 0x02;
-/// USB endpoint address for the control IN bulk endpoint.
+/// USB endpoint address for the control IN bulk endpoint, on which NIOS
+/// responses arrive.
 pub const CONTROL_ENDPOINT_IN: u8 = 0x82;
 /// USB endpoint address for the RX streaming bulk endpoint.
+///
+/// Endpoint 1 carries samples in the RF link alt setting, paired with
+/// `STREAM_ENDPOINT_TX`. RX samples are read from 0x81, not from the NIOS
+/// response endpoint 0x82.
 pub const STREAM_ENDPOINT_RX: u8 = 0x81;
 /// USB endpoint address for the TX streaming bulk endpoint.
 pub const STREAM_ENDPOINT_TX: u8 = 0x01;
 
+/// Checks that the alternate setting `alt` declares a bulk endpoint at
+/// `address`.
+///
+/// Returns `Error::Argument` if the endpoint is missing or is not a bulk
+/// endpoint.
+pub fn check_bulk_endpoint(alt: &InterfaceDescriptor<'_>, address: u8) -> Result<()> {
+    match alt.endpoints().find(|ep| ep.address() == address) {
+        Some(ep) if ep.transfer_type() == TransferType::Bulk => Ok(()),
+        Some(ep) => Err(Error::Argument(format!(
+            "endpoint {address:#04x} in alt setting {} is {:?}, not bulk",
+            alt.alternate_setting(),
+            ep.transfer_type()
+        ))),
+        None => Err(Error::Argument(format!(
+            "alt setting {} has no endpoint {address:#04x}",
+            alt.alternate_setting()
+        ))),
+    }
+}
+
 /// USB alternate setting for the BladeRF interface.
 ///
 /// Each setting reconfigures the bulk endpoints for a different
@@ -63,9 +92,11 @@ pub enum VendorRequest {
     QueryFpgaStatus = 1,
     /// Signals the device to begin FPGA programming.
     BeginProg = 2,
-    /// Enables/disables the RX streaming module.
+    /// Starts (`wValue` 1) or stops (`wValue` 0) the FX3's RX RF data path
+    /// (`BLADE_USB_CMD_RF_RX`). Issued by `RfLinkSession::enable_module`.
     RfRx = 4,
-    /// Enables/disables the TX streaming module.
+    /// Starts (`wValue` 1) or stops (`wValue` 0) the FX3's TX RF data path
+    /// (`BLADE_USB_CMD_RF_TX`). Issued by `RfLinkSession::enable_module`.
     RfTx = 5,
     /// Queries whether the firmware is ready.
     QueryDeviceReady = 6,
@@ -81,6 +112,9 @@ pub enum VendorRequest {
     FlashErase = 102,
     /// Resets the FX3 USB controller.
     Reset = 105,
+    /// Makes the FX3 re-enumerate as its ROM bootloader
+    /// (`BLADE_USB_CMD_JUMP_TO_BOOTLOADER`).
+    JumpToBootloader = 106,
     /// Reads from the FX3 page buffer.
     ReadPageBuffer = 107,
     /// Writes to the FX3 page buffer.
@@ -95,7 +129,37 @@ pub enum VendorRequest {
     ReadLogEntry = 115,
 }
 
-const TIMEOUT: Duration = Duration::from_secs(3);
+/// Default timeout for control requests and NIOS packet transfers.
+pub const DEFAULT_CONTROL_TIMEOUT: Duration = Duration::from_secs(3);
+
+const TIMEOUT: Duration = DEFAULT_CONTROL_TIMEOUT;
+
+/// Time each read while discarding late NIOS responses is waited for.
+const NIOS_DRAIN_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Largest number of late NIOS responses discarded after a timeout.
+const NIOS_DRAIN_MAX_PACKETS: usize = 4;
+
+/// FX3 SDK status returned when starting an RF data path that is already running.
+pub const CY_U3P_ERROR_ALREADY_STARTED: u32 = 0x44;
+
+/// Maps the FX3 status word returned by an RF enable request to a result.
+///
+/// `0` and `CY_U3P_ERROR_ALREADY_STARTED` are success; any other code is
+/// returned as `Error::Fx3`.
+pub fn check_fx3_status(code: u32) -> Result<()> {
+    match code {
+        0 => Ok(()),
+        CY_U3P_ERROR_ALREADY_STARTED => {
+            log::debug!(target: log_target::USB, "FX3 RF data path already started");
+            Ok(())
+        }
+        code => Err(Error::Fx3 { code }),
+    }
+}
+
+/// Maximum size of a single transfer issued by `usb_bulk_out`.
+const BULK_OUT_CHUNK_SIZE: usize = 64 * 1024;
 
 /// Standard USB string descriptor indices for the BladeRF.
 #[repr(u8)]
@@ -279,6 +343,33 @@ fn vendor_cmd_in(
     Ok(vec)
 }
 
+/// Cycles the alt setting through `Null` to `RfLink`, as the FX3 requires
+/// after toggling firmware loopback.
+///
+/// `change` performs a single alt-setting switch. If either switch fails,
+/// `change` is called once more to return to `prior`, so a failure between
+/// the two does not leave the interface without endpoints, and the original
+/// error is returned.
+pub(crate) fn cycle_alt_setting(
+    prior: UsbAltSetting,
+    mut change: impl FnMut(UsbAltSetting) -> Result<()>,
+) -> Result<()> {
+    let cycled = change(UsbAltSetting::Null).and_then(|()| change(UsbAltSetting::RfLink));
+    if let Err(err) = &cycled {
+        log::warn!(
+            target: log_target::USB,
+            "Alt setting cycle failed ({err}), restoring {prior:?}"
+        );
+        if let Err(restore) = change(prior) {
+            log::error!(
+                target: log_target::USB,
+                "Failed to restore alt setting {prior:?}: {restore}"
+            );
+        }
+    }
+    cycled
+}
+
 /// BladeRF1-specific USB interface commands.
 ///
 /// Extends `UsbInterfaceCommands` with operations for streaming
@@ -286,13 +377,27 @@ fn vendor_cmd_in(
 /// and bulk OUT transfers.
 pub trait BladeRf1UsbInterfaceCommands: UsbInterfaceCommands {
     /// Enables or disables the USB streaming module for the given channel.
+    ///
+    /// Issues vendor request `RfRx` (4) or `RfTx` (5) with `wValue` set to 1
+    /// or 0. The FX3 only moves samples between the FPGA and the streaming
+    /// bulk endpoints while the module is enabled, so this must follow the
+    /// LMS6002D RF front-end enable on activation and precede draining the
+    /// cancelled transfers on deactivation. The streams perform this
+    /// sequence in `start()`/`stop()`/`close()`.
+    ///
+    /// The FX3 status word is checked with `check_fx3_status()`, so a
+    /// failure is returned as `Error::Fx3`.
     fn usb_enable_module(&self, channel: Channel, enable: bool) -> Result<()>;
-    /// Sets the firmware loopback mode, cycling the alt setting to Null then RfLink.
+    /// Sets the firmware loopback mode, cycling the alt setting to Null then
+    /// RfLink with `cycle_alt_setting()`. If the cycle fails, the prior alt
+    /// setting is restored before the error is returned.
     fn usb_set_firmware_loopback(&mut self, enable: bool) -> Result<()>;
     /// Queries whether firmware loopback is currently enabled.
     fn usb_get_firmware_loopback(&self) -> Result<bool>;
     /// Resets the FX3 USB controller via a vendor control request.
     fn usb_device_reset(&self) -> Result<()>;
+    /// Makes the FX3 re-enumerate as its ROM bootloader via a vendor control request.
+    fn usb_jump_to_bootloader(&self) -> Result<()>;
     /// Returns `true` if the firmware has reported readiness.
     fn usb_is_firmware_ready(&self) -> Result<bool>;
     /// Returns `true` if the FPGA has finished configuration.
@@ -300,6 +405,9 @@ pub trait BladeRf1UsbInterfaceCommands: UsbInterfaceCommands {
     /// Signals the firmware to begin FPGA programming.
     fn usb_begin_fpga_prog(&self) -> Result<()>;
     /// Performs a bulk OUT transfer to the given endpoint address.
+    ///
+    /// Large payloads are sent as a sequence of transfers of at most
+    /// `BULK_OUT_CHUNK_SIZE` bytes; `timeout` applies to each transfer.
     fn usb_bulk_out(&self, endpoint: u8, data: &[u8], timeout: Duration) -> Result<()>;
 }
 impl BladeRf1UsbInterfaceCommands for Interface {
@@ -311,19 +419,23 @@ impl BladeRf1UsbInterfaceCommands for Interface {
             VendorRequest::RfTx
         };
         let fx3_ret = self.usb_vendor_cmd_int_w_value(cmd, val)?;
-        if fx3_ret != 0 {
-            log::warn!("usb_enable_module({channel:?}, {enable}): firmware returned {fx3_ret:#x}");
-        }
-        Ok(())
+        check_fx3_status(fx3_ret).inspect_err(|_| {
+            log::error!(
+                target: log_target::USB,
+                "usb_enable_module({channel:?}, {enable}): firmware returned {fx3_ret:#x}"
+            );
+        })
     }
     fn usb_set_firmware_loopback(&mut self, enable: bool) -> Result<()> {
         let fx3_ret = self.usb_vendor_cmd_int_w_value(VendorRequest::SetLoopback, enable as u16)?;
         if fx3_ret != 0 {
-            log::warn!("usb_set_firmware_loopback({enable}): firmware returned {fx3_ret:#x}");
+            log::warn!(
+                target: log_target::USB,
+                "usb_set_firmware_loopback({enable}): firmware returned {fx3_ret:#x}"
+            );
         }
-        self.usb_change_setting(UsbAltSetting::Null)?;
-        self.usb_change_setting(UsbAltSetting::RfLink)?;
-        Ok(())
+        let prior = UsbAltSetting::try_from(self.get_alt_setting()).unwrap_or(UsbAltSetting::Null);
+        cycle_alt_setting(prior, |setting| self.usb_change_setting(setting))
     }
     fn usb_get_firmware_loopback(&self) -> Result<bool> {
         let result = self.usb_vendor_cmd_int(VendorRequest::GetLoopback)?;
@@ -341,6 +453,18 @@ impl BladeRf1UsbInterfaceCommands for Interface {
         self.control_out(pkt, TIMEOUT).wait()?;
         Ok(())
     }
+    fn usb_jump_to_bootloader(&self) -> Result<()> {
+        let pkt = ControlOut {
+            control_type: ControlType::Vendor,
+            recipient: Recipient::Device,
+            request: VendorRequest::JumpToBootloader as u8,
+            value: 0x0,
+            index: 0x0,
+            data: &[],
+        };
+        self.control_out(pkt, TIMEOUT).wait()?;
+        Ok(())
+    }
     fn usb_is_firmware_ready(&self) -> Result<bool> {
         Ok(self.usb_vendor_cmd_int(VendorRequest::QueryDeviceReady)? != 0)
     }
@@ -364,11 +488,15 @@ impl BladeRf1UsbInterfaceCommands for Interface {
         let mut ep = self
             .endpoint::<Bulk, Out>(endpoint)
             .map_err(Error::EndpointBusy)?;
-        let mut buf = ep.allocate(data.len());
-        buf.extend_from_slice(data);
-        ep.submit(buf);
-        let completion = ep.wait_next_complete(timeout).ok_or(Error::Timeout)?;
-        completion.status?;
+        let mut buf = ep.allocate(data.len().min(BULK_OUT_CHUNK_SIZE));
+        for chunk in data.chunks(BULK_OUT_CHUNK_SIZE) {
+            buf.clear();
+            buf.extend_from_slice(chunk);
+            ep.submit(buf);
+            let completion = ep.wait_next_complete(timeout).ok_or(Error::Timeout)?;
+            completion.status?;
+            buf = completion.buffer;
+        }
         Ok(())
     }
 }
@@ -420,17 +548,20 @@ impl BladeRf1UsbInterfaceCommands for UsbTransport {
         self.interface.usb_enable_module(channel, enable)
     }
     /// Sets firmware loopback, using `self.usb_change_setting()` to
-    /// properly release NIOS packet URBs before the alt-setting change.
+    /// properly release NIOS packet URBs before each alt-setting change and
+    /// restoring the cached prior setting if the cycle fails.
     fn usb_set_firmware_loopback(&mut self, enable: bool) -> Result<()> {
         let fx3_ret = self
             .interface
             .usb_vendor_cmd_int_w_value(VendorRequest::SetLoopback, enable as u16)?;
         if fx3_ret != 0 {
-            log::warn!("usb_set_firmware_loopback({enable}): firmware returned {fx3_ret:#x}");
+            log::warn!(
+                target: log_target::USB,
+                "usb_set_firmware_loopback({enable}): firmware returned {fx3_ret:#x}"
+            );
         }
-        self.usb_change_setting(UsbAltSetting::Null)?;
-        self.usb_change_setting(UsbAltSetting::RfLink)?;
-        Ok(())
+        let prior = self.current_alt_setting;
+        cycle_alt_setting(prior, |setting| self.usb_change_setting(setting))
     }
     /// Delegates to the underlying interface.
     fn usb_get_firmware_loopback(&self) -> Result<bool> {
@@ -441,6 +572,10 @@ impl BladeRf1UsbInterfaceCommands for UsbTransport {
         self.interface.usb_device_reset()
     }
     /// Delegates to the underlying interface.
+    fn usb_jump_to_bootloader(&self) -> Result<()> {
+        self.interface.usb_jump_to_bootloader()
+    }
+    /// Delegates to the underlying interface.
     fn usb_is_firmware_ready(&self) -> Result<bool> {
         self.interface.usb_is_firmware_ready()
     }
@@ -475,6 +610,7 @@ pub struct UsbTransport {
     nios_endpoints: Option<NiosEndpoints>,
     current_alt_setting: UsbAltSetting,
     speed: Speed,
+    control_timeout: Duration,
 }
 impl UsbTransport {
     const NIOS_PKT_SIZE: usize = 16;
@@ -487,6 +623,7 @@ impl UsbTransport {
             nios_endpoints: None,
             current_alt_setting,
             speed,
+            control_timeout: DEFAULT_CONTROL_TIMEOUT,
         }
     }
     /// Returns a shared reference to the underlying nusb `Interface`.
@@ -501,6 +638,22 @@ impl UsbTransport {
     pub fn speed(&self) -> Speed {
         self.speed
     }
+    /// Returns the timeout applied to each NIOS packet transfer when
+    /// `submit()` is called without one.
+    pub fn control_timeout(&self) -> Duration {
+        self.control_timeout
+    }
+    /// Sets the timeout applied to each NIOS packet transfer when `submit()`
+    /// is called without one.
+    ///
+    /// Returns `Error::Argument` if `timeout` is zero.
+    pub fn set_control_timeout(&mut self, timeout: Duration) -> Result<()> {
+        if timeout.is_zero() {
+            return Err(Error::Argument("control timeout must be non-zero".into()));
+        }
+        self.control_timeout = timeout;
+        Ok(())
+    }
     /// Cancels pending NIOS transfers and releases the cached endpoints.
     ///
     /// Called before switching USB alternate settings to ensure clean
@@ -515,6 +668,7 @@ impl UsbTransport {
                 let timeout = remaining.min(Duration::from_secs(1));
                 if timeout.is_zero() {
                     log::warn!(
+                        target: log_target::USB,
                         "Timeout collecting cancelled NiosPkt transfers, OUT={} IN={} remain",
                         endpoints.ep_out.pending(),
                         endpoints.ep_in.pending()
@@ -526,7 +680,12 @@ impl UsbTransport {
                 {
                     match completion.status {
                         Ok(()) | Err(nusb::transfer::TransferError::Cancelled) => {}
-                        Err(e) => log::warn!("NiosPkt OUT transfer error during release: {e}"),
+                        Err(e) => {
+                            log::warn!(
+                                target: log_target::USB,
+                                "NiosPkt OUT transfer error during release: {e}"
+                            )
+                        }
                     }
                 }
                 if endpoints.ep_in.pending() > 0
@@ -534,7 +693,12 @@ impl UsbTransport {
                 {
                     match completion.status {
                         Ok(()) | Err(nusb::transfer::TransferError::Cancelled) => {}
-                        Err(e) => log::warn!("NiosPkt IN transfer error during release: {e}"),
+                        Err(e) => {
+                            log::warn!(
+                                target: log_target::USB,
+                                "NiosPkt IN transfer error during release: {e}"
+                            )
+                        }
                     }
                 }
             }
@@ -580,56 +744,130 @@ impl UsbTransport {
     /// Submits a NIOS packet and returns the response data.
     ///
     /// Performs a paired bulk OUT/IN transfer: submits the pre-filled
-    /// output buffer and waits for the corresponding IN response.
-    /// Returns a slice of exactly 16 bytes on success.
+    /// output buffer and waits for the corresponding IN response, each for
+    /// up to `timeout`, or `control_timeout()` if `None`.
+    /// Returns a slice of exactly 16 bytes on success, or
+    /// `Error::TransferTruncated` if the response is shorter.
+    ///
+    /// On `Error::Timeout` the outstanding transfer is cancelled and the
+    /// NIOS endpoints are released. The FPGA may still answer the timed-out
+    /// packet, and that reply would stay queued in the FX3 and be returned
+    /// for the next packet. The endpoints are therefore re-acquired and up
+    /// to `NIOS_DRAIN_MAX_PACKETS` late responses read and discarded, each
+    /// waited for `NIOS_DRAIN_TIMEOUT`.
     pub fn submit(&mut self, timeout: Option<Duration>) -> Result<&[u8]> {
+        let t = timeout.unwrap_or(self.control_timeout);
+        let transferred = self.transfer_nios_packet(t);
+        if matches!(transferred, Err(Error::Timeout)) {
+            log::warn!(
+                target: log_target::USB,
+                "NiosPkt transfer timed out after {t:?}, releasing endpoints"
+            );
+            self.release_endpoints();
+            match self.drain_nios_responses() {
+                Ok(0) => {}
+                Ok(n) => log::debug!(
+                    target: log_target::USB,
+                    "Discarded {n} late NiosPkt response(s)"
+                ),
+                Err(e) => log::warn!(
+                    target: log_target::USB,
+                    "Failed to drain late NiosPkt responses: {e}"
+                ),
+            }
+        }
+        transferred?;
+        let in_buf = self
+            .nios_endpoints
+            .as_ref()
+            .and_then(|endpoints| endpoints.buf_in.as_ref())
+            .ok_or(Error::EndpointNotAvailable)?;
+        let in_len = in_buf.len();
+        if in_len < Self::NIOS_PKT_SIZE {
+            return Err(Error::TransferTruncated {
+                actual: in_len,
+                expected: Self::NIOS_PKT_SIZE,
+            });
+        }
+        Ok(&in_buf[..Self::NIOS_PKT_SIZE])
+    }
+    fn transfer_nios_packet(&mut self, timeout: Duration) -> Result<()> {
         let endpoints = self.ensure_nios_endpoints()?;
-        let t = timeout.unwrap_or(TIMEOUT);
         let buf_out = endpoints
             .buf_out
             .take()
             .ok_or(Error::EndpointNotAvailable)?;
-        log::trace!("submit: OUT buffer len = {}", buf_out.len());
         endpoints.ep_out.submit(buf_out);
-        let mut response = endpoints
+        let response = endpoints
             .ep_out
-            .wait_next_complete(t)
+            .wait_next_complete(timeout)
             .ok_or(Error::Timeout)?;
-        response.status?;
         endpoints.buf_out = Some(response.buffer);
+        response.status?;
         let mut buf_in = endpoints.buf_in.take().ok_or(Error::EndpointNotAvailable)?;
         buf_in.set_requested_len(endpoints.ep_in.max_packet_size());
         endpoints.ep_in.submit(buf_in);
-        response = endpoints
+        let response = endpoints
             .ep_in
-            .wait_next_complete(t)
+            .wait_next_complete(timeout)
             .ok_or(Error::Timeout)?;
-        response.status?;
         endpoints.buf_in = Some(response.buffer);
-        let in_buf = endpoints
-            .buf_in
-            .as_ref()
-            .ok_or(Error::EndpointNotAvailable)?;
-        let in_len = in_buf.len();
-        if in_len < Self::NIOS_PKT_SIZE {
-            return Err(NiosPacketError::InvalidSize(in_len).into());
+        response.status?;
+        Ok(())
+    }
+    /// Reads and discards NIOS responses until none arrives within
+    /// `NIOS_DRAIN_TIMEOUT`, and returns how many were discarded.
+    fn drain_nios_responses(&mut self) -> Result<usize> {
+        let mut drained = 0;
+        let idle = {
+            let endpoints = self.ensure_nios_endpoints()?;
+            loop {
+                if drained == NIOS_DRAIN_MAX_PACKETS {
+                    break false;
+                }
+                let mut buf_in = endpoints.buf_in.take().ok_or(Error::EndpointNotAvailable)?;
+                buf_in.set_requested_len(endpoints.ep_in.max_packet_size());
+                endpoints.ep_in.submit(buf_in);
+                let Some(response) = endpoints.ep_in.wait_next_complete(NIOS_DRAIN_TIMEOUT) else {
+                    break true;
+                };
+                endpoints.buf_in = Some(response.buffer);
+                response.status?;
+                drained += 1;
+            }
+        };
+        if idle {
+            self.release_endpoints();
         }
-        Ok(&in_buf[..Self::NIOS_PKT_SIZE])
+        Ok(drained)
+    }
+    fn require_bulk_endpoint(&self, address: u8) -> Result<()> {
+        let alt = self.interface.descriptor().ok_or_else(|| {
+            Error::Argument(format!(
+                "no descriptor for alt setting {}",
+                self.interface.get_alt_setting()
+            ))
+        })?;
+        check_bulk_endpoint(&alt, address)
     }
     /// Acquires the RX streaming bulk IN endpoint.
     ///
-    /// Returns an error if the endpoint is already claimed by another
-    /// consumer.
+    /// Returns `Error::Argument` if the current alt setting does not declare
+    /// `STREAM_ENDPOINT_RX` as a bulk endpoint, and an error if the endpoint
+    /// is already claimed by another consumer.
     pub fn acquire_streaming_rx_endpoint(&self) -> Result<Endpoint<Bulk, In>> {
+        self.require_bulk_endpoint(STREAM_ENDPOINT_RX)?;
         self.interface
             .endpoint::<Bulk, In>(STREAM_ENDPOINT_RX)
             .map_err(Error::EndpointBusy)
     }
     /// Acquires the TX streaming bulk OUT endpoint.
     ///
-    /// Returns an error if the endpoint is already claimed by another
-    /// consumer.
+    /// Returns `Error::Argument` if the current alt setting does not declare
+    /// `STREAM_ENDPOINT_TX` as a bulk endpoint, and an error if the endpoint
+    /// is already claimed by another consumer.
     pub fn acquire_streaming_tx_endpoint(&self) -> Result<Endpoint<Bulk, Out>> {
+        self.require_bulk_endpoint(STREAM_ENDPOINT_TX)?;
         self.interface
             .endpoint::<Bulk, Out>(STREAM_ENDPOINT_TX)
             .map_err(Error::EndpointBusy)
@@ -1,9 +1,12 @@
+use crate::error::{Error, Result};
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
 /// A semantic version (major.minor.patch).
 ///
 /// Used for both FX3 firmware and FPGA versions queried from the device.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Versions order by major, then minor, then patch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SemanticVersion {
     /// Major version number.
     pub(crate) major: u16,
@@ -13,7 +16,7 @@ pub struct SemanticVersion {
     pub(crate) patch: u16,
 }
 impl SemanticVersion {
-    pub fn new(major: u16, minor: u16, patch: u16) -> Self {
+    pub const fn new(major: u16, minor: u16, patch: u16) -> Self {
         Self {
             major,
             minor,
@@ -39,3 +42,41 @@ impl Display for SemanticVersion {
         f.write_fmt(format_args!("{}.{}.{}", self.major, self.minor, self.patch))
     }
 }
+
+impl FromStr for SemanticVersion {
+    type Err = Error;
+
+    /// Parses a version string such as `2.4.0`, `v1.7.1` or `2.4.0-git-09e9d49c`.
+    ///
+    /// An optional leading `v` and a suffix after the patch number introduced
+    /// by `-`, `+` or whitespace are accepted. Returns `Error::Argument` for
+    /// anything else.
+    fn from_str(s: &str) -> Result<Self> {
+        let malformed = || Error::Argument(format!("malformed version string: {s:?}"));
+        let s_trim = s.trim();
+        let body = s_trim.strip_prefix('v').unwrap_or(s_trim);
+        let mut parts = body.splitn(3, '.');
+        let number = |part: Option<&str>| -> Result<u16> {
+            let part = part.ok_or_else(malformed)?;
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(malformed());
+            }
+            part.parse().map_err(|_| malformed())
+        };
+        let major = number(parts.next())?;
+        let minor = number(parts.next())?;
+        let rest = parts.next().ok_or_else(malformed)?;
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let (patch, suffix) = rest.split_at(digits);
+        if !(suffix.is_empty()
+            || suffix.starts_with(['-', '+'])
+            || suffix.starts_with(char::is_whitespace))
+        {
+            return Err(malformed());
+        }
+        let patch = number(Some(patch))?;
+        Ok(Self::new(major, minor, patch))
+    }
+}
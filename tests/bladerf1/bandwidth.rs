@@ -32,3 +32,14 @@ fn bandwidth() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn bandwidth_range_instance_matches_static() -> Result<()> {
+    logging_init("bladerf1_bandwidth");
+
+    let mut sdr = sdr();
+    let rf = sdr.rf_link_session()?;
+    assert_eq!(rf.bandwidth_range(), RfLinkSession::get_bandwidth_range());
+
+    Ok(())
+}
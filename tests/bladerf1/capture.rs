@@ -0,0 +1,78 @@
+use super::common::*;
+use libbladerf_rs::bladerf1::hardware::lms6002d::loopback::Loopback;
+use libbladerf_rs::{Channel, Error, Result};
+use std::time::Duration;
+
+#[test]
+fn capture_loopback_samples() -> Result<()> {
+    logging_init("bladerf1_capture");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    let original_loopback = rf.get_loopback()?;
+
+    rf.set_loopback(Loopback::BbTxlpfRxlpf)?;
+    let desired = 100_000;
+    let capture = rf.capture(desired, Duration::from_secs(5))?;
+    log::trace!("Captured samples (DESIRED):\t{desired}");
+    log::trace!("Captured samples (ACTUAL):\t{}", capture.samples.len());
+
+    rf.set_loopback(original_loopback)?;
+    assert!(!capture.timed_out);
+    assert_eq!(capture.samples.len(), desired);
+    assert!(
+        capture
+            .samples
+            .iter()
+            .all(|s| (-1.0..1.0).contains(&s.re) && (-1.0..1.0).contains(&s.im))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn signal_peak_loopback() -> Result<()> {
+    logging_init("bladerf1_capture");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    let original_loopback = rf.get_loopback()?;
+
+    rf.set_loopback(Loopback::BbTxlpfRxlpf)?;
+    let peak = rf.measure_signal_peak(Channel::Rx);
+    rf.set_loopback(original_loopback)?;
+    let peak = peak?;
+    log::trace!(
+        "Signal peak (ACTUAL):\tI {:.4} Q {:.4}, {:.1} dBFS",
+        peak.i,
+        peak.q,
+        peak.dbfs()
+    );
+
+    assert!((0.0..=1.0).contains(&peak.i) && (0.0..=1.0).contains(&peak.q));
+    assert!(matches!(
+        rf.measure_signal_peak(Channel::Tx),
+        Err(Error::Unsupported(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn measure_power_below_full_scale() -> Result<()> {
+    logging_init("bladerf1_capture");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+
+    let power = rf.measure_power(Channel::Rx, 4096)?;
+    log::trace!("RX power (ACTUAL):\t{power:.1} dBFS");
+
+    assert!(power <= 0.0);
+    assert!(matches!(
+        rf.measure_power(Channel::Tx, 4096),
+        Err(Error::Unsupported(_))
+    ));
+
+    Ok(())
+}
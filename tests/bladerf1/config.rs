@@ -0,0 +1,34 @@
+use super::common::*;
+use libbladerf_rs::Channel;
+use libbladerf_rs::Result;
+use libbladerf_rs::bladerf1::GainStage;
+use libbladerf_rs::bladerf1::hardware::lms6002d::loopback::Loopback;
+
+#[test]
+fn capture_apply_roundtrip() -> Result<()> {
+    logging_init("bladerf1_config");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    let original = rf.capture_config()?;
+    log::trace!("Config (CAPTURED):\t{original:?}");
+
+    let json = serde_json::to_string(&original).unwrap();
+    let parsed = serde_json::from_str(&json).unwrap();
+    assert_eq!(original, parsed);
+
+    rf.set_sample_rate(Channel::Rx, 2_000_000)?;
+    rf.set_bandwidth(Channel::Rx, 1_500_000)?;
+    rf.set_frequency_default_mode(Channel::Rx, original.rx.frequency + 10_000_000)?;
+    rf.set_frequency_default_mode(Channel::Tx, original.tx.frequency + 10_000_000)?;
+    rf.set_gain_stage(GainStage::TxVga2, 0.into())?;
+    rf.set_loopback(Loopback::BbTxlpfRxlpf)?;
+
+    rf.apply_config(&parsed)?;
+    let restored = rf.capture_config()?;
+    log::trace!("Config (DESIRED):\t{original:?}");
+    log::trace!("Config (ACTUAL):\t{restored:?}");
+    assert_eq!(restored, original);
+
+    Ok(())
+}
@@ -76,3 +76,27 @@ fn iq_correction() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn correction_out_of_range() -> Result<()> {
+    logging_init("bladerf1_correction");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+
+    for (corr, value) in [
+        (Correction::Gain, 4_097),
+        (Correction::Phase, -4_097),
+        (Correction::DcOffI, 2_049),
+        (Correction::DcOffQ, -2_049),
+    ] {
+        let current = rf.get_correction(Channel::Rx, &corr)?;
+        assert!(matches!(
+            rf.set_correction(Channel::Rx, &corr, value),
+            Err(libbladerf_rs::Error::Argument(_))
+        ));
+        assert_eq!(rf.get_correction(Channel::Rx, &corr)?, current);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use super::common::*;
+use libbladerf_rs::Result;
+
+#[test]
+fn dac_trim_roundtrip() -> Result<()> {
+    logging_init("bladerf1_dac_trim");
+
+    let mut sdr = sdr();
+    let factory_trim = sdr.factory_dac_trim();
+    log::trace!("Factory DAC trim: {factory_trim:#06x}");
+    let mut rf = sdr.rf_link_session()?;
+    let original = rf.get_dac_trim()?;
+
+    for desired in [0x0000u16, 0x7abc, 0xffff] {
+        rf.set_dac_trim(desired)?;
+        let actual = rf.get_dac_trim()?;
+        log::trace!("DAC trim (DESIRED):\t{desired:#06x}");
+        log::trace!("DAC trim (ACTUAL):\t{actual:#06x}");
+        assert_eq!(actual, desired);
+    }
+
+    rf.set_dac_trim(original)?;
+
+    Ok(())
+}
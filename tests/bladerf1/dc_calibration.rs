@@ -1,4 +1,5 @@
 use super::common::*;
+use libbladerf_rs::Channel;
 use libbladerf_rs::bladerf1::hardware::lms6002d::dc_calibration::{DcCalModule, DcCals};
 
 #[test]
@@ -61,3 +62,100 @@ fn calibrate_all_modules() -> libbladerf_rs::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn calibrate_with_rffe_disabled() -> libbladerf_rs::Result<()> {
+    logging_init("bladerf1_dc_calibration");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    let backup = rf.get_dc_cals()?;
+    rf.enable_module(Channel::Rx, true)?;
+    rf.enable_module(Channel::Tx, false)?;
+
+    let result: libbladerf_rs::Result<()> = (|| {
+        for &module in &[DcCalModule::RxLpf, DcCalModule::RxVga2] {
+            rf.calibrate_dc(module)?;
+            let with_rffe = rf.get_dc_cals()?;
+            rf.calibrate_dc_rffe_disabled(module)?;
+            let without_rffe = rf.get_dc_cals()?;
+            log::debug!("{module:?} with RFFE enabled:\n{with_rffe}");
+            log::debug!("{module:?} with RFFE disabled:\n{without_rffe}");
+
+            let rx = rf.rffe_enabled(Channel::Rx)?;
+            let tx = rf.rffe_enabled(Channel::Tx)?;
+            log::trace!("RFFE RX/TX (DESIRED):\ttrue/false");
+            log::trace!("RFFE RX/TX (ACTUAL):\t{rx}/{tx}");
+            assert!(rx);
+            assert!(!tx);
+        }
+        Ok(())
+    })();
+
+    let rx_restored = rf.enable_module(Channel::Rx, false);
+    let tx_restored = rf.enable_module(Channel::Tx, false);
+    let cals_restored = rf.set_dc_cals(backup);
+    result?;
+    rx_restored?;
+    tx_restored?;
+    cals_restored
+}
+
+#[test]
+fn tune_lpf() -> libbladerf_rs::Result<()> {
+    logging_init("bladerf1_dc_calibration");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+
+    let value = rf.tune_lpf()?;
+    log::trace!("LPF tuning value: {value:#04x}");
+    assert_eq!(rf.get_lpf_tuning()?, value);
+
+    for edge in [0, 0x3f] {
+        rf.set_lpf_tuning(edge)?;
+        assert_eq!(rf.get_lpf_tuning()?, edge);
+    }
+    assert!(matches!(
+        rf.set_lpf_tuning(0x40),
+        Err(libbladerf_rs::Error::Argument(_))
+    ));
+    assert_eq!(rf.get_lpf_tuning()?, 0x3f);
+
+    rf.set_lpf_tuning(value)
+}
+
+#[test]
+fn calibrate_dc_all_rx_sequence() -> libbladerf_rs::Result<()> {
+    logging_init("bladerf1_dc_calibration");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    let gains_before = rf.capture_config()?.rx.gains;
+
+    let dc_cals = rf.calibrate_dc_all_rx()?;
+    log::trace!("DC cals after RX sequence: {dc_cals}");
+
+    for (name, value) in [
+        ("lpf_tuning", dc_cals.lpf_tuning()),
+        ("rx_lpf_i", dc_cals.rx_lpf_i()),
+        ("rx_lpf_q", dc_cals.rx_lpf_q()),
+        ("dc_ref", dc_cals.dc_ref()),
+        ("rxvga2a_i", dc_cals.rxvga2a_i()),
+        ("rxvga2a_q", dc_cals.rxvga2a_q()),
+        ("rxvga2b_i", dc_cals.rxvga2b_i()),
+        ("rxvga2b_q", dc_cals.rxvga2b_q()),
+    ] {
+        assert!(
+            (0..=0x3f).contains(&value),
+            "{name} {value:#x} out of range"
+        );
+    }
+
+    let gains_after = rf.capture_config()?.rx.gains;
+    log::trace!("RX gains (DESIRED):\t{gains_before:?}");
+    log::trace!("RX gains (ACTUAL):\t{gains_after:?}");
+    assert_eq!(gains_after, gains_before);
+
+    Ok(())
+}
@@ -1,3 +1,7 @@
+use super::common::*;
+use libbladerf_rs::flash::{BLADERF_FLASH_ADDR_CAL, BLADERF_FLASH_PAGE_SIZE};
+use libbladerf_rs::{Error, Result};
+
 /*use super::common::*;
 use libbladerf_rs::bladerf1::BladeRf1;
 use libbladerf_rs::flash::{
@@ -149,3 +153,32 @@ fn erase_flash_roundtrip() -> Result<()> {
     Ok(())
 }
 */
+
+#[test]
+fn byte_addressed_read_matches_pages() -> Result<()> {
+    logging_init("bladerf1_flash");
+
+    let mut sdr = sdr();
+    let mut flash = sdr.flash_session()?;
+    let mut page = [0u8; BLADERF_FLASH_PAGE_SIZE];
+    flash.read_page(
+        BLADERF_FLASH_ADDR_CAL / BLADERF_FLASH_PAGE_SIZE as u32,
+        &mut page,
+    )?;
+
+    let bytes = flash.read(BLADERF_FLASH_ADDR_CAL + 3, 16)?;
+    log::trace!("Cal bytes (DESIRED):\t{:02x?}", &page[3..19]);
+    log::trace!("Cal bytes (ACTUAL):\t{bytes:02x?}");
+    assert_eq!(bytes, page[3..19]);
+
+    assert!(matches!(
+        flash.erase(BLADERF_FLASH_ADDR_CAL + 0x100, 0x10000),
+        Err(Error::Argument(_))
+    ));
+    assert!(matches!(
+        flash.write(BLADERF_FLASH_ADDR_CAL + 1, &[0]),
+        Err(Error::Argument(_))
+    ));
+
+    Ok(())
+}
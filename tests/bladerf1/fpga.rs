@@ -1,3 +1,6 @@
+use super::common::*;
+use libbladerf_rs::{Error, Result};
+
 /*use super::common::*;
 use libbladerf_rs::Result;
 use libbladerf_rs::bladerf1::BladeRf1;
@@ -170,3 +173,17 @@ fn erase_stored_fpga_roundtrip() -> Result<()> {
     Ok(())
 }
 */
+
+#[test]
+fn load_truncated_fpga_is_rejected() -> Result<()> {
+    logging_init("bladerf1_fpga");
+
+    let mut sdr = sdr();
+    let bitstream = vec![0xFFu8; 64 * 1024];
+    let result = sdr.config_session()?.load_fpga(&bitstream);
+    log::trace!("Truncated FPGA load (ACTUAL):\t{result:?}");
+    assert!(matches!(result, Err(Error::Argument(_))));
+    assert!(sdr.rf_link_session()?.is_fpga_configured()?);
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use super::common::*;
 use libbladerf_rs::bladerf1::TuningMode;
+use libbladerf_rs::bladerf1::protocol::RetuneTimestamp;
 use libbladerf_rs::range::RangeItem;
 use libbladerf_rs::{Channel, Result};
 
@@ -45,3 +46,106 @@ fn frequency_tuning() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn default_tuning_mode() -> Result<()> {
+    logging_init("bladerf1_frequency");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    let original_mode = rf.get_tuning_mode();
+    let original_freq = rf.get_frequency(Channel::Rx)?;
+
+    for mode in [TuningMode::Host, TuningMode::Fpga] {
+        rf.set_tuning_mode(mode);
+        assert_eq!(rf.get_tuning_mode(), mode);
+
+        rf.set_frequency_default_mode(Channel::Rx, 915_000_000)?;
+        let new = rf.get_frequency(Channel::Rx)?;
+        log::trace!("{mode:?} tuning: {new}");
+        assert!((new as i64 - 915_000_000).abs() <= 1);
+    }
+
+    rf.set_tuning_mode(original_mode);
+    rf.set_frequency(Channel::Rx, original_freq, TuningMode::Fpga)?;
+
+    Ok(())
+}
+
+#[test]
+fn quick_tune_reapply() -> Result<()> {
+    logging_init("bladerf1_frequency");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    let original_freq = rf.get_frequency(Channel::Rx)?;
+
+    rf.set_frequency(Channel::Rx, 915_000_000, TuningMode::Fpga)?;
+    let quick_tune = rf.get_quick_tune(Channel::Rx)?;
+
+    rf.set_frequency(Channel::Rx, 2_400_000_000, TuningMode::Fpga)?;
+    rf.schedule_quick_retune(Channel::Rx, RetuneTimestamp::Now, &quick_tune)?;
+    let new = rf.get_frequency(Channel::Rx)?;
+    log::trace!("Quick tune re-applied: {new}");
+    assert!((new as i64 - 915_000_000).abs() <= 1);
+
+    rf.set_frequency(Channel::Rx, original_freq, TuningMode::Fpga)?;
+
+    Ok(())
+}
+
+#[test]
+fn frequency_both() -> Result<()> {
+    logging_init("bladerf1_frequency");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    let original_rx = rf.get_frequency(Channel::Rx)?;
+    let original_tx = rf.get_frequency(Channel::Tx)?;
+
+    let (rx_desired, tx_desired) = (915_000_000, 2_400_000_000);
+    log::trace!("RX/TX Frequency (DESIRED):\t{rx_desired} / {tx_desired}");
+    rf.set_frequency_both(rx_desired, tx_desired)?;
+    let rx_actual = rf.get_frequency(Channel::Rx)?;
+    let tx_actual = rf.get_frequency(Channel::Tx)?;
+    log::trace!("RX/TX Frequency (ACTUAL):\t{rx_actual} / {tx_actual}");
+    assert!((rx_actual as i64 - rx_desired as i64).abs() <= 1);
+    assert!((tx_actual as i64 - tx_desired as i64).abs() <= 1);
+
+    assert!(rf.set_frequency_both(rx_desired, 10_000_000_000).is_err());
+    assert_eq!(rf.get_frequency(Channel::Rx)?, rx_actual);
+
+    rf.set_frequency_both(original_rx, original_tx)?;
+
+    Ok(())
+}
+
+#[test]
+fn frequency_fast_path() -> Result<()> {
+    logging_init("bladerf1_frequency");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    let original = rf.get_frequency(Channel::Rx)?;
+    let desired = 915_000_000;
+
+    rf.set_frequency(Channel::Rx, desired, TuningMode::Host)?;
+    let start = std::time::Instant::now();
+    rf.set_frequency(Channel::Rx, desired, TuningMode::Host)?;
+    let skipped = start.elapsed();
+    let start = std::time::Instant::now();
+    rf.set_frequency_force(Channel::Rx, desired, TuningMode::Host)?;
+    let forced = start.elapsed();
+    log::trace!("Retune duration (SKIPPED):\t{skipped:?}");
+    log::trace!("Retune duration (FORCED):\t{forced:?}");
+    assert!(skipped < forced);
+
+    let actual = rf.get_frequency(Channel::Rx)?;
+    log::trace!("Frequency (DESIRED):\t{desired}");
+    log::trace!("Frequency (ACTUAL):\t{actual}");
+    assert!((actual as i64 - desired as i64).abs() <= 1);
+
+    rf.set_frequency(Channel::Rx, original, TuningMode::Fpga)?;
+
+    Ok(())
+}
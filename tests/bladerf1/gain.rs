@@ -1,7 +1,9 @@
 use super::common::*;
-use libbladerf_rs::bladerf1::RfLinkSession;
+use libbladerf_rs::bladerf1::calibration::{DcCalEntry, DcCalTable};
+use libbladerf_rs::bladerf1::hardware::lms6002d::dc_calibration::DcCals;
+use libbladerf_rs::bladerf1::{DcPair, GainMode, RfLinkSession};
 use libbladerf_rs::range::RangeItem;
-use libbladerf_rs::{Channel, Result};
+use libbladerf_rs::{Channel, Error, Result};
 
 #[test]
 fn set_gain() -> Result<()> {
@@ -41,3 +43,74 @@ fn set_gain() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn gain_stage_info() -> Result<()> {
+    logging_init("bladerf1_gain");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+
+    let rx = rf.gain_stage_info(Channel::Rx)?;
+    let names: Vec<&str> = rx.iter().map(|info| info.name).collect();
+    assert_eq!(names, ["lna", "rxvga1", "rxvga2"]);
+
+    for info in rx.iter().chain(rf.gain_stage_info(Channel::Tx)?.iter()) {
+        log::trace!("{info:?}");
+        let min = info.range.min_checked()?;
+        let max = info.range.max_checked()?;
+        assert!(min < max, "{}: empty range {min}..{max}", info.name);
+        let gain = info.gain.db() as f64;
+        assert!(
+            (min..=max).contains(&gain),
+            "{}: gain {gain} outside {min}..{max}",
+            info.name
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn gain_mode_toggle() -> Result<()> {
+    logging_init("bladerf1_gain");
+
+    let mut sdr = sdr();
+    sdr.clear_dc_cal_table(Channel::Rx);
+    {
+        let mut rf = sdr.rf_link_session()?;
+        assert!(matches!(
+            rf.set_gain_mode(Channel::Rx, GainMode::Default),
+            Err(Error::Unsupported(_))
+        ));
+        assert_eq!(rf.get_gain_mode(Channel::Rx)?, GainMode::Mgc);
+        assert!(matches!(
+            rf.get_gain_mode(Channel::Tx),
+            Err(Error::Unsupported(_))
+        ));
+    }
+
+    sdr.set_dc_cal_table(
+        Channel::Rx,
+        DcCalTable::new(
+            DcCals::new(-1, -1, -1, -1, -1, -1, -1, -1, -1, -1),
+            vec![
+                DcCalEntry::new(1_000_000_000, DcPair::new(0, 0)),
+                DcCalEntry::new(3_000_000_000, DcPair::new(0, 0)),
+            ],
+        ),
+    );
+    {
+        let mut rf = sdr.rf_link_session()?;
+        for desired in [GainMode::Default, GainMode::Mgc] {
+            rf.set_gain_mode(Channel::Rx, desired)?;
+            let actual = rf.get_gain_mode(Channel::Rx)?;
+            log::trace!("Gain Mode (DESIRED):\t{desired:?}");
+            log::trace!("Gain Mode (ACTUAL):\t{actual:?}");
+            assert_eq!(actual, desired);
+        }
+    }
+    sdr.clear_dc_cal_table(Channel::Rx);
+
+    Ok(())
+}
@@ -3,8 +3,10 @@ use libbladerf_rs::Channel;
 use libbladerf_rs::Result;
 use libbladerf_rs::bladerf1::board::SampleFormat;
 use libbladerf_rs::bladerf1::hardware::lms6002d::loopback::Loopback;
-use libbladerf_rs::bladerf1::{RxStream, TuningMode, TxStream};
-use std::time::Duration;
+use libbladerf_rs::bladerf1::{
+    METADATA_HEADER_SIZE, MetadataHeader, RxStream, TuningMode, TxStream,
+};
+use std::time::{Duration, Instant};
 
 #[test]
 fn loopback_set_get_roundtrip() -> Result<()> {
@@ -37,6 +39,59 @@ fn loopback_set_get_roundtrip() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn effective_loopback_roundtrip() -> Result<()> {
+    logging_init("bladerf1_loopback");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    rf.set_loopback(Loopback::None)?;
+
+    for desired in [
+        Loopback::BbTxlpfRxlpf,
+        Loopback::BbTxlpfRxvga2,
+        Loopback::BbTxvga1Rxlpf,
+        Loopback::BbTxvga1Rxvga2,
+        Loopback::Lna1,
+        Loopback::Lna2,
+        Loopback::Lna3,
+        Loopback::Firmware,
+        Loopback::None,
+    ] {
+        rf.set_loopback(desired)?;
+
+        let actual = rf.get_loopback()?;
+        log::trace!("Loopback (DESIRED):\t{desired:?}");
+        log::trace!("Loopback (ACTUAL):\t{actual:?}");
+        assert_eq!(actual, desired);
+    }
+
+    assert!(matches!(
+        rf.set_loopback(Loopback::RficBist),
+        Err(libbladerf_rs::Error::Argument(_))
+    ));
+    assert_eq!(rf.get_loopback()?, Loopback::None);
+
+    Ok(())
+}
+
+#[test]
+fn firmware_loopback_toggle_keeps_rf_link() -> Result<()> {
+    logging_init("bladerf1_loopback");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    let rate = rf.get_sample_rate(Channel::Rx)?;
+
+    for desired in [Loopback::Firmware, Loopback::None] {
+        rf.set_loopback(desired)?;
+        assert_eq!(rf.get_loopback()?, desired);
+        assert_eq!(rf.get_sample_rate(Channel::Rx)?, rate);
+    }
+
+    Ok(())
+}
+
 #[test]
 fn firmware_loopback_stream() -> Result<()> {
     logging_init("bladerf1_loopback");
@@ -115,6 +170,99 @@ fn firmware_loopback_stream() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn scheduled_tx_burst_loopback() -> Result<()> {
+    logging_init("bladerf1_loopback");
+
+    let mut sdr = sdr();
+    let message_size = if matches!(sdr.speed(), nusb::Speed::Super | nusb::Speed::SuperPlus) {
+        2048
+    } else {
+        1024
+    };
+    let mut rf = sdr.rf_link_session()?;
+    let original_rx_sr = rf.get_sample_rate(Channel::Rx)?;
+    let original_tx_sr = rf.get_sample_rate(Channel::Tx)?;
+
+    let sample_rate = 2_000_000u64;
+    rf.set_sample_rate(Channel::Rx, sample_rate as u32)?;
+    rf.set_sample_rate(Channel::Tx, sample_rate as u32)?;
+    rf.set_lms_loopback(Loopback::BbTxlpfRxlpf)?;
+
+    let buffer_size = message_size * 16;
+
+    let mut rx_stream = {
+        RxStream::builder(&mut rf)
+            .buffer_size(buffer_size)
+            .buffer_count(8)
+            .format(SampleFormat::Sc16Q11Meta)
+            .build()?
+    };
+    rx_stream.start(&mut rf)?;
+
+    let mut tx_stream = {
+        TxStream::builder(&mut rf)
+            .buffer_size(buffer_size)
+            .buffer_count(8)
+            .format(SampleFormat::Sc16Q11Meta)
+            .build()?
+    };
+    tx_stream.start(&mut rf)?;
+
+    let tx_data: Vec<u8> = (0..4096)
+        .flat_map(|i| {
+            let phase = (i as f32 * 2.0 * std::f32::consts::PI / 64.0).sin();
+            let val = (phase * 2047.0) as i16;
+            let bytes = val.to_le_bytes();
+            [bytes[0], bytes[1], bytes[0], bytes[1]]
+        })
+        .collect();
+
+    let scheduled = rf.get_timestamp(Channel::Tx)? + sample_rate / 100;
+    tx_stream.write(
+        &tx_data,
+        Some(scheduled),
+        true,
+        Some(Duration::from_secs(2)),
+    )?;
+
+    let mut arrival = None;
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while arrival.is_none() && Instant::now() < deadline {
+        let rx_buf = rx_stream.read(Some(Duration::from_secs(1)))?;
+        for message in rx_buf.chunks_exact(message_size) {
+            let Some(header) = MetadataHeader::from_bytes(message) else {
+                continue;
+            };
+            let onset = message[METADATA_HEADER_SIZE..]
+                .chunks_exact(4)
+                .position(|s| i16::from_le_bytes([s[0], s[1]]).abs() > 512);
+            if let Some(pos) = onset {
+                arrival = Some(header.timestamp() + pos as u64);
+                break;
+            }
+        }
+        rx_stream.recycle(rx_buf);
+    }
+    log::trace!("Scheduled burst at {scheduled}, observed at {arrival:?}");
+
+    rx_stream.close(&mut rf)?;
+    tx_stream.close(&mut rf)?;
+
+    rf.set_lms_loopback(Loopback::None)?;
+    rf.set_sample_rate(Channel::Rx, original_rx_sr)?;
+    rf.set_sample_rate(Channel::Tx, original_tx_sr)?;
+
+    let arrival = arrival.expect("scheduled burst was not observed on RX");
+    let tolerance = sample_rate / 500;
+    assert!(
+        arrival + tolerance >= scheduled && arrival <= scheduled + tolerance,
+        "burst scheduled at {scheduled} arrived at {arrival}"
+    );
+
+    Ok(())
+}
+
 fn run_loopback_stream_test(loopback_mode: Loopback, test_name: &str) -> Result<()> {
     logging_init("bladerf1_loopback");
 
@@ -2,7 +2,10 @@
 mod common;
 
 mod bandwidth;
+mod capture;
+mod config;
 mod correction;
+mod dac_trim;
 mod dc_cal_table;
 mod dc_calibration;
 mod flash;
@@ -11,8 +14,15 @@ mod frequency;
 mod gain;
 mod loopback;
 mod open;
+#[cfg(feature = "direct-register-access")]
+mod registers;
 mod rx_mux;
 mod sample_rate;
+mod self_test;
+mod stream;
+mod sweep;
+mod timestamp;
+mod tone;
 #[cfg(feature = "xb200")]
 mod xb200;
 #[cfg(feature = "xb200")]
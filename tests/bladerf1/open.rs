@@ -1,6 +1,12 @@
 use super::common::*;
-use libbladerf_rs::Result;
 use libbladerf_rs::bladerf1::board::Loopback;
+use libbladerf_rs::bladerf1::{
+    BladeRf1, BoardState, FX3_BOOTLOADER_USB_PID, FX3_BOOTLOADER_USB_VID,
+};
+use libbladerf_rs::usb::UsbAltSetting;
+use libbladerf_rs::{Channel, Error, Result};
+use nusb::MaybeFuture;
+use std::time::Duration;
 
 #[test]
 fn firmware_loopback_set_get() -> Result<()> {
@@ -19,3 +25,225 @@ fn firmware_loopback_set_get() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn usb_mode_after_initialize() -> Result<()> {
+    logging_init("bladerf1_open");
+
+    let mut sdr = sdr();
+    {
+        let mut rf = sdr.rf_link_session()?;
+        rf.initialize(false)?;
+    }
+    let mode = sdr.usb_mode();
+    assert_eq!(mode, UsbAltSetting::RfLink, "expected RfLink, got {mode:?}");
+
+    Ok(())
+}
+
+#[test]
+fn guarded_method_before_initialize() -> Result<()> {
+    logging_init("bladerf1_open");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+
+    rf.config_gpio_write(0)?;
+    assert_eq!(rf.board_state()?, BoardState::FpgaLoaded);
+    let result = rf.enable_module(Channel::Rx, true);
+    assert!(
+        matches!(
+            result,
+            Err(Error::WrongState {
+                required: BoardState::Initialized,
+                actual: BoardState::FpgaLoaded,
+            })
+        ),
+        "expected WrongState error, got {result:?}"
+    );
+
+    rf.initialize(true)?;
+    assert_eq!(rf.board_state()?, BoardState::Initialized);
+
+    Ok(())
+}
+
+#[test]
+fn fx3_version_matches_descriptor() -> Result<()> {
+    logging_init("bladerf1_open");
+
+    let sdr = sdr();
+    let descriptor = sdr.fx3_firmware_version()?;
+    let version = sdr.fx3_version()?;
+    log::trace!("FX3 firmware (DESCRIPTOR):\t{descriptor}");
+    log::trace!("FX3 firmware (PARSED):\t{version}");
+    assert!(descriptor.contains(&version.to_string()));
+
+    Ok(())
+}
+
+#[test]
+fn close_clears_loopback() -> Result<()> {
+    logging_init("bladerf1_open");
+
+    let mut sdr = sdr();
+    {
+        let mut rf = sdr.rf_link_session()?;
+        rf.set_loopback(Loopback::BbTxlpfRxvga2)?;
+    }
+    sdr.close()?;
+
+    let mut rf = sdr.rf_link_session()?;
+    let lb = rf.get_loopback()?;
+    log::trace!("Loopback after close (DESIRED):\t{:?}", Loopback::None);
+    log::trace!("Loopback after close (ACTUAL):\t{lb:?}");
+    assert_eq!(lb, Loopback::None);
+    assert_eq!(rf.board_state()?, BoardState::Initialized);
+
+    Ok(())
+}
+
+#[test]
+fn enumerate_reports_serial() -> Result<()> {
+    logging_init("bladerf1_open");
+
+    let sdr = sdr();
+    let devices = BladeRf1::enumerate()?;
+    log::trace!("Enumerated devices:\t{devices:?}");
+    assert!(!devices.is_empty());
+    assert!(
+        devices
+            .iter()
+            .all(|dev| dev.serial.as_deref().is_some_and(|s| !s.is_empty()))
+    );
+    let serial = sdr.serial()?;
+    assert!(
+        devices
+            .iter()
+            .any(|dev| dev.serial.as_deref() == Some(serial.as_str()))
+    );
+
+    Ok(())
+}
+
+/// Opens its own handle instead of the shared one, which it would otherwise
+/// invalidate for every other test. Run on its own with `-- --ignored`.
+#[test]
+#[ignore = "resets the device; run alone with --ignored"]
+fn reset_and_reopen_keeps_serial() -> Result<()> {
+    logging_init("bladerf1_open");
+
+    let sdr = BladeRf1::from_first()?;
+    let desired = sdr.serial()?;
+    log::trace!("Serial (DESIRED):\t{desired}");
+
+    let sdr = sdr.reset_and_reopen(std::time::Duration::from_secs(10))?;
+    let actual = sdr.serial()?;
+    log::trace!("Serial (ACTUAL):\t{actual}");
+    assert_eq!(actual, desired);
+
+    Ok(())
+}
+
+/// Leaves the device in the FX3 bootloader, from which it only returns after
+/// a power cycle or a firmware load. Runs only with
+/// `BLADERF_TEST_JUMP_TO_BOOTLOADER=1`, on its own (`-- --exact
+/// open::jump_to_bootloader_enumerates_boot_device`).
+#[test]
+fn jump_to_bootloader_enumerates_boot_device() -> Result<()> {
+    logging_init("bladerf1_open");
+    if std::env::var("BLADERF_TEST_JUMP_TO_BOOTLOADER").as_deref() != Ok("1") {
+        log::trace!("BLADERF_TEST_JUMP_TO_BOOTLOADER not set, skipping");
+        return Ok(());
+    }
+
+    BladeRf1::from_first()?.jump_to_bootloader()?;
+
+    let is_boot_device = |dev: &nusb::DeviceInfo| {
+        dev.vendor_id() == FX3_BOOTLOADER_USB_VID && dev.product_id() == FX3_BOOTLOADER_USB_PID
+    };
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    let found = loop {
+        if nusb::list_devices().wait()?.any(|dev| is_boot_device(&dev)) {
+            break true;
+        }
+        if std::time::Instant::now() >= deadline {
+            break false;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    };
+    log::trace!("FX3 bootloader (DESIRED):\ttrue");
+    log::trace!("FX3 bootloader (ACTUAL):\t{found}");
+    assert!(found, "FX3 bootloader did not enumerate");
+
+    Ok(())
+}
+
+#[test]
+fn shared_device_concurrent_reads() -> Result<()> {
+    logging_init("bladerf1_open");
+
+    let expected = sdr().rf_link_session()?.fpga_version()?;
+    let readers: Vec<_> = (0..2)
+        .map(|_| {
+            std::thread::spawn(|| -> Result<Vec<String>> {
+                (0..50)
+                    .map(|_| sdr().rf_link_session()?.fpga_version())
+                    .collect()
+            })
+        })
+        .collect();
+    for reader in readers {
+        let versions = reader.join().expect("reader thread panicked")?;
+        log::trace!("FPGA version (DESIRED):\t{expected}");
+        log::trace!("FPGA version (ACTUAL):\t{:?}", versions.last());
+        assert!(versions.iter().all(|v| *v == expected));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn control_timeout_expires_cleanly() -> Result<()> {
+    logging_init("bladerf1_open");
+
+    let mut sdr = sdr();
+    let original = sdr.control_timeout();
+    let version = sdr.rf_link_session()?.fpga_version()?;
+
+    sdr.set_control_timeout(Duration::from_nanos(1))?;
+    let result = sdr.rf_link_session()?.config_gpio_read();
+    sdr.set_control_timeout(original)?;
+    log::trace!("Short timeout read (ACTUAL):\t{result:?}");
+    assert!(matches!(result, Err(Error::Timeout)));
+
+    let after = sdr.rf_link_session()?.fpga_version()?;
+    log::trace!("FPGA version after timeout (DESIRED):\t{version}");
+    log::trace!("FPGA version after timeout (ACTUAL):\t{after}");
+    assert_eq!(after, version);
+    assert!(matches!(
+        sdr.set_control_timeout(Duration::ZERO),
+        Err(Error::Argument(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn enable_module_tracks_state() -> Result<()> {
+    logging_init("bladerf1_open");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+
+    rf.enable_module(Channel::Rx, true)?;
+    rf.enable_module(Channel::Rx, true)?;
+    log::trace!("RX module (DESIRED):\tSome(true)");
+    log::trace!("RX module (ACTUAL):\t{:?}", rf.module_enabled(Channel::Rx));
+    assert_eq!(rf.module_enabled(Channel::Rx), Some(true));
+
+    rf.enable_module_forced(Channel::Rx, false)?;
+    assert_eq!(rf.module_enabled(Channel::Rx), Some(false));
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use super::common::*;
+use libbladerf_rs::bladerf1::hardware::si5338::si5338_reg_is_restorable;
+use libbladerf_rs::{Channel, Result};
+
+#[test]
+fn si5338_dump_restore_round_trip() -> Result<()> {
+    logging_init("bladerf1_registers");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    let rate = rf.get_sample_rate(Channel::Rx)?;
+    let dump = rf.si5338_dump_registers()?;
+
+    rf.set_sample_rate(
+        Channel::Rx,
+        if rate == 1_000_000 {
+            2_000_000
+        } else {
+            1_000_000
+        },
+    )?;
+    rf.si5338_restore_registers(&dump)?;
+
+    let restored = rf.si5338_dump_registers()?;
+    assert_eq!(
+        restored.iter().map(|&(addr, _)| addr).collect::<Vec<_>>(),
+        dump.iter().map(|&(addr, _)| addr).collect::<Vec<_>>()
+    );
+    for (&(addr, before), &(_, after)) in dump.iter().zip(&restored) {
+        if si5338_reg_is_restorable(addr) {
+            assert_eq!(after, before, "register {addr}");
+        }
+    }
+    assert_eq!(rf.get_sample_rate(Channel::Rx)?, rate);
+    Ok(())
+}
+
+#[test]
+fn lms_dump_write_back_round_trip() -> Result<()> {
+    logging_init("bladerf1_registers");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    let frequency = rf.get_frequency(Channel::Rx)?;
+    let dump = rf.lms_dump_registers()?;
+
+    rf.set_frequency_default_mode(
+        Channel::Rx,
+        if frequency == 915_000_000 {
+            2_400_000_000
+        } else {
+            915_000_000
+        },
+    )?;
+    for &(addr, value) in &dump {
+        rf.lms_write(addr, value)?;
+    }
+
+    assert_eq!(rf.lms_dump_registers()?, dump);
+    Ok(())
+}
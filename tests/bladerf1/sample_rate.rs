@@ -43,3 +43,46 @@ fn sample_rate() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn rational_sample_rate_readback() -> Result<()> {
+    logging_init("bladerf1_sample_rate");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    let desired = 2_000_000u32;
+
+    for channel in [Channel::Rx, Channel::Tx] {
+        let current = rf.get_sample_rate(channel)?;
+        rf.set_sample_rate(channel, desired)?;
+
+        let rational = rf.get_rational_sample_rate(channel)?;
+        log::trace!("Channel {channel:?} Sample Rate (RATIONAL):\t{rational:?}");
+        let exact = rational.integer() as f64
+            + if rational.denominator() != 0 {
+                rational.numerator() as f64 / rational.denominator() as f64
+            } else {
+                0.0
+            };
+        assert!((exact - desired as f64).abs() < 1.0);
+        assert_eq!(rf.get_sample_rate(channel)?, desired);
+
+        rf.set_sample_rate(channel, current)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn sample_rate_range_instance_matches_static() -> Result<()> {
+    logging_init("bladerf1_sample_rate");
+
+    let mut sdr = sdr();
+    let rf = sdr.rf_link_session()?;
+    assert_eq!(
+        rf.sample_rate_range(),
+        RfLinkSession::get_sample_rate_range()
+    );
+
+    Ok(())
+}
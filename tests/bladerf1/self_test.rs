@@ -0,0 +1,38 @@
+use super::common::*;
+use libbladerf_rs::Result;
+use libbladerf_rs::bladerf1::hardware::lms6002d::loopback::Loopback;
+
+#[test]
+fn self_test_firmware_loopback_passes() -> Result<()> {
+    logging_init("bladerf1_self_test");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    let before = rf.capture_config()?;
+
+    let report = rf.self_test()?;
+    for result in &report.results {
+        log::trace!(
+            "Self-test {:?}:\tcorrelation {:.3}, SNR {:.1} dB, passed {}",
+            result.mode,
+            result.measurement.correlation,
+            result.measurement.snr_db,
+            result.passed
+        );
+    }
+
+    let firmware = report
+        .result(Loopback::Firmware)
+        .expect("firmware loopback leg missing");
+    assert!(
+        firmware.passed,
+        "firmware loopback leg failed: {firmware:?}"
+    );
+
+    let after = rf.capture_config()?;
+    log::trace!("Config (DESIRED):\t{before:?}");
+    log::trace!("Config (ACTUAL):\t{after:?}");
+    assert_eq!(after, before);
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use super::common::*;
+use libbladerf_rs::bladerf1::{RxStream, TxStream};
+use libbladerf_rs::{Error, Result};
+use std::time::Duration;
+
+#[test]
+fn set_buffer_count_before_start() -> Result<()> {
+    logging_init("bladerf1_stream");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+
+    let mut rx_stream = RxStream::builder(&mut rf).buffer_count(4).build()?;
+    assert_eq!(rx_stream.buffer_count()?, 4);
+    rx_stream.set_buffer_count(16)?;
+    assert_eq!(rx_stream.buffer_count()?, 16);
+    assert!(matches!(
+        rx_stream.set_buffer_count(0),
+        Err(Error::Argument(_))
+    ));
+
+    rx_stream.start(&mut rf)?;
+    assert!(matches!(
+        rx_stream.set_buffer_count(8),
+        Err(Error::BoardState(_))
+    ));
+    let buf = rx_stream.read(Some(Duration::from_secs(1)))?;
+    rx_stream.recycle(buf);
+    rx_stream.close(&mut rf)?;
+
+    let mut tx_stream = TxStream::builder(&mut rf).buffer_count(4).build()?;
+    tx_stream.set_buffer_count(2)?;
+    assert_eq!(tx_stream.buffer_count()?, 2);
+    tx_stream.close(&mut rf)?;
+
+    Ok(())
+}
+
+#[test]
+fn bare_rx_start_read_stop() -> Result<()> {
+    logging_init("bladerf1_stream");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+
+    let mut rx_stream = RxStream::builder(&mut rf).build()?;
+    for _ in 0..2 {
+        rx_stream.start(&mut rf)?;
+        for _ in 0..4 {
+            let buf = rx_stream.read(Some(Duration::from_secs(1)))?;
+            assert!(!buf.is_empty());
+            rx_stream.recycle(buf);
+        }
+        rx_stream.stop(&mut rf)?;
+    }
+    rx_stream.close(&mut rf)?;
+
+    Ok(())
+}
+
+#[test]
+fn bare_tx_start_submit_stop() -> Result<()> {
+    logging_init("bladerf1_stream");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+
+    let mut tx_stream = TxStream::builder(&mut rf).build()?;
+    tx_stream.start(&mut rf)?;
+    for _ in 0..4 {
+        let mut buf = tx_stream.get_buffer(Some(Duration::from_secs(1)))?;
+        let len = tx_stream.buffer_size()?;
+        buf.extend_fill(len, 0);
+        tx_stream.submit(buf, len)?;
+    }
+    tx_stream.wait_completion(Some(Duration::from_secs(1)))?;
+    tx_stream.stop(&mut rf)?;
+    tx_stream.close(&mut rf)?;
+
+    Ok(())
+}
+
+/// Minimal single-future executor so the async path can be exercised without
+/// pulling a runtime into the dev-dependencies.
+#[cfg(feature = "async")]
+fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    struct ThreadWaker(std::thread::Thread);
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = std::pin::pin!(fut);
+    loop {
+        match fut.as_mut().poll(&mut cx) {
+            Poll::Ready(out) => return out,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn rx_read_async_one_buffer() -> Result<()> {
+    logging_init("bladerf1_stream");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+
+    let mut rx_stream = RxStream::builder(&mut rf).build()?;
+    rx_stream.start(&mut rf)?;
+    let buf = block_on(rx_stream.read_async())?;
+    log::trace!("Async RX buffer length (ACTUAL):\t{}", buf.len());
+    assert_eq!(buf.len(), rx_stream.buffer_size()?);
+    rx_stream.recycle(buf);
+    rx_stream.close(&mut rf)?;
+
+    Ok(())
+}
+
+#[test]
+fn rx_explicit_buffer_cycling() -> Result<()> {
+    logging_init("bladerf1_stream");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    let buffer_count = 8;
+
+    let mut rx_stream = RxStream::builder(&mut rf)
+        .buffer_count(buffer_count)
+        .build()?;
+    let buffer_size = rx_stream.buffer_size()?;
+    rx_stream.start(&mut rf)?;
+    assert_eq!(rx_stream.pending()?, buffer_count);
+
+    let result = (|| {
+        for _ in 0..1000 {
+            let buf = rx_stream.poll_complete(Some(Duration::from_secs(1)))?;
+            assert_eq!(buf.len(), buffer_size);
+            rx_stream.submit(buf)?;
+        }
+        let mut held = Vec::new();
+        for _ in 0..buffer_count {
+            held.push(rx_stream.poll_complete(Some(Duration::from_secs(1)))?);
+        }
+        assert_eq!(rx_stream.pending()?, 0);
+        assert!(matches!(
+            rx_stream.poll_complete(Some(Duration::from_millis(10))),
+            Err(Error::NoTransfersPending)
+        ));
+        for buf in held {
+            rx_stream.recycle(buf);
+        }
+        Ok(())
+    })();
+    rx_stream.close(&mut rf)?;
+
+    result
+}
@@ -0,0 +1,26 @@
+use super::common::*;
+use libbladerf_rs::{Channel, Result};
+
+#[test]
+fn sweep_three_points() -> Result<()> {
+    logging_init("bladerf1_sweep");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    let original = rf.get_frequency(Channel::Rx)?;
+
+    let desired = [900_000_000, 910_000_000, 920_000_000];
+    let points = rf
+        .sweep(desired[0], desired[2], 10_000_000, 4096)?
+        .collect::<Result<Vec<_>>>();
+    rf.set_frequency_default_mode(Channel::Rx, original)?;
+    let points = points?;
+
+    let actual: Vec<u64> = points.iter().map(|p| p.frequency).collect();
+    log::trace!("Sweep frequencies (DESIRED):\t{desired:?}");
+    log::trace!("Sweep frequencies (ACTUAL):\t{actual:?}");
+    assert_eq!(actual, desired);
+    assert!(points.iter().all(|p| p.samples.len() == 4096));
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use super::common::*;
+use libbladerf_rs::bladerf1::SampleFormat;
+use libbladerf_rs::{Channel, Error, Result};
+use std::thread::sleep;
+use std::time::Duration;
+
+#[test]
+fn timestamp_requires_enable() -> Result<()> {
+    logging_init("bladerf1_timestamp");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    rf.perform_format_deconfig()?;
+
+    let result = rf.get_timestamp(Channel::Rx);
+    assert!(
+        matches!(result, Err(Error::BoardState(_))),
+        "expected BoardState error, got {result:?}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn rx_timestamp_monotonic() -> Result<()> {
+    logging_init("bladerf1_timestamp");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    rf.perform_format_config(SampleFormat::Sc16Q11Meta)?;
+
+    let first = rf.get_timestamp(Channel::Rx)?;
+    sleep(Duration::from_millis(10));
+    let second = rf.get_timestamp(Channel::Rx)?;
+    log::trace!("RX timestamps: {first} -> {second}");
+
+    rf.perform_format_deconfig()?;
+
+    assert!(
+        second >= first,
+        "RX timestamp went backwards: {first} -> {second}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use super::common::*;
+use libbladerf_rs::Channel;
+use libbladerf_rs::Result;
+use libbladerf_rs::bladerf1::hardware::lms6002d::loopback::Loopback;
+use libbladerf_rs::bladerf1::{RxStream, SampleFormat};
+use num_complex::Complex;
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+const SAMPLE_RATE: u32 = 2_000_000;
+const TONE_HZ: f32 = 31_250.0;
+const DFT_SIZE: usize = 256;
+
+fn dft_peak_bin(samples: &[Complex<f32>]) -> usize {
+    let power = |k: usize| {
+        samples
+            .iter()
+            .enumerate()
+            .map(|(n, s)| s * Complex::from_polar(1.0, -TAU * (k * n) as f32 / DFT_SIZE as f32))
+            .sum::<Complex<f32>>()
+            .norm_sqr()
+    };
+    (0..DFT_SIZE)
+        .max_by(|&a, &b| power(a).total_cmp(&power(b)))
+        .unwrap()
+}
+
+#[test]
+fn transmit_tone_firmware_loopback() -> Result<()> {
+    logging_init("bladerf1_tone");
+
+    let mut sdr = sdr();
+    let mut rf = sdr.rf_link_session()?;
+    let original_rx_sr = rf.get_sample_rate(Channel::Rx)?;
+    let original_tx_sr = rf.get_sample_rate(Channel::Tx)?;
+
+    rf.set_sample_rate(Channel::Rx, SAMPLE_RATE)?;
+    rf.set_sample_rate(Channel::Tx, SAMPLE_RATE)?;
+    rf.set_loopback(Loopback::Firmware)?;
+
+    let mut rx_stream = RxStream::builder(&mut rf)
+        .buffer_count(8)
+        .format(SampleFormat::Sc16Q11)
+        .build()?;
+    rx_stream.start(&mut rf)?;
+
+    rf.transmit_tone(Channel::Tx, TONE_HZ, 0.5, Duration::from_millis(100))?;
+
+    let mut loudest: Vec<Complex<f32>> = Vec::new();
+    for _ in 0..8 {
+        let buf = rx_stream.read(Some(Duration::from_secs(2)))?;
+        let mut samples = vec![Complex::default(); buf.len() / 4];
+        SampleFormat::Sc16Q11.to_complex_f32(&buf, &mut samples)?;
+        rx_stream.recycle(buf);
+        let block = &samples[..DFT_SIZE.min(samples.len())];
+        let energy = |s: &[Complex<f32>]| s.iter().map(|c| c.norm_sqr()).sum::<f32>();
+        if energy(block) > energy(&loudest) {
+            loudest = block.to_vec();
+        }
+    }
+
+    rx_stream.close(&mut rf)?;
+    rf.set_loopback(Loopback::None)?;
+    rf.set_sample_rate(Channel::Rx, original_rx_sr)?;
+    rf.set_sample_rate(Channel::Tx, original_tx_sr)?;
+
+    let expected = (TONE_HZ * DFT_SIZE as f32 / SAMPLE_RATE as f32).round() as usize;
+    let actual = dft_peak_bin(&loudest);
+    log::trace!("Tone peak bin (DESIRED):\t{expected}");
+    log::trace!("Tone peak bin (ACTUAL):\t{actual}");
+    assert_eq!(loudest.len(), DFT_SIZE);
+    assert_eq!(actual, expected);
+    assert!(
+        rf.transmit_tone(Channel::Rx, 0.0, 0.5, Duration::from_millis(1))
+            .is_err()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use libbladerf_rs::Channel;
+use libbladerf_rs::bladerf1::Band;
+
+const TX_FIELD: u32 = 3 << 3;
+const RX_FIELD: u32 = 3 << 5;
+
+#[test]
+fn band_select_bits() {
+    assert_eq!(Band::Low.apply_to_config_gpio(Channel::Tx, 0), 2 << 3);
+    assert_eq!(Band::High.apply_to_config_gpio(Channel::Tx, 0), 1 << 3);
+    assert_eq!(Band::Low.apply_to_config_gpio(Channel::Rx, 0), 2 << 5);
+    assert_eq!(Band::High.apply_to_config_gpio(Channel::Rx, 0), 1 << 5);
+}
+
+#[test]
+fn band_select_preserves_other_bits() {
+    for gpio in [
+        0x0000_0057u32,
+        0xFFFF_FFFF,
+        0x8003_0000,
+        0x0000_0060,
+        0x0000_0018,
+    ] {
+        for (channel, field) in [(Channel::Tx, TX_FIELD), (Channel::Rx, RX_FIELD)] {
+            for band in [Band::Low, Band::High] {
+                let result = band.apply_to_config_gpio(channel, gpio);
+                assert_eq!(
+                    result & !field,
+                    gpio & !field,
+                    "{band:?} on {channel:?} changed bits outside the band field of {gpio:#010x}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn band_select_switches_field() {
+    let gpio = Band::High.apply_to_config_gpio(Channel::Rx, 0x57);
+    assert_eq!(gpio, (0x57 & !RX_FIELD) | (1 << 5));
+    let gpio = Band::Low.apply_to_config_gpio(Channel::Rx, gpio);
+    assert_eq!(gpio, (0x57 & !RX_FIELD) | (2 << 5));
+    let gpio = Band::Low.apply_to_config_gpio(Channel::Tx, gpio);
+    assert_eq!(gpio & TX_FIELD, 2 << 3);
+    assert_eq!(gpio & RX_FIELD, 2 << 5);
+}
@@ -0,0 +1,62 @@
+use libbladerf_rs::Error;
+use libbladerf_rs::bladerf1::RfLinkSession;
+use libbladerf_rs::bladerf1::hardware::lms6002d::bandwidth::UINT_BANDWIDTHS;
+
+#[test]
+fn snap_to_next_wider_filter() {
+    assert_eq!(RfLinkSession::snap_bandwidth(4_000_000).unwrap(), 5_000_000);
+    assert_eq!(RfLinkSession::snap_bandwidth(1_600_000).unwrap(), 1_750_000);
+    assert_eq!(
+        RfLinkSession::snap_bandwidth(27_000_000).unwrap(),
+        28_000_000
+    );
+}
+
+#[test]
+fn supported_filters_unchanged() {
+    for bw in UINT_BANDWIDTHS {
+        assert_eq!(RfLinkSession::snap_bandwidth(bw).unwrap(), bw);
+    }
+}
+
+#[test]
+fn out_of_range_rejected() {
+    for bw in [0, 1_499_999, 28_000_001, 40_000_000] {
+        assert!(matches!(
+            RfLinkSession::snap_bandwidth(bw),
+            Err(Error::Argument(_))
+        ));
+    }
+}
+
+#[test]
+fn auto_bandwidth_leaves_guard_band() {
+    for (rate, bw) in [
+        (10_000_000, 7_000_000),
+        (2_000_000, 1_500_000),
+        (4_000_000, 3_000_000),
+        (5_000_000, 3_840_000),
+        (12_500_000, 10_000_000),
+        (40_000_000, 28_000_000),
+    ] {
+        assert_eq!(RfLinkSession::auto_bandwidth(rate), bw, "rate {rate}");
+    }
+}
+
+#[test]
+fn auto_bandwidth_narrowest_for_low_rates() {
+    for rate in [160_000, 1_000_000, 1_874_999] {
+        assert_eq!(RfLinkSession::auto_bandwidth(rate), 1_500_000);
+    }
+    assert_eq!(RfLinkSession::auto_bandwidth(1_875_000), 1_500_000);
+    assert_eq!(RfLinkSession::auto_bandwidth(2_187_500), 1_750_000);
+}
+
+#[test]
+fn auto_bandwidth_is_a_supported_filter() {
+    for rate in (160_000..=40_000_000).step_by(371_111) {
+        let bw = RfLinkSession::auto_bandwidth(rate);
+        assert!(UINT_BANDWIDTHS.contains(&bw));
+        assert!(bw as u64 * 5 <= rate as u64 * 4 || bw == 1_500_000);
+    }
+}
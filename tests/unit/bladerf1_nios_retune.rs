@@ -1,6 +1,11 @@
 use libbladerf_rs::Channel;
-use libbladerf_rs::bladerf1::protocol::NiosPktRetuneRequest;
+use libbladerf_rs::Error;
+use libbladerf_rs::bladerf1::protocol::{
+    NiosPktRetuneRequest, NiosPktRetuneResponse, RetuneTimestamp, nios_decode_retune,
+    nios_encode_retune,
+};
 use libbladerf_rs::bladerf1::{Band, Tune};
+use libbladerf_rs::protocol::nios::NiosPacketError;
 
 #[test]
 fn packet_retune_request() {
@@ -30,3 +35,78 @@ fn packet_retune_request() {
     assert_eq!(pkt.tune(), Tune::Normal);
     assert_eq!(pkt.xb_gpio(), xb_gpio);
 }
+
+#[test]
+fn packet_retune_clear_queue_layout() {
+    let mut buf = [0u8; 16];
+    nios_encode_retune(
+        &mut buf,
+        Channel::Rx,
+        RetuneTimestamp::ClearQueue,
+        0,
+        0,
+        0,
+        0,
+        Band::Low,
+        Tune::Normal,
+        0,
+    )
+    .expect("valid packet");
+
+    let expected: [u8; 16] = [
+        0x54, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0x40, 0x80,
+        0x00,
+    ];
+    assert_eq!(buf, expected);
+}
+
+#[test]
+fn packet_retune_response_magic() {
+    let mut buf = [0u8; 16];
+    buf[0] = 0x54;
+    buf[10] = 0x2;
+    let resp = NiosPktRetuneResponse::new(&buf).expect("valid response");
+    assert!(resp.is_success());
+
+    buf[0] = 0x41;
+    assert!(matches!(
+        NiosPktRetuneResponse::new(&buf),
+        Err(Error::NiosPacket(NiosPacketError::InvalidMagic(0x54, 0x41)))
+    ));
+}
+
+#[test]
+fn packet_retune_response_validate() {
+    let mut buf = [0u8; 16];
+    buf[0] = 0x54;
+    buf[9] = 0x3f;
+    NiosPktRetuneResponse::validate(&buf).expect("valid response");
+
+    buf[0] = 0x41;
+    assert!(matches!(
+        NiosPktRetuneResponse::validate(&buf),
+        Err(NiosPacketError::InvalidMagic(0x54, 0x41))
+    ));
+
+    buf[0] = 0x54;
+    buf[9] = 0x40;
+    assert!(matches!(
+        NiosPktRetuneResponse::validate(&buf),
+        Err(NiosPacketError::VcocapOverflow(0x40, 0x3f))
+    ));
+}
+
+#[test]
+fn packet_retune_response_over_length() {
+    let mut buf = [0u8; 17];
+    buf[0] = 0x54;
+    assert!(matches!(
+        NiosPktRetuneResponse::validate(&buf),
+        Err(NiosPacketError::InvalidSize(17))
+    ));
+    assert!(matches!(
+        nios_decode_retune(&buf),
+        Err(Error::NiosPacket(NiosPacketError::InvalidSize(17)))
+    ));
+    assert!(nios_decode_retune(&buf[..16]).is_ok());
+}
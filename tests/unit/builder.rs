@@ -0,0 +1,43 @@
+use libbladerf_rs::bladerf1::BladeRf1;
+use libbladerf_rs::{Channel, Error};
+
+#[test]
+fn valid_settings_pass_validation() {
+    BladeRf1::builder()
+        .serial("0123456789abcdef")
+        .frequency(Channel::Rx, 915_000_000)
+        .frequency(Channel::Tx, 2_400_000_000)
+        .sample_rate(Channel::Rx, 2_000_000)
+        .bandwidth(Channel::Rx, 1_500_000)
+        .gain(Channel::Rx, 30.into())
+        .gain(Channel::Tx, 40.into())
+        .validate()
+        .unwrap();
+}
+
+#[test]
+fn out_of_range_values_are_rejected() {
+    let builders = [
+        BladeRf1::builder().frequency(Channel::Rx, 100_000_000),
+        BladeRf1::builder().frequency(Channel::Tx, 4_000_000_000),
+        BladeRf1::builder().sample_rate(Channel::Rx, 1),
+        BladeRf1::builder().bandwidth(Channel::Tx, 100_000_000),
+        BladeRf1::builder().gain(Channel::Rx, 100.into()),
+        BladeRf1::builder().gain(Channel::Tx, 0.into()),
+    ];
+    for builder in builders {
+        assert!(
+            matches!(builder.validate(), Err(Error::Argument(_))),
+            "{builder:?}"
+        );
+    }
+}
+
+#[test]
+fn out_of_range_frequency_errors_before_opening() {
+    let result = BladeRf1::builder()
+        .serial("no-such-device")
+        .frequency(Channel::Rx, 10)
+        .open();
+    assert!(matches!(result, Err(Error::Argument(_))));
+}
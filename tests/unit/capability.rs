@@ -0,0 +1,37 @@
+use libbladerf_rs::SemanticVersion;
+use libbladerf_rs::bladerf1::{Capability, have_cap};
+
+#[test]
+fn xb200_threshold() {
+    let min = SemanticVersion::new(0, 0, 5);
+    assert_eq!(Capability::Xb200.min_fpga_version(), min);
+    assert!(!have_cap(SemanticVersion::new(0, 0, 4), Capability::Xb200));
+    assert!(have_cap(min, Capability::Xb200));
+    assert!(have_cap(SemanticVersion::new(0, 15, 0), Capability::Xb200));
+}
+
+#[test]
+fn masked_xbio_threshold() {
+    let min = SemanticVersion::new(0, 4, 1);
+    assert_eq!(Capability::MaskedXbioWrite.min_fpga_version(), min);
+    assert!(!have_cap(
+        SemanticVersion::new(0, 4, 0),
+        Capability::MaskedXbioWrite
+    ));
+    assert!(!have_cap(
+        SemanticVersion::new(0, 3, 9),
+        Capability::MaskedXbioWrite
+    ));
+    assert!(have_cap(min, Capability::MaskedXbioWrite));
+    assert!(have_cap(
+        SemanticVersion::new(1, 0, 0),
+        Capability::MaskedXbioWrite
+    ));
+}
+
+#[test]
+fn xb200_precedes_masked_xbio() {
+    let v = SemanticVersion::new(0, 1, 0);
+    assert!(have_cap(v, Capability::Xb200));
+    assert!(!have_cap(v, Capability::MaskedXbioWrite));
+}
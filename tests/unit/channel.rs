@@ -0,0 +1,37 @@
+use libbladerf_rs::{Channel, Error};
+
+#[test]
+fn channel_from_module() {
+    assert_eq!(Channel::try_from(0u8).unwrap(), Channel::Rx);
+    assert_eq!(Channel::try_from(1u8).unwrap(), Channel::Tx);
+    assert_eq!(Channel::try_from(Channel::Tx as u8).unwrap(), Channel::Tx);
+}
+
+#[test]
+fn invalid_module_is_rejected() {
+    for module in [2u8, 0x05, 0xff] {
+        assert!(matches!(Channel::try_from(module), Err(Error::Argument(_))));
+    }
+}
+
+#[test]
+fn channel_from_index() {
+    assert_eq!(Channel::rx(0).unwrap(), Channel::Rx);
+    assert_eq!(Channel::tx(0).unwrap(), Channel::Tx);
+    assert!(matches!(Channel::rx(1), Err(Error::Argument(_))));
+    assert!(matches!(Channel::tx(1), Err(Error::Argument(_))));
+}
+
+#[test]
+fn module_round_trip() {
+    for channel in [Channel::Rx, Channel::Tx] {
+        assert_eq!(channel.index(), 0);
+        assert_eq!(u8::from(channel), channel.as_module());
+        assert_eq!(Channel::try_from(channel.as_module()).unwrap(), channel);
+    }
+    assert_eq!(Channel::Rx.as_module(), 0);
+    assert_eq!(Channel::Tx.as_module(), 1);
+    for module in [2u8, 3] {
+        assert!(matches!(Channel::try_from(module), Err(Error::Argument(_))));
+    }
+}
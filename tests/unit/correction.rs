@@ -0,0 +1,47 @@
+use libbladerf_rs::bladerf1::board::{
+    BLADERF_CORR_DCOFF_MAX, BLADERF_CORR_DCOFF_MIN, BLADERF_CORR_GAIN_MAX, BLADERF_CORR_GAIN_MIN,
+    BLADERF_CORR_PHASE_MAX, BLADERF_CORR_PHASE_MIN, Correction,
+};
+use libbladerf_rs::protocol::nios::{NiosPkt8x16AddrIqCorr, NiosPkt8x16Target, nios_encode_write};
+
+#[test]
+fn correction_ranges() {
+    assert_eq!(
+        Correction::DcOffI.range(),
+        BLADERF_CORR_DCOFF_MIN..=BLADERF_CORR_DCOFF_MAX
+    );
+    assert_eq!(
+        Correction::DcOffQ.range(),
+        BLADERF_CORR_DCOFF_MIN..=BLADERF_CORR_DCOFF_MAX
+    );
+    assert_eq!(
+        Correction::Phase.range(),
+        BLADERF_CORR_PHASE_MIN..=BLADERF_CORR_PHASE_MAX
+    );
+    assert_eq!(
+        Correction::Gain.range(),
+        BLADERF_CORR_GAIN_MIN..=BLADERF_CORR_GAIN_MAX
+    );
+}
+
+#[test]
+fn iq_correction_packet_encoding() {
+    for (addr, value, addr_byte) in [
+        (NiosPkt8x16AddrIqCorr::RxGain, 4_096u16 + 100, 0x00),
+        (NiosPkt8x16AddrIqCorr::RxPhase, (-100i16) as u16, 0x01),
+        (NiosPkt8x16AddrIqCorr::TxGain, 4_096u16 - 100, 0x02),
+        (NiosPkt8x16AddrIqCorr::TxPhase, 100u16, 0x03),
+    ] {
+        let mut buf = [0u8; 16];
+        nios_encode_write::<u8, u16>(
+            &mut buf,
+            NiosPkt8x16Target::IqCorr.into(),
+            addr.into(),
+            value,
+        )
+        .unwrap();
+        let [lo, hi] = value.to_le_bytes();
+        assert_eq!(buf[..7], [0x42, 0x01, 0x01, 0x00, addr_byte, lo, hi]);
+        assert!(buf[7..].iter().all(|&b| b == 0));
+    }
+}
@@ -124,3 +124,59 @@ fn invalid_json() {
     let result: Result<DcCalTable, _> = serde_json::from_str("not json");
     assert!(result.is_err());
 }
+
+fn tbl_bytes(version: u32, entries: &[(u32, [i16; 8])]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&0x1ab1u16.to_le_bytes());
+    buf.extend_from_slice(&version.to_le_bytes());
+    buf.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+    buf.extend_from_slice(&[20, 10, 15, 25, 30, 5, 12, 18, 8, 22]);
+    for (freq, vals) in entries {
+        buf.extend_from_slice(&freq.to_le_bytes());
+        let n = if version >= 2 { 8 } else { 2 };
+        for v in &vals[..n] {
+            buf.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+    buf
+}
+
+#[test]
+fn binary_v2_interpolates() {
+    let buf = tbl_bytes(
+        2,
+        &[
+            (300_000_000, [300, 600, 3000, 6000, 1500, 1800, 300, 600]),
+            (100_000_000, [100, 200, 1000, 2000, 500, 600, 100, 200]),
+        ],
+    );
+    let table = DcCalTable::from_bytes(&buf).unwrap();
+    assert_eq!(table.reg_vals().lpf_tuning(), 20);
+    assert_eq!(table.reg_vals().rxvga2b_q(), 22);
+    assert_eq!(table.entries()[0].freq, 100_000_000);
+
+    let e = table.lookup(150_000_000);
+    assert_eq!(e.dc, DcPair::new(150, 300));
+    assert_eq!(e.max_dc, DcPair::new(1500, 3000));
+    assert_eq!(e.mid_dc, DcPair::new(750, 900));
+    assert_eq!(e.min_dc, DcPair::new(150, 300));
+}
+
+#[test]
+fn binary_v1_has_no_agc() {
+    let buf = tbl_bytes(1, &[(100_000_000, [-8, 16, 0, 0, 0, 0, 0, 0])]);
+    assert_eq!(buf.len(), 28);
+    let table = DcCalTable::from_bytes(&buf).unwrap();
+    let e = table.lookup(100_000_000);
+    assert_eq!(e.dc, DcPair::new(-8, 16));
+    assert_eq!(e.max_dc, DcPair::default());
+}
+
+#[test]
+fn binary_rejects_bad_header() {
+    let mut buf = tbl_bytes(2, &[(100_000_000, [0; 8])]);
+    assert!(DcCalTable::from_bytes(&buf[..buf.len() - 1]).is_err());
+    buf[0] = 0;
+    assert!(DcCalTable::from_bytes(&buf).is_err());
+    assert!(DcCalTable::from_bytes(&tbl_bytes(3, &[])).is_err());
+}
@@ -0,0 +1,64 @@
+use libbladerf_rs::Error;
+use libbladerf_rs::usb::{
+    CONTROL_ENDPOINT_IN, CONTROL_ENDPOINT_OUT, STREAM_ENDPOINT_RX, STREAM_ENDPOINT_TX,
+    check_bulk_endpoint,
+};
+use nusb::descriptors::ConfigurationDescriptor;
+
+/// Builds a configuration descriptor with one interface in alt setting 1
+/// holding the given `(address, bmAttributes)` endpoints.
+fn config(endpoints: &[(u8, u8)]) -> Vec<u8> {
+    let total = 9 + 9 + 7 * endpoints.len();
+    let mut bytes = vec![9, 2, total as u8, 0, 1, 1, 0, 0x80, 50];
+    bytes.extend_from_slice(&[9, 4, 0, 1, endpoints.len() as u8, 0xFF, 0, 0, 0]);
+    for &(address, attributes) in endpoints {
+        bytes.extend_from_slice(&[7, 5, address, attributes, 0x00, 0x02, 0]);
+    }
+    bytes
+}
+
+const BULK: u8 = 2;
+const INTERRUPT: u8 = 3;
+
+#[test]
+fn rf_link_endpoints_present() {
+    let bytes = config(&[
+        (STREAM_ENDPOINT_TX, BULK),
+        (STREAM_ENDPOINT_RX, BULK),
+        (CONTROL_ENDPOINT_OUT, BULK),
+        (CONTROL_ENDPOINT_IN, BULK),
+    ]);
+    let config = ConfigurationDescriptor::new(&bytes).unwrap();
+    let alt = config.interface_alt_settings().next().unwrap();
+    for address in [
+        STREAM_ENDPOINT_TX,
+        STREAM_ENDPOINT_RX,
+        CONTROL_ENDPOINT_OUT,
+        CONTROL_ENDPOINT_IN,
+    ] {
+        check_bulk_endpoint(&alt, address).unwrap();
+    }
+}
+
+#[test]
+fn missing_rx_endpoint() {
+    let bytes = config(&[(CONTROL_ENDPOINT_OUT, BULK), (CONTROL_ENDPOINT_IN, BULK)]);
+    let config = ConfigurationDescriptor::new(&bytes).unwrap();
+    let alt = config.interface_alt_settings().next().unwrap();
+    assert!(matches!(
+        check_bulk_endpoint(&alt, STREAM_ENDPOINT_RX),
+        Err(Error::Argument(_))
+    ));
+    check_bulk_endpoint(&alt, CONTROL_ENDPOINT_IN).unwrap();
+}
+
+#[test]
+fn non_bulk_endpoint() {
+    let bytes = config(&[(STREAM_ENDPOINT_RX, INTERRUPT)]);
+    let config = ConfigurationDescriptor::new(&bytes).unwrap();
+    let alt = config.interface_alt_settings().next().unwrap();
+    assert!(matches!(
+        check_bulk_endpoint(&alt, STREAM_ENDPOINT_RX),
+        Err(Error::Argument(_))
+    ));
+}
@@ -0,0 +1,102 @@
+use libbladerf_rs::bladerf1::BoardState;
+use libbladerf_rs::{Error, ResultExt};
+use nusb::transfer::TransferError;
+
+#[test]
+fn transfer_disconnected_maps_to_disconnected() {
+    assert!(matches!(
+        Error::from(TransferError::Disconnected),
+        Error::Disconnected
+    ));
+}
+
+#[test]
+fn transfer_errors_keep_their_variant() {
+    for err in [
+        TransferError::Cancelled,
+        TransferError::Stall,
+        TransferError::Fault,
+        TransferError::InvalidArgument,
+    ] {
+        assert!(matches!(Error::from(err), Error::Transfer(e) if e == err));
+    }
+}
+
+#[test]
+fn transfer_error_is_exposed_as_source() {
+    use std::error::Error as _;
+
+    let err = Error::from(TransferError::Stall);
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        TransferError::Stall.to_string()
+    );
+}
+
+#[test]
+fn wrong_state_display() {
+    let err = Error::WrongState {
+        required: BoardState::Initialized,
+        actual: BoardState::FpgaLoaded,
+    };
+    assert_eq!(
+        err.to_string(),
+        "wrong board state: requires initialized, board is FPGA loaded"
+    );
+}
+
+#[test]
+fn dc_calibration_names_module_and_keeps_source() {
+    use libbladerf_rs::bladerf1::hardware::lms6002d::dc_calibration::DcCalModule;
+    use std::error::Error as _;
+
+    let err = Error::DcCalibration {
+        module: DcCalModule::RxVga2,
+        source: Box::new(Error::CalibrationFailed("loop did not converge")),
+    };
+    assert_eq!(err.to_string(), "DC calibration of RxVga2 failed");
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "calibration failed: loop did not converge"
+    );
+}
+
+#[test]
+fn context_appears_in_display_chain() {
+    use std::error::Error as _;
+
+    let result: libbladerf_rs::Result<()> = Err(Error::Timeout);
+    let err = result
+        .context("writing VCOCAP")
+        .context("tuning RX")
+        .unwrap_err();
+    let mut chain = vec![err.to_string()];
+    let mut source = err.source();
+    while let Some(e) = source {
+        chain.push(e.to_string());
+        source = e.source();
+    }
+    assert_eq!(
+        chain,
+        ["tuning RX", "writing VCOCAP", "operation timed out"]
+    );
+    assert!(err.is_transient());
+}
+
+#[test]
+fn context_passes_ok_through() {
+    let result: libbladerf_rs::Result<u8> = Ok(7);
+    assert_eq!(result.context("reading freqsel").unwrap(), 7);
+}
+
+#[test]
+fn root_cause_looks_through_context() {
+    let result: libbladerf_rs::Result<()> = Err(Error::TuningFailed);
+    let err = result
+        .context("tuning VCOCAP")
+        .context("tuning RX")
+        .unwrap_err();
+    assert!(matches!(err, Error::Context { .. }));
+    assert!(matches!(err.root_cause(), Error::TuningFailed));
+    assert!(matches!(Error::Timeout.root_cause(), Error::Timeout));
+}
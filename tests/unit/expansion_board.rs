@@ -0,0 +1,70 @@
+use libbladerf_rs::Error;
+use libbladerf_rs::bladerf1::{ExpansionBoard, xb_gpio};
+
+const XB200_DIR: u32 = 0x3C00383E;
+const XB300_DIR: u32 = 0x004506F0;
+const XB100_DIR: u32 = 0xF9F80000;
+const RF_ON: u32 = 0x0800;
+
+#[test]
+fn unconfigured_directions_identify_nothing() {
+    for dir in [
+        0,
+        0x1,
+        XB200_DIR & !0x2,
+        XB300_DIR & !0x40,
+        XB100_DIR & !(1 << 19),
+        XB200_DIR | XB100_DIR,
+    ] {
+        assert_eq!(
+            ExpansionBoard::identify(dir),
+            ExpansionBoard::XbNone,
+            "{dir:#010x}"
+        );
+    }
+}
+
+#[test]
+fn all_outputs_identify_nothing() {
+    assert_eq!(ExpansionBoard::identify(0xffffffff), ExpansionBoard::XbNone);
+}
+
+#[cfg(feature = "xb200")]
+#[test]
+fn xb200_signature() {
+    assert_eq!(ExpansionBoard::identify(XB200_DIR), ExpansionBoard::Xb200);
+}
+
+#[cfg(feature = "xb300")]
+#[test]
+fn xb300_signature() {
+    assert_eq!(ExpansionBoard::identify(XB300_DIR), ExpansionBoard::Xb300);
+}
+
+#[cfg(feature = "xb100")]
+#[test]
+fn xb100_signature() {
+    assert_eq!(ExpansionBoard::identify(XB100_DIR), ExpansionBoard::Xb100);
+    assert_eq!(
+        ExpansionBoard::identify(XB100_DIR | 0x1),
+        ExpansionBoard::Xb100
+    );
+}
+
+#[cfg(feature = "xb200")]
+#[test]
+fn xb200_enabled_needs_signature_and_rf_on() {
+    assert!(ExpansionBoard::is_xb200_enabled_in_gpio(RF_ON, XB200_DIR));
+    assert!(!ExpansionBoard::is_xb200_enabled_in_gpio(0, XB200_DIR));
+    assert!(!ExpansionBoard::is_xb200_enabled_in_gpio(0xffffffff, 0));
+    assert!(!ExpansionBoard::is_xb200_enabled_in_gpio(RF_ON, XB300_DIR));
+}
+
+#[test]
+fn xb_gpio_pin_bounds() {
+    assert_eq!(xb_gpio(1).unwrap(), 0x0000_0001);
+    assert_eq!(xb_gpio(20).unwrap(), 0x0008_0000);
+    assert_eq!(xb_gpio(32).unwrap(), 0x8000_0000);
+    assert!(matches!(xb_gpio(0), Err(Error::Argument(_))));
+    assert!(matches!(xb_gpio(33), Err(Error::Argument(_))));
+}
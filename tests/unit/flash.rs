@@ -1,6 +1,8 @@
 use libbladerf_rs::flash::{
-    BLADERF_FLASH_FPGA_SIZE_40KLE, BLADERF_FLASH_FPGA_SIZE_115KLE, FpgaSize, binkv_add_field,
-    binkv_decode_field, binkv_encode_field, is_valid_fpga_size, make_cal_region, zcrc,
+    BLADERF_FLASH_ERASE_BLOCK_SIZE, BLADERF_FLASH_FPGA_SIZE_40KLE, BLADERF_FLASH_FPGA_SIZE_115KLE,
+    BLADERF_FLASH_PAGE_SIZE, FpgaImageInfo, FpgaSize, binkv_add_field, binkv_decode_field,
+    binkv_encode_field, decode_dac_trim, decode_fpga_meta_page, flash_units, is_valid_fpga_size,
+    make_cal_region, make_fpga_meta_page, zcrc,
 };
 
 #[test]
@@ -46,6 +48,23 @@ fn test_make_cal_region() {
     assert_eq!(binkv_decode_field(&cal, "DAC").unwrap(), "32768");
 }
 
+#[test]
+fn test_decode_dac_trim_known_layout() {
+    let mut cal = [0xFFu8; 256];
+    let fields = [
+        0x03, 0x42, 0x34, 0x30, 0x13, 0x17, 0x08, 0x44, 0x41, 0x43, 0x33, 0x31, 0x32, 0x35, 0x31,
+        0x89, 0xde,
+    ];
+    cal[..fields.len()].copy_from_slice(&fields);
+    assert_eq!(decode_dac_trim(&cal).unwrap(), 31251);
+}
+
+#[test]
+fn test_decode_dac_trim_blank() {
+    let cal = [0xFFu8; 256];
+    assert!(decode_dac_trim(&cal).is_err());
+}
+
 #[test]
 fn test_fpga_size_roundtrip() {
     for (variant, s) in [
@@ -69,3 +88,84 @@ fn test_is_valid_fpga_size() {
     assert!(!is_valid_fpga_size(BLADERF_FLASH_FPGA_SIZE_40KLE - 1));
     assert!(!is_valid_fpga_size(BLADERF_FLASH_FPGA_SIZE_40KLE + 1));
 }
+
+#[test]
+fn test_flash_units_aligned() {
+    let size = 4 << 20;
+    assert_eq!(
+        flash_units(0x40000, 0x20000, BLADERF_FLASH_ERASE_BLOCK_SIZE, size).unwrap(),
+        (4, 2)
+    );
+    assert_eq!(
+        flash_units(0x30000, 0x100, BLADERF_FLASH_PAGE_SIZE, size).unwrap(),
+        (0x300, 1)
+    );
+    assert_eq!(
+        flash_units(size - 0x100, 0x100, BLADERF_FLASH_PAGE_SIZE, size).unwrap(),
+        ((size - 0x100) / 0x100, 1)
+    );
+}
+
+#[test]
+fn test_flash_units_misaligned() {
+    let size = 4 << 20;
+    assert!(flash_units(0x41000, 0x10000, BLADERF_FLASH_ERASE_BLOCK_SIZE, size).is_err());
+    assert!(flash_units(0x40000, 0x1000, BLADERF_FLASH_ERASE_BLOCK_SIZE, size).is_err());
+    assert!(flash_units(0x30080, 0x100, BLADERF_FLASH_PAGE_SIZE, size).is_err());
+}
+
+#[test]
+fn test_flash_units_out_of_range() {
+    let size = 4 << 20;
+    assert!(flash_units(size, 0x100, BLADERF_FLASH_PAGE_SIZE, size).is_err());
+    assert!(
+        flash_units(
+            size - 0x10000,
+            0x20000,
+            BLADERF_FLASH_ERASE_BLOCK_SIZE,
+            size
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_decode_fpga_meta_page() {
+    let mut page = [0xFFu8; BLADERF_FLASH_PAGE_SIZE];
+    binkv_encode_field(&mut page, 0, "LEN", "1191788").unwrap();
+    let info = decode_fpga_meta_page(&page).unwrap().unwrap();
+    assert_eq!(
+        info,
+        FpgaImageInfo {
+            length: BLADERF_FLASH_FPGA_SIZE_40KLE
+        }
+    );
+    assert_eq!(info.fpga_size(), Some(FpgaSize::KLE40));
+}
+
+#[test]
+fn test_fpga_meta_page_roundtrip() {
+    let page = make_fpga_meta_page(BLADERF_FLASH_FPGA_SIZE_115KLE).unwrap();
+    let info = decode_fpga_meta_page(&page).unwrap().unwrap();
+    assert_eq!(info.length, BLADERF_FLASH_FPGA_SIZE_115KLE);
+    assert_eq!(info.fpga_size(), Some(FpgaSize::KLE115));
+
+    let odd = decode_fpga_meta_page(&make_fpga_meta_page(1234).unwrap()).unwrap();
+    assert_eq!(odd.unwrap().fpga_size(), None);
+}
+
+#[test]
+fn test_decode_fpga_meta_page_blank_and_invalid() {
+    assert_eq!(
+        decode_fpga_meta_page(&[0xFF; BLADERF_FLASH_PAGE_SIZE]).unwrap(),
+        None
+    );
+
+    let mut garbage = [0xFFu8; BLADERF_FLASH_PAGE_SIZE];
+    binkv_encode_field(&mut garbage, 0, "LEN", "12ab").unwrap();
+    assert!(decode_fpga_meta_page(&garbage).is_err());
+
+    let mut missing = [0xFFu8; BLADERF_FLASH_PAGE_SIZE];
+    binkv_encode_field(&mut missing, 0, "FOO", "1").unwrap();
+    assert!(decode_fpga_meta_page(&missing).is_err());
+}
@@ -0,0 +1,19 @@
+use libbladerf_rs::Error;
+use libbladerf_rs::bladerf1::FpgaSource;
+
+#[test]
+fn known_values() {
+    assert_eq!(FpgaSource::try_from(0).unwrap(), FpgaSource::Unknown);
+    assert_eq!(FpgaSource::try_from(1).unwrap(), FpgaSource::Flash);
+    assert_eq!(FpgaSource::try_from(2).unwrap(), FpgaSource::Host);
+}
+
+#[test]
+fn unexpected_values() {
+    for raw in [3u8, 0x7f, 0xff] {
+        assert!(matches!(
+            FpgaSource::try_from(raw),
+            Err(Error::Unsupported(_))
+        ));
+    }
+}
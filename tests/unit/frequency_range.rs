@@ -0,0 +1,147 @@
+use libbladerf_rs::Error;
+use libbladerf_rs::bladerf1::RfLinkSession;
+use libbladerf_rs::bladerf1::hardware::lms6002d::frequency::{
+    BLADERF_FREQUENCY_MIN, LmsFreq, get_frequency_max, get_frequency_min, vcocap_step_hz,
+};
+
+#[test]
+fn in_range() {
+    let (min, max) = (get_frequency_min() as u64, get_frequency_max() as u64);
+    for xb200 in [false, true] {
+        for frequency in [min, 915_000_000, 2_400_000_000, max] {
+            RfLinkSession::check_frequency(frequency, xb200).unwrap();
+        }
+    }
+}
+
+#[test]
+fn below_range() {
+    let min = get_frequency_min() as u64;
+    for frequency in [0, 100_000_000, min - 1] {
+        assert!(matches!(
+            RfLinkSession::check_frequency(frequency, false),
+            Err(Error::Argument(_))
+        ));
+        RfLinkSession::check_frequency(frequency, true).unwrap();
+    }
+}
+
+#[test]
+fn above_range() {
+    let max = get_frequency_max() as u64;
+    for xb200 in [false, true] {
+        for frequency in [max + 1, 6_000_000_000] {
+            assert!(matches!(
+                RfLinkSession::check_frequency(frequency, xb200),
+                Err(Error::Argument(_))
+            ));
+        }
+    }
+}
+
+#[test]
+fn xb200_extends_minimum_only() {
+    let native = RfLinkSession::frequency_range(false);
+    let xb200 = RfLinkSession::frequency_range(true);
+    assert_eq!(native.min(), Some(get_frequency_min() as f64));
+    assert_eq!(xb200.min(), Some(0.0));
+    assert_eq!(native.max(), xb200.max());
+}
+
+#[test]
+fn native_range_bounds() {
+    let range = RfLinkSession::frequency_range(false);
+    assert_eq!(range.min(), Some(237_500_000.0));
+    assert_eq!(range.max(), Some(3_800_000_000.0));
+}
+
+#[test]
+fn xb200_range_bounds() {
+    let range = RfLinkSession::frequency_range(true);
+    assert_eq!(range.min(), Some(0.0));
+    assert_eq!(range.max(), Some(3_800_000_000.0));
+}
+
+#[test]
+fn register_round_trip_does_not_diverge() {
+    for commanded in [
+        BLADERF_FREQUENCY_MIN as u64,
+        915_000_000,
+        2_400_000_000,
+        3_800_000_000,
+    ] {
+        let regs = LmsFreq::try_from(commanded).unwrap();
+        let actual = u64::from(&regs);
+        assert!(!RfLinkSession::frequency_diverges(commanded, actual));
+    }
+}
+
+#[test]
+fn divergent_registers_are_detected() {
+    let commanded = 915_000_000;
+    let step = vcocap_step_hz(commanded);
+    assert!(step > 0);
+
+    let stale = LmsFreq::try_from(commanded + 2 * step).unwrap();
+    assert!(RfLinkSession::frequency_diverges(
+        commanded,
+        u64::from(&stale)
+    ));
+    assert!(RfLinkSession::frequency_diverges(
+        commanded,
+        commanded - 2 * step
+    ));
+    assert!(!RfLinkSession::frequency_diverges(
+        commanded,
+        commanded + step
+    ));
+}
+
+#[test]
+fn mixer_frequencies_use_the_lms_step() {
+    let commanded = 100_000_000;
+    let step = vcocap_step_hz(1_248_000_000 - commanded);
+    assert!(!RfLinkSession::frequency_diverges(
+        commanded,
+        commanded + step
+    ));
+    assert!(RfLinkSession::frequency_diverges(
+        commanded,
+        commanded + step + 1
+    ));
+}
+
+#[test]
+fn retune_required_skips_only_identical_pll_settings() {
+    assert!(RfLinkSession::retune_required(None, 915_000_000));
+    assert!(!RfLinkSession::retune_required(
+        Some(915_000_000),
+        915_000_000
+    ));
+    assert!(!RfLinkSession::retune_required(
+        Some(2_400_000_000),
+        2_400_000_001
+    ));
+    assert!(RfLinkSession::retune_required(
+        Some(915_000_000),
+        915_100_000
+    ));
+    assert!(RfLinkSession::retune_required(
+        Some(1_499_999_999),
+        1_500_000_000
+    ));
+}
+
+#[test]
+fn retune_required_compares_xb200_mixer_side() {
+    let min = get_frequency_min() as u64;
+    assert!(!RfLinkSession::retune_required(
+        Some(100_000_000),
+        100_000_000
+    ));
+    assert!(RfLinkSession::retune_required(
+        Some(100_000_000),
+        101_000_000
+    ));
+    assert!(RfLinkSession::retune_required(Some(min - 1), min));
+}
@@ -0,0 +1,23 @@
+use libbladerf_rs::bladerf1::{FwLogEntry, FwLogFile};
+
+#[test]
+fn decode_entry_fields() {
+    let entry = FwLogEntry::from((4 << 27) | (1234 << 16) | 0xBEEF);
+    assert_eq!(entry.file(), FwLogFile::Gpif);
+    assert_eq!(entry.line(), 1234);
+    assert_eq!(entry.data(), 0xBEEF);
+    assert_eq!(entry.to_string(), "gpif.c, 1234, 0xBEEF");
+
+    let entry = FwLogEntry::from((7 << 27) | (0x7FF << 16) | 0x0001);
+    assert_eq!(entry.file(), FwLogFile::SpiFlash);
+    assert_eq!(entry.line(), 0x7FF);
+    assert_eq!(entry.data(), 0x0001);
+}
+
+#[test]
+fn unknown_file_id_decodes_as_none() {
+    let entry = FwLogEntry::from((31 << 27) | (42 << 16));
+    assert_eq!(entry.file(), FwLogFile::None);
+    assert_eq!(entry.line(), 42);
+    assert_eq!(entry.to_string(), "<none>, 42, 0x0000");
+}
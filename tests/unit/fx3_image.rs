@@ -0,0 +1,106 @@
+use libbladerf_rs::Error;
+use libbladerf_rs::bladerf1::{Fx3Image, Fx3Section, fx3_checksum};
+
+fn image(sections: &[(u32, &[u8])], entry_point: u32) -> Vec<u8> {
+    let mut img = vec![b'C', b'Y', 0x1C, 0xB0];
+    let mut checksum = 0u32;
+    for &(address, data) in sections {
+        img.extend_from_slice(&((data.len() / 4) as u32).to_le_bytes());
+        img.extend_from_slice(&address.to_le_bytes());
+        img.extend_from_slice(data);
+        checksum = checksum.wrapping_add(fx3_checksum(data));
+    }
+    img.extend_from_slice(&0u32.to_le_bytes());
+    img.extend_from_slice(&entry_point.to_le_bytes());
+    img.extend_from_slice(&checksum.to_le_bytes());
+    img
+}
+
+fn assert_rejected(img: &[u8]) {
+    let err = Fx3Image::parse(img).unwrap_err();
+    assert!(matches!(err, Error::Argument(_)), "{err:?}");
+}
+
+#[test]
+fn checksum_sums_le_words() {
+    assert_eq!(fx3_checksum(&[]), 0);
+    assert_eq!(fx3_checksum(&[1, 0, 0, 0, 2, 0, 0, 0]), 3);
+    assert_eq!(fx3_checksum(&[0x78, 0x56, 0x34, 0x12]), 0x1234_5678);
+    assert_eq!(fx3_checksum(&[0xFF; 8]), 0xFFFF_FFFE);
+    assert_eq!(fx3_checksum(&[1, 0, 0, 0, 9]), 1);
+}
+
+#[test]
+fn parses_sections_and_entry_point() {
+    let code = [0x11u8; 16];
+    let data = [0x22u8; 8];
+    let img = image(&[(0x4000_0000, &code), (0x0000_0100, &data)], 0x4000_0040);
+    let parsed = Fx3Image::parse(&img).unwrap();
+    assert_eq!(
+        parsed.sections,
+        [
+            Fx3Section {
+                address: 0x4000_0000,
+                data: &code
+            },
+            Fx3Section {
+                address: 0x0000_0100,
+                data: &data
+            },
+        ]
+    );
+    assert_eq!(parsed.entry_point, 0x4000_0040);
+}
+
+#[test]
+fn rejects_bad_header() {
+    let img = image(&[(0x4000_0000, &[0; 4])], 0x4000_0000);
+    let mut bad_magic = img.clone();
+    bad_magic[0] = b'X';
+    assert_rejected(&bad_magic);
+    let mut not_executable = img.clone();
+    not_executable[2] |= 0x01;
+    assert_rejected(&not_executable);
+    let mut bad_type = img.clone();
+    bad_type[3] = 0xB1;
+    assert_rejected(&bad_type);
+    assert_rejected(b"CY");
+}
+
+#[test]
+fn rejects_bad_checksum() {
+    let mut img = image(&[(0x4000_0000, &[1, 2, 3, 4])], 0x4000_0000);
+    let last = img.len() - 1;
+    img[last] ^= 0x80;
+    assert_rejected(&img);
+}
+
+#[test]
+fn rejects_truncated_and_trailing_data() {
+    let img = image(&[(0x4000_0000, &[0; 64])], 0x4000_0000);
+    for len in [4, 8, 40, img.len() - 4, img.len() - 1] {
+        assert_rejected(&img[..len]);
+    }
+    let mut trailing = img.clone();
+    trailing.push(0);
+    assert_rejected(&trailing);
+}
+
+#[test]
+fn rejects_addresses_outside_ram() {
+    assert_rejected(&image(&[(0x1000_0000, &[0; 4])], 0x4000_0000));
+    assert_rejected(&image(&[(0x0000_3FFC, &[0; 8])], 0x4000_0000));
+    assert_rejected(&image(&[(0x4007_FFFC, &[0; 8])], 0x4000_0000));
+    assert_rejected(&image(&[(0x4000_0000, &[0; 4])], 0x8000_0000));
+    assert_rejected(&image(&[(0x4000_0000, &[0; 4])], 0x4008_0000));
+    assert_rejected(&image(&[(0x4000_0000, &[0; 4])], 0x0000_4000));
+}
+
+#[test]
+fn rejects_oversized_section_length() {
+    let mut img = vec![b'C', b'Y', 0x1C, 0xB0];
+    img.extend_from_slice(&u32::MAX.to_le_bytes());
+    img.extend_from_slice(&0x4000_0000u32.to_le_bytes());
+    img.extend_from_slice(&[0; 16]);
+    assert_rejected(&img);
+}
@@ -0,0 +1,19 @@
+use libbladerf_rs::Error;
+use libbladerf_rs::usb::{CY_U3P_ERROR_ALREADY_STARTED, check_fx3_status};
+
+#[test]
+fn success_codes() {
+    check_fx3_status(0).unwrap();
+    check_fx3_status(CY_U3P_ERROR_ALREADY_STARTED).unwrap();
+}
+
+#[test]
+fn failure_codes_keep_their_value() {
+    for code in [0x40, 0x43, 0x45, 0xffff_ffff] {
+        assert!(matches!(check_fx3_status(code), Err(Error::Fx3 { code: c }) if c == code));
+    }
+    assert_eq!(
+        check_fx3_status(0x47).unwrap_err().to_string(),
+        "FX3 firmware returned status 0x47"
+    );
+}
@@ -0,0 +1,244 @@
+use libbladerf_rs::bladerf1::{GainDb, GainStage, RfLinkSession};
+use libbladerf_rs::{Channel, Error};
+
+#[test]
+fn linear_to_db() {
+    assert_eq!(GainDb::from_linear(1.0).db(), 0);
+    assert_eq!(GainDb::from_linear(10.0).db(), 20);
+    assert_eq!(GainDb::from_linear(0.1).db(), -20);
+    assert_eq!(GainDb::from_linear(2.0).db(), 6);
+}
+
+#[test]
+fn db_to_linear() {
+    assert!((GainDb::from(0).linear() - 1.0).abs() < 1e-6);
+    assert!((GainDb::from(20).linear() - 10.0).abs() < 1e-4);
+    assert!((GainDb::from(-20).linear() - 0.1).abs() < 1e-6);
+}
+
+#[test]
+fn linear_roundtrip() {
+    for db in -30i8..=60 {
+        assert_eq!(GainDb::from_linear(GainDb::from(db).linear()).db(), db);
+    }
+}
+
+#[test]
+fn linear_clamping() {
+    assert_eq!(GainDb::from_linear(1e10).db(), i8::MAX);
+    assert_eq!(GainDb::from_linear(1e-10).db(), i8::MIN);
+    assert_eq!(GainDb::from_linear(0.0).db(), i8::MIN);
+    assert_eq!(GainDb::from_linear(-1.0).db(), i8::MIN);
+    assert_eq!(GainDb::from_linear(f32::NAN).db(), i8::MIN);
+}
+
+fn distribution(channel: Channel, gain: i8) -> Vec<(GainStage, i8)> {
+    RfLinkSession::gain_distribution(channel, gain.into())
+        .unwrap()
+        .into_iter()
+        .map(|(stage, db)| (stage, db.db()))
+        .collect()
+}
+
+#[test]
+fn rx_gain_distribution() {
+    use GainStage::{Lna, RxVga1, RxVga2};
+    let cases = [
+        (-1, [0, 5, 0]),
+        (20, [3, 23, 0]),
+        (40, [6, 28, 12]),
+        (60, [6, 30, 30]),
+        (100, [6, 30, 30]),
+    ];
+    for (gain, [lna, rxvga1, rxvga2]) in cases {
+        assert_eq!(
+            distribution(Channel::Rx, gain),
+            [(Lna, lna), (RxVga1, rxvga1), (RxVga2, rxvga2)],
+            "{gain} dB"
+        );
+    }
+}
+
+#[test]
+fn tx_gain_distribution() {
+    use GainStage::{TxVga1, TxVga2};
+    let cases = [
+        (0, [-35, 0]),
+        (17, [-35, 0]),
+        (30, [-35, 13]),
+        (50, [-27, 25]),
+        (73, [-4, 25]),
+    ];
+    for (gain, [txvga1, txvga2]) in cases {
+        assert_eq!(
+            distribution(Channel::Tx, gain),
+            [(TxVga1, txvga1), (TxVga2, txvga2)],
+            "{gain} dB"
+        );
+    }
+}
+
+#[test]
+fn gain_distribution_sums_to_request() {
+    for (channel, offset) in [(Channel::Rx, -6), (Channel::Tx, 52)] {
+        let range = RfLinkSession::get_gain_range(channel);
+        let (min, max) = (range.min().unwrap() as i8, range.max().unwrap() as i8);
+        for gain in min..=max {
+            let total: i8 = distribution(channel, gain).iter().map(|(_, db)| db).sum();
+            assert_eq!(total + offset, gain, "{channel:?} {gain} dB");
+        }
+    }
+}
+
+fn distribution_codes(channel: Channel, gain: i8) -> Vec<(GainStage, u8)> {
+    RfLinkSession::gain_distribution(channel, gain.into())
+        .unwrap()
+        .into_iter()
+        .map(|(stage, db)| (stage, stage.gain_code(db)))
+        .collect()
+}
+
+#[test]
+fn tx_system_gain_register_codes() {
+    use GainStage::{TxVga1, TxVga2};
+    assert_eq!(
+        distribution_codes(Channel::Tx, 0),
+        [(TxVga1, 0), (TxVga2, 0)]
+    );
+    assert_eq!(
+        distribution_codes(Channel::Tx, 17),
+        [(TxVga1, 0), (TxVga2, 0)]
+    );
+    assert_eq!(
+        distribution_codes(Channel::Tx, 60),
+        [(TxVga1, 18), (TxVga2, 25)]
+    );
+    assert_eq!(
+        distribution_codes(Channel::Tx, 73),
+        [(TxVga1, 31), (TxVga2, 25)]
+    );
+}
+
+#[test]
+fn rx_system_gain_register_codes() {
+    use GainStage::{Lna, RxVga1, RxVga2};
+    assert_eq!(
+        distribution(Channel::Rx, 0),
+        [(Lna, 0), (RxVga1, 6), (RxVga2, 0)]
+    );
+    assert_eq!(
+        distribution_codes(Channel::Rx, 0),
+        [(Lna, 1), (RxVga1, 14), (RxVga2, 0)]
+    );
+    assert_eq!(
+        distribution_codes(Channel::Rx, 60),
+        [(Lna, 3), (RxVga1, 120), (RxVga2, 10)]
+    );
+}
+
+#[test]
+fn stage_name_dispatch() {
+    let cases = [
+        (Channel::Rx, "lna", GainStage::Lna),
+        (Channel::Rx, "rxvga1", GainStage::RxVga1),
+        (Channel::Rx, "RXVGA2", GainStage::RxVga2),
+        (Channel::Tx, "txvga1", GainStage::TxVga1),
+        (Channel::Tx, "TxVga2", GainStage::TxVga2),
+    ];
+    for (channel, name, stage) in cases {
+        assert_eq!(GainStage::for_channel(channel, name).unwrap(), stage);
+        assert_eq!(
+            RfLinkSession::get_gain_stage_range_by_name(channel, name).unwrap(),
+            stage.gain_range()
+        );
+    }
+}
+
+#[test]
+fn stage_name_rejects_unknown_and_wrong_direction() {
+    for (channel, name) in [
+        (Channel::Rx, "vga1"),
+        (Channel::Tx, ""),
+        (Channel::Rx, "txvga1"),
+        (Channel::Rx, "txvga2"),
+        (Channel::Tx, "lna"),
+        (Channel::Tx, "rxvga1"),
+        (Channel::Tx, "rxvga2"),
+    ] {
+        assert!(matches!(
+            GainStage::for_channel(channel, name),
+            Err(Error::Argument(_))
+        ));
+        assert!(matches!(
+            RfLinkSession::get_gain_stage_range_by_name(channel, name),
+            Err(Error::Argument(_))
+        ));
+    }
+}
+
+fn gain_from_codes(codes: &[(GainStage, u8)]) -> Vec<(GainStage, GainDb)> {
+    codes
+        .iter()
+        .map(|&(stage, code)| (stage, stage.gain_from_code(code).unwrap()))
+        .collect()
+}
+
+#[test]
+fn rx_system_gain_from_register_codes() {
+    let stages = gain_from_codes(&[
+        (GainStage::Lna, 3),
+        (GainStage::RxVga1, 120),
+        (GainStage::RxVga2, 10),
+    ]);
+    assert_eq!(
+        stages.iter().map(|(_, g)| g.db()).collect::<Vec<_>>(),
+        [6, 30, 30]
+    );
+    assert_eq!(RfLinkSession::system_gain(Channel::Rx, &stages).db(), 60);
+
+    let stages = gain_from_codes(&[
+        (GainStage::Lna, 1),
+        (GainStage::RxVga1, 14),
+        (GainStage::RxVga2, 0),
+    ]);
+    assert_eq!(RfLinkSession::system_gain(Channel::Rx, &stages).db(), 0);
+
+    let stages = gain_from_codes(&[
+        (GainStage::Lna, 1),
+        (GainStage::RxVga1, 14),
+        (GainStage::RxVga2, 10),
+    ]);
+    assert_eq!(RfLinkSession::system_gain(Channel::Rx, &stages).db(), 30);
+}
+
+#[test]
+fn tx_system_gain_from_register_codes() {
+    let stages = gain_from_codes(&[(GainStage::TxVga1, 18), (GainStage::TxVga2, 25)]);
+    assert_eq!(RfLinkSession::system_gain(Channel::Tx, &stages).db(), 60);
+    let stages = gain_from_codes(&[(GainStage::TxVga1, 0), (GainStage::TxVga2, 0)]);
+    assert_eq!(RfLinkSession::system_gain(Channel::Tx, &stages).db(), 17);
+}
+
+#[test]
+fn gain_code_roundtrip() {
+    for channel in [Channel::Rx, Channel::Tx] {
+        let range = RfLinkSession::get_gain_range(channel);
+        let (min, max) = (range.min().unwrap() as i8, range.max().unwrap() as i8);
+        for gain in min..=max {
+            let stages = RfLinkSession::gain_distribution(channel, gain.into()).unwrap();
+            let decoded: Vec<_> = stages
+                .iter()
+                .map(|&(stage, db)| (stage, stage.gain_from_code(stage.gain_code(db)).unwrap()))
+                .collect();
+            assert_eq!(decoded, stages, "{channel:?} {gain} dB");
+        }
+    }
+}
+
+#[test]
+fn invalid_lna_code() {
+    assert!(matches!(
+        GainStage::Lna.gain_from_code(0),
+        Err(Error::Argument(_))
+    ));
+}
@@ -0,0 +1,13 @@
+use libbladerf_rs::bladerf1::hardware::lms6002d::LMS_REG_DUMPSET;
+
+#[test]
+fn dumpset_covers_pll_and_gain_registers() {
+    let tx_pll = 0x10..=0x1F;
+    let rx_pll = 0x20..=0x2F;
+    for addr in tx_pll.chain(rx_pll) {
+        assert!(LMS_REG_DUMPSET.contains(&addr), "register {addr:#04x}");
+    }
+    for addr in [0x41, 0x45, 0x65, 0x75, 0x76] {
+        assert!(LMS_REG_DUMPSET.contains(&addr), "register {addr:#04x}");
+    }
+}
@@ -1,6 +1,45 @@
+mod band_select;
+mod bandwidth;
 mod bladerf1_nios_retune;
+mod builder;
+mod capability;
+mod channel;
+mod correction;
 mod dc_cal_table;
+mod endpoints;
+mod error;
+mod expansion_board;
 mod flash;
+mod fpga_source;
+mod frequency_range;
+mod fw_log;
+mod fx3_image;
+mod fx3_status;
+mod gain;
+mod lms_registers;
+mod metadata_header;
+mod module_enable;
 mod nios_packet;
+mod nios_retune2;
+mod open_retry;
+mod packet;
+mod power;
 mod range;
+mod rational_rate;
 mod sample_format;
+mod sample_rate;
+mod self_test;
+mod si5338_registers;
+mod signal_peak;
+mod stream_buffer;
+mod sweep;
+mod tone;
+mod trigger;
+mod tuning_mode;
+mod tx_gap;
+mod vctcxo_tamer;
+mod version;
+#[cfg(feature = "xb200")]
+mod xb200_filter;
+#[cfg(feature = "xb300")]
+mod xb300_amplifier;
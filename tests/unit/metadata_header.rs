@@ -0,0 +1,61 @@
+use libbladerf_rs::bladerf1::{BladeRfMetaFlags, METADATA_HEADER_SIZE, MetadataHeader};
+
+#[test]
+fn header_bytes_roundtrip() {
+    let flags = BladeRfMetaFlags::TX_BURST_START | BladeRfMetaFlags::TX_BURST_END;
+    let header = MetadataHeader::new(0, 0, 0x0123_4567_89AB_CDEF, flags.bits());
+    let bytes = header.to_bytes();
+    assert_eq!(bytes.len(), METADATA_HEADER_SIZE);
+    assert_eq!(&bytes[4..12], &0x0123_4567_89AB_CDEFu64.to_le_bytes());
+    assert_eq!(&bytes[12..16], &0x3u32.to_le_bytes());
+    let parsed = MetadataHeader::from_bytes(&bytes).unwrap();
+    assert_eq!(parsed, header);
+    assert_eq!(parsed.timestamp(), 0x0123_4567_89AB_CDEF);
+}
+
+#[test]
+fn header_from_short_slice() {
+    assert!(MetadataHeader::from_bytes(&[0u8; METADATA_HEADER_SIZE - 1]).is_none());
+}
+
+#[test]
+fn meta_flags_contains() {
+    let mut flags = BladeRfMetaFlags::empty();
+    assert!(!flags.contains(BladeRfMetaFlags::TX_BURST_START));
+    flags |= BladeRfMetaFlags::TX_BURST_START;
+    assert!(flags.contains(BladeRfMetaFlags::TX_BURST_START));
+    assert!(!flags.contains(BladeRfMetaFlags::TX_BURST_END));
+    let both = flags | BladeRfMetaFlags::TX_BURST_END;
+    assert!(both.contains(flags));
+    assert_eq!(BladeRfMetaFlags::from_bits(both.bits()), both);
+}
+
+fn message(meta_flags: u32, message_size: usize) -> Vec<u8> {
+    let mut msg = vec![0u8; message_size];
+    msg[..METADATA_HEADER_SIZE]
+        .copy_from_slice(&MetadataHeader::new(0, 0, 0, meta_flags).to_bytes());
+    msg
+}
+
+#[test]
+fn count_flagged_overruns() {
+    let underflow = BladeRfMetaFlags::RX_HW_UNDERFLOW;
+    let mut buf = Vec::new();
+    buf.extend(message(0, 2048));
+    buf.extend(message(underflow.bits(), 2048));
+    buf.extend(message(BladeRfMetaFlags::RX_HW_MINIEXP1.bits(), 2048));
+    buf.extend(message(
+        underflow.bits() | BladeRfMetaFlags::RX_HW_MINIEXP2.bits(),
+        2048,
+    ));
+    assert_eq!(MetadataHeader::count_flagged(&buf, 2048, underflow), 2);
+}
+
+#[test]
+fn count_flagged_ignores_partial_message() {
+    let underflow = BladeRfMetaFlags::RX_HW_UNDERFLOW;
+    let mut buf = message(underflow.bits(), 1024);
+    buf.extend(&message(underflow.bits(), 1024)[..512]);
+    assert_eq!(MetadataHeader::count_flagged(&buf, 1024, underflow), 1);
+    assert_eq!(MetadataHeader::count_flagged(&[], 1024, underflow), 0);
+}
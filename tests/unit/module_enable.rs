@@ -0,0 +1,46 @@
+use libbladerf_rs::bladerf1::ModuleEnableState;
+use libbladerf_rs::{Channel, Error};
+
+#[test]
+fn second_enable_skips_transfer() {
+    let mut state = ModuleEnableState::default();
+    let mut transfers = 0;
+    for _ in 0..2 {
+        state
+            .apply(Channel::Rx, true, false, || {
+                transfers += 1;
+                Ok(())
+            })
+            .unwrap();
+    }
+    assert_eq!(transfers, 1);
+    assert_eq!(state.get(Channel::Rx), Some(true));
+    assert_eq!(state.get(Channel::Tx), None);
+}
+
+#[test]
+fn force_and_state_change_issue_transfer() {
+    let mut state = ModuleEnableState::default();
+    let mut transfers = 0;
+    let mut count = || {
+        transfers += 1;
+        Ok(())
+    };
+    assert!(state.apply(Channel::Tx, true, false, &mut count).unwrap());
+    assert!(state.apply(Channel::Tx, true, true, &mut count).unwrap());
+    assert!(state.apply(Channel::Tx, false, false, &mut count).unwrap());
+    assert!(!state.apply(Channel::Tx, false, false, &mut count).unwrap());
+    assert_eq!(transfers, 3);
+}
+
+#[test]
+fn failed_transfer_forgets_state() {
+    let mut state = ModuleEnableState::default();
+    state.apply(Channel::Rx, true, false, || Ok(())).unwrap();
+    let result = state.apply(Channel::Rx, false, false, || Err(Error::Timeout));
+    assert!(matches!(result, Err(Error::Timeout)));
+    assert_eq!(state.get(Channel::Rx), None);
+
+    state.invalidate();
+    assert_eq!(state.get(Channel::Rx), None);
+}
@@ -1,4 +1,11 @@
-use libbladerf_rs::protocol::nios::{NiosPkt, NiosPktFlags};
+use libbladerf_rs::Error;
+use libbladerf_rs::bladerf1::xb_gpio;
+use libbladerf_rs::protocol::nios::packet_generic::NiosPktDecoder;
+use libbladerf_rs::protocol::nios::{
+    NiosPacketError, NiosPkt, NiosPkt8x8, NiosPkt8x32, NiosPkt8x32Target, NiosPkt32x32,
+    NiosPkt32x32Target, NiosPktFlags, nios_decode_read, nios_decode_write, nios_encode_read,
+    nios_encode_write,
+};
 
 const EXPECTED_MAGIC_8X8: u8 = 0x41;
 
@@ -109,3 +116,130 @@ fn packet32x32_new() {
     assert_eq!(addr, packet.addr());
     assert_eq!(data, packet.data());
 }
+
+#[test]
+fn expansion_gpio_data_write_encoding() {
+    let mut buf = make_buf();
+    nios_encode_write::<u32, u32>(
+        &mut buf,
+        NiosPkt32x32Target::Exp.into(),
+        0x0000_ff00,
+        0x1234_5678,
+    )
+    .unwrap();
+    assert_eq!(
+        buf,
+        [
+            0x4b, 0x00, 0x01, 0x00, 0x00, 0xff, 0x00, 0x00, 0x78, 0x56, 0x34, 0x12, 0x00, 0x00,
+            0x00, 0x00
+        ]
+    );
+}
+
+#[test]
+fn expansion_gpio_dir_write_encoding() {
+    let mut buf = make_buf();
+    let mask = xb_gpio(20).unwrap() | xb_gpio(32).unwrap();
+    nios_encode_write::<u32, u32>(&mut buf, NiosPkt32x32Target::ExpDir.into(), mask, mask).unwrap();
+    assert_eq!(
+        buf,
+        [
+            0x4b, 0x01, 0x01, 0x00, 0x00, 0x00, 0x08, 0x80, 0x00, 0x00, 0x08, 0x80, 0x00, 0x00,
+            0x00, 0x00
+        ]
+    );
+}
+
+#[test]
+fn expansion_gpio_read_encoding() {
+    for target in [NiosPkt32x32Target::Exp, NiosPkt32x32Target::ExpDir] {
+        let mut buf = make_buf();
+        nios_encode_read::<u32, u32>(&mut buf, target.into(), u32::MAX).unwrap();
+        let packet = NiosPkt::<u32, u32>::new(&mut buf).unwrap();
+        assert_eq!(packet.target(), target as u8);
+        assert_eq!(packet.flags(), NiosPktFlags::Read);
+        assert_eq!(packet.addr(), u32::MAX);
+    }
+}
+
+#[test]
+fn packet_validate() {
+    let mut buf = make_buf();
+    nios_encode_read::<u8, u32>(&mut buf, 0, 0).unwrap();
+    NiosPktDecoder::validate::<u8, u32>(&buf).expect("valid packet");
+
+    assert!(matches!(
+        NiosPktDecoder::validate::<u8, u8>(&buf),
+        Err(NiosPacketError::InvalidMagic(EXPECTED_MAGIC_8X8, 0x43))
+    ));
+    assert!(matches!(
+        NiosPktDecoder::validate::<u8, u32>(&[0x43; 20]),
+        Err(NiosPacketError::InvalidSize(20))
+    ));
+    assert!(matches!(
+        NiosPktDecoder::validate::<u16, u16>(&buf),
+        Err(NiosPacketError::InvalidTypeCombination)
+    ));
+}
+
+#[test]
+fn packet8x32_control_read_layout() {
+    let mut buf = make_buf();
+    let packet = NiosPkt8x32::read(&mut buf, NiosPkt8x32Target::Control, 0).unwrap();
+    assert_eq!(packet.target(), NiosPkt8x32Target::Control as u8);
+    assert_eq!(packet.flags(), NiosPktFlags::Read);
+    let expected: [u8; 16] = [0x43, 0x01, 0x00, 0x00, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    assert_eq!(buf, expected);
+}
+
+#[test]
+fn packet32x32_wishbone_write_layout() {
+    let mut buf = make_buf();
+    NiosPkt32x32::write(
+        &mut buf,
+        NiosPkt32x32Target::WbMstr,
+        0x1234_5678,
+        0xdead_beef,
+    )
+    .unwrap();
+    assert_eq!(buf[0], 0x4b);
+    assert_eq!(buf[1], NiosPkt32x32Target::WbMstr as u8);
+    assert_eq!(buf[2], NiosPktFlags::Write as u8);
+    assert_eq!(&buf[4..8], &0x1234_5678u32.to_le_bytes());
+    assert_eq!(&buf[8..12], &0xdead_beefu32.to_le_bytes());
+}
+
+#[test]
+fn packet_response_data() {
+    let mut buf = make_buf();
+    NiosPkt8x32::write(&mut buf, NiosPkt8x32Target::Control, 0, 0x0000_0057).unwrap();
+    assert_eq!(NiosPkt8x32::response_data(&buf).unwrap(), 0x57);
+    assert!(matches!(
+        NiosPkt8x8::response_data(&buf),
+        Err(Error::NiosPacket(NiosPacketError::InvalidMagic(
+            EXPECTED_MAGIC_8X8,
+            0x43
+        )))
+    ));
+}
+
+#[test]
+fn truncated_response() {
+    let buf = [0x43u8; 10];
+    let err = nios_decode_read::<u8, u32>(&buf).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::TransferTruncated {
+            actual: 10,
+            expected: 16
+        }
+    ));
+    assert_eq!(err.to_string(), "transfer truncated: got 10 of 16 bytes");
+    assert!(matches!(
+        nios_decode_write::<u8, u32>(&buf),
+        Err(Error::TransferTruncated {
+            actual: 10,
+            expected: 16
+        })
+    ));
+}
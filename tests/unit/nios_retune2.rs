@@ -0,0 +1,71 @@
+use libbladerf_rs::Channel;
+use libbladerf_rs::Error;
+use libbladerf_rs::protocol::nios::{
+    NIOS_PKT_RETUNE2_MAGIC, NiosPacketError, NiosPktRetune2Request, NiosPktRetune2Response,
+};
+
+#[test]
+fn packet_retune2_request_roundtrip() {
+    let mut buf = [0u8; 16];
+    let mut pkt = NiosPktRetune2Request::new(&mut buf).expect("valid packet");
+    pkt.prepare(Channel::Rx, 0x0123_4567_89ab_cdef, 0x1234, 5, 0x02, 0xa5);
+
+    assert_eq!(pkt.timestamp(), 0x0123_4567_89ab_cdef);
+    assert_eq!(pkt.nios_profile(), 0x1234);
+    assert_eq!(pkt.rffe_profile(), 5);
+    assert_eq!(pkt.port(), 0x02);
+    assert_eq!(pkt.channel(), Channel::Rx);
+    assert_eq!(pkt.spdt(), 0xa5);
+}
+
+#[test]
+fn packet_retune2_request_layout() {
+    let mut buf = [0u8; 16];
+    NiosPktRetune2Request::new(&mut buf)
+        .expect("valid packet")
+        .prepare(Channel::Tx, u64::MAX, 0x0201, 3, 0x81, 0x0f);
+
+    let expected: [u8; 16] = [
+        0x55, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 0x02, 0x03, 0x01, 0x0f, 0xff,
+        0xff,
+    ];
+    assert_eq!(buf, expected);
+    assert_eq!(buf[0], NIOS_PKT_RETUNE2_MAGIC);
+}
+
+#[test]
+fn packet_retune2_request_short_buffer() {
+    let mut buf = [0u8; 15];
+    assert!(matches!(
+        NiosPktRetune2Request::new(&mut buf),
+        Err(Error::NiosPacket(NiosPacketError::InvalidSize(15)))
+    ));
+}
+
+#[test]
+fn packet_retune2_response() {
+    let mut buf = [0u8; 16];
+    buf[0] = NIOS_PKT_RETUNE2_MAGIC;
+    buf[1..9].copy_from_slice(&1234u64.to_le_bytes());
+    buf[9] = 0x3;
+    NiosPktRetune2Response::validate(&buf).expect("valid response");
+    let resp = NiosPktRetune2Response::new(&buf).expect("valid response");
+    assert_eq!(resp.duration(), 1234);
+    assert!(resp.duration_valid());
+    assert!(resp.is_success());
+
+    buf[9] = 0x0;
+    let resp = NiosPktRetune2Response::new(&buf).expect("valid response");
+    assert!(!resp.duration_valid());
+    assert!(!resp.is_success());
+
+    buf[0] = 0x54;
+    assert!(matches!(
+        NiosPktRetune2Response::new(&buf),
+        Err(Error::NiosPacket(NiosPacketError::InvalidMagic(0x55, 0x54)))
+    ));
+    assert!(matches!(
+        NiosPktRetune2Response::validate(&[0x55; 17]),
+        Err(NiosPacketError::InvalidSize(17))
+    ));
+}
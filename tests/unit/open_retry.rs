@@ -0,0 +1,64 @@
+use libbladerf_rs::Error;
+use libbladerf_rs::error::retry_transient;
+use nusb::transfer::TransferError;
+use std::time::{Duration, Instant};
+
+#[test]
+fn transient_errors() {
+    assert!(Error::Disconnected.is_transient());
+    assert!(Error::Timeout.is_transient());
+    assert!(!Error::NotFound.is_transient());
+    assert!(!Error::UnsupportedSpeed.is_transient());
+    assert!(!Error::Argument("x".into()).is_transient());
+    assert!(!Error::from(TransferError::Stall).is_transient());
+}
+
+#[test]
+fn retries_transient_errors_until_success() {
+    let mut calls = 0;
+    let result = retry_transient(5, Duration::ZERO, || {
+        calls += 1;
+        if calls < 3 {
+            Err(Error::Timeout)
+        } else {
+            Ok(calls)
+        }
+    });
+    assert_eq!(result.unwrap(), 3);
+    assert_eq!(calls, 3);
+}
+
+#[test]
+fn gives_up_after_attempts() {
+    let mut calls = 0;
+    let result: libbladerf_rs::Result<()> = retry_transient(4, Duration::ZERO, || {
+        calls += 1;
+        Err(Error::Disconnected)
+    });
+    assert!(matches!(result, Err(Error::Disconnected)));
+    assert_eq!(calls, 4);
+}
+
+#[test]
+fn permanent_error_returns_promptly() {
+    let mut calls = 0;
+    let start = Instant::now();
+    let result: libbladerf_rs::Result<()> = retry_transient(10, Duration::from_secs(1), || {
+        calls += 1;
+        Err(Error::NotFound)
+    });
+    assert!(matches!(result, Err(Error::NotFound)));
+    assert_eq!(calls, 1);
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn zero_attempts_runs_once() {
+    let mut calls = 0;
+    let result: libbladerf_rs::Result<()> = retry_transient(0, Duration::ZERO, || {
+        calls += 1;
+        Err(Error::Timeout)
+    });
+    assert!(matches!(result, Err(Error::Timeout)));
+    assert_eq!(calls, 1);
+}
@@ -0,0 +1,78 @@
+use libbladerf_rs::Error;
+use libbladerf_rs::bladerf1::{METADATA_HEADER_SIZE, MetadataHeader, Packet};
+
+fn packet() -> Packet {
+    Packet {
+        core_id: 0x12,
+        flags: 0x34,
+        timestamp: 0x0001_0203_0405,
+        payload: vec![0xDEAD_BEEF, 0x0102_0304],
+    }
+}
+
+#[test]
+fn header_layout() {
+    let bytes = packet().to_bytes().unwrap();
+    assert_eq!(bytes.len(), METADATA_HEADER_SIZE + 8);
+    assert_eq!(&bytes[0..2], &2u16.to_le_bytes());
+    assert_eq!(&bytes[2..4], &0x1234u16.to_le_bytes());
+    assert_eq!(&bytes[4..12], &0x0001_0203_0405u64.to_le_bytes());
+    assert_eq!(&bytes[12..16], &[0; 4]);
+    assert_eq!(&bytes[16..20], &0xDEAD_BEEFu32.to_le_bytes());
+
+    let header = MetadataHeader::from_bytes(&bytes).unwrap();
+    assert_eq!(header.packet_length(), 2);
+    assert_eq!(header.packet_core_id(), 0x12);
+    assert_eq!(header.packet_flags(), 0x34);
+    assert_eq!(
+        header,
+        MetadataHeader::packet(2, 0x12, 0x34, 0x0001_0203_0405)
+    );
+}
+
+#[test]
+fn roundtrip() {
+    let bytes = packet().to_bytes().unwrap();
+    assert_eq!(Packet::from_bytes(&bytes).unwrap(), packet());
+}
+
+#[test]
+fn trailing_words_are_ignored() {
+    let mut bytes = packet().to_bytes().unwrap();
+    bytes.extend_from_slice(&[0xFF; 8]);
+    assert_eq!(Packet::from_bytes(&bytes).unwrap(), packet());
+}
+
+#[test]
+fn empty_payload() {
+    let empty = Packet::default();
+    let bytes = empty.to_bytes().unwrap();
+    assert_eq!(bytes.len(), METADATA_HEADER_SIZE);
+    assert_eq!(Packet::from_bytes(&bytes).unwrap(), empty);
+}
+
+#[test]
+fn rejects_malformed() {
+    let bytes = packet().to_bytes().unwrap();
+    assert!(matches!(
+        Packet::from_bytes(&bytes[..METADATA_HEADER_SIZE - 1]),
+        Err(Error::Argument(_))
+    ));
+    assert!(matches!(
+        Packet::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(Error::Argument(_))
+    ));
+    assert!(matches!(
+        Packet::from_bytes(&bytes[..bytes.len() - 4]),
+        Err(Error::Argument(_))
+    ));
+}
+
+#[test]
+fn rejects_oversized_payload() {
+    let oversized = Packet {
+        payload: vec![0; u16::MAX as usize + 1],
+        ..Packet::default()
+    };
+    assert!(matches!(oversized.to_bytes(), Err(Error::Argument(_))));
+}
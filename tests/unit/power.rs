@@ -0,0 +1,69 @@
+use libbladerf_rs::bladerf1::{POWER_FULL_SCALE, SampleFormat, power_dbfs, tone_samples};
+use num_complex::Complex;
+
+fn complex(bytes: &[u8]) -> Vec<Complex<f32>> {
+    let mut samples = vec![Complex::default(); bytes.len() / 4];
+    SampleFormat::Sc16Q11
+        .to_complex_f32(bytes, &mut samples)
+        .unwrap();
+    samples
+}
+
+#[test]
+fn full_scale_tone_is_near_zero_dbfs() {
+    let tone = complex(&tone_samples(31_250.0, 1.0, 2_000_000, 0, 4096).unwrap());
+    let power = power_dbfs(&tone);
+    assert!(power.abs() < 0.05, "{power} dBFS");
+}
+
+#[test]
+fn half_amplitude_is_six_db_down() {
+    let tone = complex(&tone_samples(-50_000.0, 0.5, 2_000_000, 0, 4096).unwrap());
+    let power = power_dbfs(&tone);
+    assert!((power + 6.02).abs() < 0.05, "{power} dBFS");
+}
+
+#[test]
+fn full_scale_reference_is_2048_counts() {
+    let bytes: Vec<u8> = [2048i16, 0, -2048, 0, 0, 2048, 0, -2048]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+    let samples = complex(&bytes);
+    assert!(
+        samples
+            .iter()
+            .all(|s| s.norm() * 2048.0 == POWER_FULL_SCALE)
+    );
+    assert_eq!(power_dbfs(&samples), 0.0);
+    assert_eq!(power_dbfs(&[]), f32::NEG_INFINITY);
+    assert_eq!(power_dbfs(&[Complex::default(); 8]), f32::NEG_INFINITY);
+}
+
+#[cfg(feature = "dsp")]
+mod psd {
+    use super::complex;
+    use libbladerf_rs::Error;
+    use libbladerf_rs::bladerf1::{psd_dbfs, tone_samples};
+
+    #[test]
+    fn tone_lands_in_its_bin() {
+        let tone = complex(&tone_samples(62_500.0, 1.0, 2_000_000, 0, 1024).unwrap());
+        let psd = psd_dbfs(&tone, 256).unwrap();
+        assert_eq!(psd.len(), 256);
+        let peak = (0..psd.len())
+            .max_by(|&a, &b| psd[a].total_cmp(&psd[b]))
+            .unwrap();
+        assert_eq!(peak, 136);
+        assert!(psd[peak].abs() < 0.1, "{} dBFS", psd[peak]);
+        assert!(psd[100] < -60.0);
+    }
+
+    #[test]
+    fn rejects_bad_fft_size() {
+        let tone = complex(&tone_samples(0.0, 1.0, 2_000_000, 0, 100).unwrap());
+        for size in [0, 1, 48, 128] {
+            assert!(matches!(psd_dbfs(&tone, size), Err(Error::Argument(_))));
+        }
+    }
+}
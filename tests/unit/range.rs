@@ -1,3 +1,4 @@
+use libbladerf_rs::Error;
 use libbladerf_rs::range::{Range, RangeItem};
 
 #[test]
@@ -72,3 +73,84 @@ fn at_max() {
     assert_eq!(r.at_max(100.3), Some(100.0));
     assert_eq!(r.at_max(111.3), Some(110.0));
 }
+
+#[test]
+fn snap_within_item() {
+    let r = Range::new(vec![
+        RangeItem::Interval(23.0, 42.0),
+        RangeItem::Step(100.0, 110.0, 2.0, 1.0),
+    ]);
+    assert_eq!(r.snap(30.5), 30.5);
+    assert_eq!(r.snap(104.0), 104.0);
+    assert_eq!(r.snap(104.9), 104.0);
+    assert_eq!(r.snap(105.1), 106.0);
+}
+
+#[test]
+fn snap_between_items() {
+    let r = Range::new(vec![
+        RangeItem::Interval(23.0, 42.0),
+        RangeItem::Step(100.0, 110.0, 2.0, 1.0),
+    ]);
+    assert_eq!(r.snap(60.0), 42.0);
+    assert_eq!(r.snap(80.0), 100.0);
+    assert!(r.contains(r.snap(80.0)));
+}
+
+#[test]
+fn snap_outside_items() {
+    let r = Range::new(vec![
+        RangeItem::Interval(23.0, 42.0),
+        RangeItem::Step(100.0, 109.0, 2.0, 1.0),
+    ]);
+    assert_eq!(r.snap(0.0), 23.0);
+    assert_eq!(r.snap(1_000.0), 108.0);
+    assert!(r.contains(r.snap(1_000.0)));
+}
+
+#[test]
+fn snap_empty() {
+    let r = Range::new(Vec::new());
+    assert_eq!(r.snap(5.0), 5.0);
+}
+
+#[test]
+fn display_items() {
+    assert_eq!(RangeItem::Interval(1.5, 28.0).to_string(), "[1.5 .. 28]");
+    assert_eq!(RangeItem::Value(-3.0).to_string(), "-3");
+    assert_eq!(
+        RangeItem::Step(0.0, 30.0, 3.0, 1.0).to_string(),
+        "[0 .. 30] step 3 (scale 1)"
+    );
+}
+
+#[test]
+fn display_multi_item_range() {
+    let r = Range::new(vec![
+        RangeItem::Step(5.0, 30.0, 1.0, 1.0),
+        RangeItem::Value(0.0),
+        RangeItem::Interval(40.0, 60.5),
+    ]);
+    assert_eq!(r.to_string(), "[5 .. 30] step 1 (scale 1), 0, [40 .. 60.5]");
+    assert_eq!(Range::new(Vec::new()).to_string(), "[]");
+}
+
+#[test]
+fn check_bounds() {
+    let range = Range::new(vec![
+        RangeItem::Interval(1.0, 2.0),
+        RangeItem::Step(4.0, 8.0, 2.0, 1.0),
+    ]);
+    assert!(range.check(1.0, "value").is_ok());
+    assert!(range.check(3.0, "value").is_ok());
+    assert!(range.check(8.0, "value").is_ok());
+    assert!(matches!(
+        range.check(0.5, "value"),
+        Err(Error::Argument(msg)) if msg == "value 0.5 outside supported range 1..=8"
+    ));
+    assert!(matches!(range.check(8.5, "value"), Err(Error::Argument(_))));
+    assert!(matches!(
+        Range::new(vec![]).check(0.0, "value"),
+        Err(Error::BoardState(_))
+    ));
+}
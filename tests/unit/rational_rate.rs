@@ -0,0 +1,79 @@
+use libbladerf_rs::bladerf1::RationalRate;
+use libbladerf_rs::bladerf1::hardware::si5338::{
+    BLADERF_SAMPLERATE_MIN, BLADERF_SAMPLERATE_REC_MAX, sample_rate_multisynth_regs,
+};
+use libbladerf_rs::{Channel, Error};
+
+#[test]
+fn round_integer_rate() {
+    assert_eq!(RationalRate::new(2_000_000, 0, 1).round(), 2_000_000);
+}
+
+#[test]
+fn round_fractional_rate() {
+    assert_eq!(RationalRate::new(1_999_999, 2, 3).round(), 2_000_000);
+    assert_eq!(RationalRate::new(2_000_000, 1, 3).round(), 2_000_000);
+    assert_eq!(RationalRate::new(2_000_000, 1, 2).round(), 2_000_001);
+}
+
+#[test]
+fn round_zero_denominator() {
+    assert_eq!(RationalRate::new(2_000_000, 0, 0).round(), 2_000_000);
+}
+
+#[test]
+fn multisynth_regs_integer_rate() {
+    let rate = RationalRate::new(30_720_000, 0, 1);
+    let (regs, actual) = sample_rate_multisynth_regs(Channel::Rx, &rate).unwrap();
+    assert_eq!(
+        regs,
+        [0xa0, 0x12, 0x00, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00]
+    );
+    assert_eq!(actual, RationalRate::new(30_720_000, 0, 1));
+}
+
+#[test]
+fn multisynth_regs_fractional_rate() {
+    let rate = RationalRate::new(1_000_000, 1, 3);
+    let (regs, actual) = sample_rate_multisynth_regs(Channel::Tx, &rate).unwrap();
+    assert_eq!(
+        regs,
+        [0x66, 0x9c, 0x68, 0xc4, 0x46, 0x00, 0xc1, 0xc6, 0x2d, 0x00]
+    );
+    assert_eq!(actual, RationalRate::new(1_000_000, 1, 3));
+}
+
+#[test]
+fn multisynth_regs_accept_range_bounds() {
+    for rate in [
+        RationalRate::new(BLADERF_SAMPLERATE_MIN as u64, 0, 1),
+        RationalRate::new(BLADERF_SAMPLERATE_REC_MAX as u64, 0, 1),
+        RationalRate::new(BLADERF_SAMPLERATE_REC_MAX as u64 - 1, 999, 1_000),
+    ] {
+        assert!(
+            sample_rate_multisynth_regs(Channel::Rx, &rate).is_ok(),
+            "{rate:?}"
+        );
+    }
+}
+
+#[test]
+fn multisynth_regs_reject_invalid_rates() {
+    for rate in [
+        RationalRate::new(2_000_000, 0, 0),
+        RationalRate::new(BLADERF_SAMPLERATE_MIN as u64 - 1, 0, 1),
+        RationalRate::new(BLADERF_SAMPLERATE_MIN as u64 - 1, 999, 1_000),
+        RationalRate::new(BLADERF_SAMPLERATE_REC_MAX as u64, 1, 1_000),
+        RationalRate::new(BLADERF_SAMPLERATE_REC_MAX as u64 + 1, 0, 1),
+        RationalRate::new(u64::MAX, 0, 1),
+        RationalRate::new(1_000_000, 1, u64::MAX),
+    ] {
+        assert!(
+            matches!(
+                sample_rate_multisynth_regs(Channel::Rx, &rate),
+                Err(Error::InvalidSampleRate(_))
+            ),
+            "{rate:?}"
+        );
+    }
+}
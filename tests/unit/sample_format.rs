@@ -1,4 +1,7 @@
-use libbladerf_rs::bladerf1::SampleFormat;
+use libbladerf_rs::bladerf1::{METADATA_HEADER_SIZE, RfLinkSession, SampleFormat, StreamFormats};
+use libbladerf_rs::{Channel, Error};
+use num_complex::Complex;
+use nusb::Speed;
 
 fn pack_i16(value: i16) -> [u8; 2] {
     value.to_le_bytes()
@@ -42,3 +45,293 @@ fn pack_unpack_roundtrip_negative() {
         assert_eq!(got, orig, "Sample {i}: expected {orig}, got {got}");
     }
 }
+
+#[test]
+fn deinterleave_sc16q11() {
+    let mut src = Vec::new();
+    for (i, q) in [(1i16, -1i16), (2_047, -2_048), (-300, 300)] {
+        src.extend_from_slice(&i.to_le_bytes());
+        src.extend_from_slice(&q.to_le_bytes());
+    }
+    let mut i_out = [0i16; 3];
+    let mut q_out = [0i16; 3];
+    let n = SampleFormat::Sc16Q11
+        .deinterleave(&src, &mut i_out, &mut q_out)
+        .unwrap();
+    assert_eq!(n, 3);
+    assert_eq!(i_out, [1, 2_047, -300]);
+    assert_eq!(q_out, [-1, -2_048, 300]);
+
+    let mut dst = vec![0u8; src.len()];
+    SampleFormat::Sc16Q11
+        .interleave(&i_out, &q_out, &mut dst)
+        .unwrap();
+    assert_eq!(dst, src);
+}
+
+#[test]
+fn deinterleave_skips_metadata_header() {
+    let mut src = vec![0xAAu8; METADATA_HEADER_SIZE];
+    src.extend_from_slice(&[5, 0xFB, 0x7F, 0x80]);
+    let mut i_out = [0i16; 2];
+    let mut q_out = [0i16; 2];
+    let n = SampleFormat::Sc8Q7Meta
+        .deinterleave(&src, &mut i_out, &mut q_out)
+        .unwrap();
+    assert_eq!(n, 2);
+    assert_eq!(i_out, [5, 127]);
+    assert_eq!(q_out, [-5, -128]);
+
+    let mut dst = vec![0xAAu8; src.len()];
+    SampleFormat::Sc8Q7Meta
+        .interleave(&[5, 1_000], &[-5, -1_000], &mut dst)
+        .unwrap();
+    assert_eq!(dst, src);
+}
+
+#[test]
+fn deinterleave_rejects_bad_buffers() {
+    let mut i_out = [0i16; 1];
+    let mut q_out = [0i16; 1];
+    assert!(
+        SampleFormat::Sc16Q11
+            .deinterleave(&[0; 8], &mut i_out, &mut q_out)
+            .is_err()
+    );
+    assert!(
+        SampleFormat::Sc16Q11Meta
+            .deinterleave(&[0; 8], &mut i_out, &mut q_out)
+            .is_err()
+    );
+    assert!(
+        SampleFormat::Sc16Q11Packed
+            .deinterleave(&[0; 6], &mut i_out, &mut q_out)
+            .is_err()
+    );
+}
+
+#[test]
+fn complex_i16_matches_scaled_f32() {
+    let mut src = Vec::new();
+    for (i, q) in [(1i16, -1i16), (2_047, -2_048), (-300, 300), (0, 1_024)] {
+        src.extend_from_slice(&i.to_le_bytes());
+        src.extend_from_slice(&q.to_le_bytes());
+    }
+    let mut raw = [Complex::new(0i16, 0); 4];
+    let mut scaled = [Complex::new(0f32, 0.0); 4];
+    assert_eq!(
+        SampleFormat::Sc16Q11
+            .to_complex_i16(&src, &mut raw)
+            .unwrap(),
+        4
+    );
+    assert_eq!(
+        SampleFormat::Sc16Q11
+            .to_complex_f32(&src, &mut scaled)
+            .unwrap(),
+        4
+    );
+
+    assert_eq!(raw[1], Complex::new(2_047, -2_048));
+    assert_eq!(scaled[3], Complex::new(0.0, 0.5));
+    for (r, f) in raw.iter().zip(&scaled) {
+        assert_eq!(r.re as f32 / 2048.0, f.re);
+        assert_eq!(r.im as f32 / 2048.0, f.im);
+        assert_eq!((f.re * 2048.0) as i16, r.re);
+        assert_eq!((f.im * 2048.0) as i16, r.im);
+    }
+}
+
+#[test]
+fn complex_paths_skip_metadata_header() {
+    let mut src = vec![0xAAu8; METADATA_HEADER_SIZE];
+    src.extend_from_slice(&[5, 0xFB, 0x7F, 0x80]);
+    let mut raw = [Complex::new(0i16, 0); 2];
+    let mut scaled = [Complex::new(0f32, 0.0); 2];
+    SampleFormat::Sc8Q7Meta
+        .to_complex_i16(&src, &mut raw)
+        .unwrap();
+    SampleFormat::Sc8Q7Meta
+        .to_complex_f32(&src, &mut scaled)
+        .unwrap();
+    assert_eq!(raw, [Complex::new(5, -5), Complex::new(127, -128)]);
+    assert_eq!(scaled[1], Complex::new(127.0 / 128.0, -1.0));
+}
+
+#[test]
+fn complex_paths_reject_bad_buffers() {
+    let mut raw = [Complex::new(0i16, 0); 1];
+    assert!(
+        SampleFormat::Sc16Q11
+            .to_complex_i16(&[0; 8], &mut raw)
+            .is_err()
+    );
+    assert!(
+        SampleFormat::Sc16Q11
+            .to_complex_i16(&[0; 6], &mut raw)
+            .is_err()
+    );
+    assert!(
+        SampleFormat::PacketMeta
+            .to_complex_i16(&[0; 4], &mut raw)
+            .is_err()
+    );
+}
+
+#[test]
+fn requires_timestamps_for_meta_formats() {
+    assert!(SampleFormat::Sc16Q11Meta.requires_timestamps());
+    assert!(SampleFormat::Sc8Q7Meta.requires_timestamps());
+    assert!(SampleFormat::PacketMeta.requires_timestamps());
+    assert!(!SampleFormat::Sc16Q11.requires_timestamps());
+    assert!(!SampleFormat::Sc8Q7.requires_timestamps());
+    assert!(!SampleFormat::Sc16Q11Packed.requires_timestamps());
+}
+
+#[test]
+fn format_conflict_between_directions() {
+    for (channel, format, other) in [
+        (
+            Channel::Rx,
+            SampleFormat::Sc16Q11,
+            SampleFormat::Sc16Q11Meta,
+        ),
+        (
+            Channel::Tx,
+            SampleFormat::Sc16Q11Meta,
+            SampleFormat::Sc16Q11,
+        ),
+    ] {
+        let err = RfLinkSession::check_format_conflict(channel, format, Some(other)).unwrap_err();
+        assert!(matches!(err, Error::Argument(_)), "{err:?}");
+    }
+}
+
+#[test]
+fn stopped_stream_reclaims_its_format_on_start() {
+    let mut formats = StreamFormats::default();
+    formats
+        .claim(Channel::Rx, SampleFormat::Sc16Q11Meta)
+        .unwrap();
+    let err = formats
+        .claim(Channel::Tx, SampleFormat::Sc16Q11)
+        .unwrap_err();
+    assert!(matches!(err, Error::Argument(_)), "{err:?}");
+
+    assert_eq!(formats.release(Channel::Rx), None);
+    formats.claim(Channel::Tx, SampleFormat::Sc16Q11).unwrap();
+    let err = formats
+        .claim(Channel::Rx, SampleFormat::Sc16Q11Meta)
+        .unwrap_err();
+    assert!(matches!(err, Error::Argument(_)), "{err:?}");
+    assert_eq!(formats.get(Channel::Rx), None);
+
+    assert_eq!(formats.release(Channel::Tx), None);
+    formats
+        .claim(Channel::Rx, SampleFormat::Sc16Q11Meta)
+        .unwrap();
+    formats
+        .claim(Channel::Rx, SampleFormat::Sc16Q11Meta)
+        .unwrap();
+    assert_eq!(formats.get(Channel::Rx), Some(SampleFormat::Sc16Q11Meta));
+}
+
+#[test]
+fn release_leaves_the_other_direction_claimed() {
+    let mut formats = StreamFormats::default();
+    formats.claim(Channel::Rx, SampleFormat::Sc16Q11).unwrap();
+    formats.claim(Channel::Tx, SampleFormat::Sc8Q7).unwrap();
+    assert_eq!(formats.release(Channel::Tx), Some(SampleFormat::Sc16Q11));
+    assert_eq!(formats.get(Channel::Tx), None);
+    assert_eq!(formats.get(Channel::Rx), Some(SampleFormat::Sc16Q11));
+}
+
+#[test]
+fn format_without_conflict() {
+    let check = RfLinkSession::check_format_conflict;
+    assert!(check(Channel::Rx, SampleFormat::Sc16Q11Meta, None).is_ok());
+    assert!(check(Channel::Rx, SampleFormat::Sc16Q11, None).is_ok());
+    assert!(
+        check(
+            Channel::Tx,
+            SampleFormat::Sc16Q11,
+            Some(SampleFormat::Sc16Q11)
+        )
+        .is_ok()
+    );
+    assert!(
+        check(
+            Channel::Tx,
+            SampleFormat::Sc8Q7Meta,
+            Some(SampleFormat::Sc16Q11Meta)
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn buffer_size_bytes_plain_formats() {
+    let size = |format: SampleFormat| format.buffer_size_bytes(2048, 1, Speed::High).unwrap();
+    assert_eq!(size(SampleFormat::Sc16Q11), 8192);
+    assert_eq!(size(SampleFormat::Sc8Q7), 4096);
+    assert_eq!(size(SampleFormat::Sc16Q11Packed), 6144);
+    assert_eq!(
+        SampleFormat::Sc16Q11
+            .buffer_size_bytes(2048, 2, Speed::Super)
+            .unwrap(),
+        16384
+    );
+}
+
+#[test]
+fn buffer_size_bytes_meta_formats() {
+    let meta = SampleFormat::Sc16Q11Meta;
+    assert_eq!(meta.buffer_size_bytes(508, 1, Speed::Super).unwrap(), 2048);
+    assert_eq!(meta.buffer_size_bytes(509, 1, Speed::Super).unwrap(), 4096);
+    assert_eq!(
+        meta.buffer_size_bytes(2048, 1, Speed::Super).unwrap(),
+        5 * 2048
+    );
+    assert_eq!(meta.buffer_size_bytes(252, 1, Speed::High).unwrap(), 1024);
+    assert_eq!(
+        SampleFormat::Sc8Q7Meta
+            .buffer_size_bytes(1016, 1, Speed::Super)
+            .unwrap(),
+        2048
+    );
+    assert!(meta.buffer_size_bytes(1, 1, Speed::Full).is_err());
+}
+
+#[test]
+fn samples_per_buffer_inverts_buffer_size() {
+    assert_eq!(
+        SampleFormat::Sc16Q11
+            .samples_per_buffer(8192, Speed::High)
+            .unwrap(),
+        2048
+    );
+    assert_eq!(
+        SampleFormat::Sc8Q7
+            .samples_per_buffer(8192, Speed::High)
+            .unwrap(),
+        4096
+    );
+    let meta = SampleFormat::Sc16Q11Meta;
+    assert_eq!(
+        meta.samples_per_buffer(4 * 2048, Speed::Super).unwrap(),
+        4 * 508
+    );
+    assert_eq!(
+        meta.samples_per_buffer(2048 + 16 + 40, Speed::Super)
+            .unwrap(),
+        518
+    );
+    for format in [
+        SampleFormat::Sc16Q11,
+        SampleFormat::Sc16Q11Meta,
+        SampleFormat::Sc8Q7Meta,
+    ] {
+        let bytes = format.buffer_size_bytes(4096, 1, Speed::Super).unwrap();
+        assert!(format.samples_per_buffer(bytes, Speed::Super).unwrap() >= 4096);
+    }
+}
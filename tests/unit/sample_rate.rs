@@ -0,0 +1,47 @@
+use libbladerf_rs::bladerf1::RationalRate;
+use libbladerf_rs::bladerf1::RfLinkSession;
+use libbladerf_rs::bladerf1::hardware::si5338::{
+    BLADERF_SAMPLERATE_MIN, BLADERF_SAMPLERATE_REC_MAX, sample_rate_multisynth_regs,
+};
+use libbladerf_rs::{Channel, Error};
+
+fn as_f64(rate: &RationalRate) -> f64 {
+    rate.integer() as f64 + rate.numerator() as f64 / rate.denominator() as f64
+}
+
+#[test]
+fn check_sample_rate_bounds() {
+    RfLinkSession::check_sample_rate(BLADERF_SAMPLERATE_MIN).unwrap();
+    RfLinkSession::check_sample_rate(BLADERF_SAMPLERATE_REC_MAX).unwrap();
+    RfLinkSession::check_sample_rate(2_000_000).unwrap();
+    for rate in [
+        0,
+        BLADERF_SAMPLERATE_MIN - 1,
+        BLADERF_SAMPLERATE_REC_MAX + 1,
+    ] {
+        assert!(matches!(
+            RfLinkSession::check_sample_rate(rate),
+            Err(Error::Argument(_))
+        ));
+    }
+}
+
+#[test]
+fn integer_rates_are_exact() {
+    for rate in [
+        80_000, 520_834, 1_000_001, 7_777_777, 30_720_000, 40_000_000,
+    ] {
+        let req = RationalRate::new(rate, 0, 1);
+        let (_, actual) = sample_rate_multisynth_regs(Channel::Rx, &req).unwrap();
+        assert_eq!(actual, req, "rate {rate}");
+    }
+}
+
+#[test]
+fn unreachable_rate_is_close() {
+    let req = RationalRate::new(1_000_000, 123_456_789, 987_654_321);
+    let (_, actual) = sample_rate_multisynth_regs(Channel::Rx, &req).unwrap();
+    assert_ne!(actual, req);
+    assert_eq!(actual.integer(), 1_000_000);
+    assert!((as_f64(&actual) - as_f64(&req)).abs() < 0.01);
+}
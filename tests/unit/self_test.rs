@@ -0,0 +1,74 @@
+use libbladerf_rs::bladerf1::hardware::lms6002d::loopback::Loopback;
+use libbladerf_rs::bladerf1::{
+    SELF_TEST_MIN_CORRELATION, SELF_TEST_TONE_PERIOD, SelfTestReport, SelfTestResult,
+    ToneMeasurement, self_test_tone,
+};
+
+fn conjugate(samples: &[u8]) -> Vec<u8> {
+    samples
+        .chunks_exact(4)
+        .flat_map(|s| {
+            let q = -i16::from_le_bytes([s[2], s[3]]);
+            let [q0, q1] = q.to_le_bytes();
+            [s[0], s[1], q0, q1]
+        })
+        .collect()
+}
+
+#[test]
+fn tone_matches_itself() {
+    let tone = self_test_tone(2048);
+    assert_eq!(tone.len(), 2048 * 4);
+    let m = ToneMeasurement::measure(&tone, SELF_TEST_TONE_PERIOD);
+    assert!(m.correlation > 0.999, "{m:?}");
+    assert!(m.snr_db > 40.0, "{m:?}");
+}
+
+#[test]
+fn tone_survives_phase_shift_and_inversion() {
+    let tone = self_test_tone(2048 + 17);
+    let shifted = &tone[17 * 4..];
+    let m = ToneMeasurement::measure(shifted, SELF_TEST_TONE_PERIOD);
+    assert!(m.correlation > 0.999, "{m:?}");
+
+    let m = ToneMeasurement::measure(&conjugate(&tone), SELF_TEST_TONE_PERIOD);
+    assert!(m.correlation > 0.999, "{m:?}");
+}
+
+#[test]
+fn silence_and_wrong_tone_fail() {
+    let m = ToneMeasurement::measure(&[0u8; 4096], SELF_TEST_TONE_PERIOD);
+    assert_eq!(m.correlation, 0.0);
+    assert_eq!(m.snr_db, f64::NEG_INFINITY);
+
+    let m = ToneMeasurement::measure(&self_test_tone(2048), SELF_TEST_TONE_PERIOD / 2);
+    assert!(m.correlation < SELF_TEST_MIN_CORRELATION, "{m:?}");
+    assert!(m.snr_db < 0.0, "{m:?}");
+}
+
+#[test]
+fn report_passes_only_if_every_leg_passes() {
+    let measurement = ToneMeasurement {
+        correlation: 1.0,
+        snr_db: 50.0,
+    };
+    let mut report = SelfTestReport {
+        results: vec![
+            SelfTestResult {
+                mode: Loopback::Firmware,
+                measurement,
+                passed: true,
+            },
+            SelfTestResult {
+                mode: Loopback::BbTxlpfRxlpf,
+                measurement,
+                passed: true,
+            },
+        ],
+    };
+    assert!(report.passed());
+    report.results[1].passed = false;
+    assert!(!report.passed());
+    assert!(report.result(Loopback::Firmware).unwrap().passed);
+    assert!(report.result(Loopback::Lna3).is_none());
+}
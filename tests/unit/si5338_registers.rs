@@ -0,0 +1,40 @@
+use libbladerf_rs::bladerf1::hardware::si5338::{
+    SI5338_REG_DUMPSET, SI5338_VOLATILE_REGS, si5338_reg_is_restorable,
+};
+
+#[test]
+fn dumpset_covers_multisynths_and_pll() {
+    let multisynths = (0..4).flat_map(|index| {
+        let base = 53 + index * 11;
+        base..base + 10
+    });
+    let msn = 97..=106;
+    let r_dividers_and_enables = 31..=39;
+    let pll_config = 48..=50;
+    for addr in multisynths
+        .chain(msn)
+        .chain(r_dividers_and_enables)
+        .chain(pll_config)
+    {
+        assert!(SI5338_REG_DUMPSET.contains(&addr), "register {addr}");
+    }
+    for addr in [6, 28, 29, 30, 230] {
+        assert!(SI5338_REG_DUMPSET.contains(&addr), "register {addr}");
+    }
+}
+
+#[test]
+fn status_registers_are_dumped_but_not_restored() {
+    for addr in SI5338_VOLATILE_REGS {
+        assert!(SI5338_REG_DUMPSET.contains(&addr));
+        assert!(!si5338_reg_is_restorable(addr));
+    }
+    let restored = SI5338_REG_DUMPSET
+        .iter()
+        .filter(|&&addr| si5338_reg_is_restorable(addr))
+        .count();
+    assert_eq!(
+        restored,
+        SI5338_REG_DUMPSET.len() - SI5338_VOLATILE_REGS.len()
+    );
+}
@@ -0,0 +1,27 @@
+use libbladerf_rs::bladerf1::SignalPeak;
+use num_complex::Complex;
+
+#[test]
+fn empty_measures_zero() {
+    assert_eq!(SignalPeak::measure(&[]), SignalPeak::default());
+}
+
+#[test]
+fn peaks_are_per_component_magnitudes() {
+    let samples = [
+        Complex::new(0.25, -0.5),
+        Complex::new(-0.75, 0.125),
+        Complex::new(0.5, 0.25),
+    ];
+    let peak = SignalPeak::measure(&samples);
+    assert_eq!(peak.i, 0.75);
+    assert_eq!(peak.q, 0.5);
+}
+
+#[test]
+fn dbfs_uses_larger_component() {
+    let half = SignalPeak { i: 0.1, q: 0.5 };
+    assert!((half.dbfs() - 20.0 * 0.5f32.log10()).abs() < 1e-4);
+    assert!((SignalPeak { i: 1.0, q: 0.0 }.dbfs()).abs() < 1e-6);
+    assert_eq!(SignalPeak::default().dbfs(), f32::NEG_INFINITY);
+}
@@ -0,0 +1,51 @@
+use libbladerf_rs::Error;
+use libbladerf_rs::bladerf1::{
+    MAX_BUFFER_COUNT, align_buffer_size, check_buffer_count, stream_block_size,
+};
+use nusb::Speed;
+
+#[test]
+fn block_size_per_speed() {
+    assert_eq!(stream_block_size(Speed::Super).unwrap(), 2048);
+    assert_eq!(stream_block_size(Speed::SuperPlus).unwrap(), 2048);
+    assert_eq!(stream_block_size(Speed::High).unwrap(), 1024);
+    assert!(matches!(
+        stream_block_size(Speed::Full),
+        Err(Error::UnsupportedSpeed)
+    ));
+}
+
+#[test]
+fn aligned_sizes_unchanged() {
+    assert_eq!(align_buffer_size(65_536, Speed::Super).unwrap(), 65_536);
+    assert_eq!(align_buffer_size(2048, Speed::Super).unwrap(), 2048);
+    assert_eq!(align_buffer_size(65_536, Speed::High).unwrap(), 65_536);
+    assert_eq!(align_buffer_size(1024, Speed::High).unwrap(), 1024);
+}
+
+#[test]
+fn misaligned_sizes_rounded_up() {
+    assert_eq!(align_buffer_size(1024, Speed::Super).unwrap(), 2048);
+    assert_eq!(align_buffer_size(3000, Speed::Super).unwrap(), 4096);
+    assert_eq!(align_buffer_size(512, Speed::High).unwrap(), 1024);
+    assert_eq!(align_buffer_size(3000, Speed::High).unwrap(), 3072);
+}
+
+#[test]
+fn zero_size_rejected() {
+    assert!(matches!(
+        align_buffer_size(0, Speed::Super),
+        Err(Error::Argument(_))
+    ));
+}
+
+#[test]
+fn buffer_count_capped() {
+    assert_eq!(check_buffer_count(8).unwrap(), 8);
+    assert_eq!(
+        check_buffer_count(MAX_BUFFER_COUNT).unwrap(),
+        MAX_BUFFER_COUNT
+    );
+    assert_eq!(check_buffer_count(1000).unwrap(), MAX_BUFFER_COUNT);
+    assert!(matches!(check_buffer_count(0), Err(Error::Argument(_))));
+}
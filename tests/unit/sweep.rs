@@ -0,0 +1,51 @@
+use libbladerf_rs::Error;
+use libbladerf_rs::bladerf1::{SWEEP_MAX_STEPS, sweep_frequencies};
+
+#[test]
+fn includes_stop_on_step() {
+    assert_eq!(
+        sweep_frequencies(900_000_000, 920_000_000, 10_000_000).unwrap(),
+        [900_000_000, 910_000_000, 920_000_000]
+    );
+}
+
+#[test]
+fn excludes_stop_off_step() {
+    assert_eq!(sweep_frequencies(100, 125, 10).unwrap(), [100, 110, 120]);
+}
+
+#[test]
+fn single_point() {
+    assert_eq!(
+        sweep_frequencies(915_000_000, 915_000_000, 1).unwrap(),
+        [915_000_000]
+    );
+}
+
+#[test]
+fn rejects_zero_step_and_reversed_range() {
+    assert!(matches!(
+        sweep_frequencies(100, 200, 0),
+        Err(Error::Argument(_))
+    ));
+    assert!(matches!(
+        sweep_frequencies(200, 100, 10),
+        Err(Error::Argument(_))
+    ));
+}
+
+#[test]
+fn rejects_too_many_steps() {
+    assert!(matches!(
+        sweep_frequencies(0, u64::MAX, 1),
+        Err(Error::Argument(_))
+    ));
+    assert!(matches!(
+        sweep_frequencies(0, SWEEP_MAX_STEPS, 1),
+        Err(Error::Argument(_))
+    ));
+    assert_eq!(
+        sweep_frequencies(0, SWEEP_MAX_STEPS - 1, 1).unwrap().len() as u64,
+        SWEEP_MAX_STEPS
+    );
+}
@@ -0,0 +1,65 @@
+use libbladerf_rs::Error;
+use libbladerf_rs::bladerf1::{TONE_FULL_SCALE, ToneMeasurement, check_tone, tone_samples};
+
+fn iq(bytes: &[u8]) -> Vec<(i16, i16)> {
+    bytes
+        .chunks_exact(4)
+        .map(|s| {
+            (
+                i16::from_le_bytes([s[0], s[1]]),
+                i16::from_le_bytes([s[2], s[3]]),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn tone_correlates_at_its_period() {
+    let tone = tone_samples(31_250.0, 0.5, 2_000_000, 0, 2048).unwrap();
+    assert_eq!(tone.len(), 2048 * 4);
+    let m = ToneMeasurement::measure(&tone, 64);
+    assert!(m.correlation > 0.99, "{m:?}");
+    assert!(ToneMeasurement::measure(&tone, 16).correlation < 0.1);
+}
+
+#[test]
+fn blocks_join_without_phase_step() {
+    let whole = tone_samples(-123_456.0, 0.8, 3_000_000, 0, 1000).unwrap();
+    let head = tone_samples(-123_456.0, 0.8, 3_000_000, 0, 400).unwrap();
+    let tail = tone_samples(-123_456.0, 0.8, 3_000_000, 400, 600).unwrap();
+    assert_eq!([head, tail].concat(), whole);
+}
+
+#[test]
+fn amplitude_is_clamped_to_full_scale() {
+    let full = TONE_FULL_SCALE as i16;
+    let loud = iq(&tone_samples(0.0, 4.0, 1_000_000, 0, 4).unwrap());
+    assert!(loud.iter().all(|&s| s == (full, 0)));
+    let silent = iq(&tone_samples(1000.0, -1.0, 1_000_000, 0, 16).unwrap());
+    assert!(silent.iter().all(|&s| s == (0, 0)));
+}
+
+#[test]
+fn rejects_tone_beyond_nyquist() {
+    for tone_hz in [1_000_000.0, -1_000_000.0, 1_500_000.0, f32::NAN] {
+        assert!(matches!(
+            tone_samples(tone_hz, 0.5, 2_000_000, 0, 16),
+            Err(Error::Argument(_))
+        ));
+    }
+    assert!(tone_samples(999_000.0, 0.5, 2_000_000, 0, 16).is_ok());
+    assert!(matches!(
+        tone_samples(0.0, 0.5, 0, 0, 16),
+        Err(Error::Argument(_))
+    ));
+}
+
+#[test]
+fn check_tone_matches_tone_samples() {
+    assert!(check_tone(999_999.0, 2_000_000).is_ok());
+    assert!(matches!(
+        check_tone(1_000_000.0, 2_000_000),
+        Err(Error::Argument(_))
+    ));
+    assert!(matches!(check_tone(0.0, 0), Err(Error::Argument(_))));
+}
@@ -0,0 +1,44 @@
+use libbladerf_rs::bladerf1::{TriggerRole, TriggerState};
+
+const ARM: u8 = 1 << 0;
+const FIRE: u8 = 1 << 1;
+const MASTER: u8 = 1 << 2;
+const LINE: u8 = 1 << 3;
+
+#[test]
+fn arm_encoding() {
+    assert_eq!(TriggerRole::Master.arm_register(0), ARM | MASTER);
+    assert_eq!(TriggerRole::Slave.arm_register(0), ARM);
+    assert_eq!(TriggerRole::Slave.arm_register(MASTER | FIRE), ARM);
+    assert_eq!(
+        TriggerRole::Master.arm_register(LINE | FIRE),
+        LINE | ARM | MASTER
+    );
+}
+
+#[test]
+fn disarm_encoding() {
+    assert_eq!(
+        TriggerRole::disarm_register(ARM | MASTER | FIRE | LINE),
+        LINE
+    );
+}
+
+#[test]
+fn state_decoding() {
+    let disarmed = TriggerState::from_register(LINE);
+    assert_eq!(disarmed, TriggerState::new(None, false, false));
+
+    let slave = TriggerState::from_register(TriggerRole::Slave.arm_register(LINE));
+    assert_eq!(
+        slave,
+        TriggerState::new(Some(TriggerRole::Slave), false, false)
+    );
+
+    let master = TriggerRole::Master.arm_register(LINE);
+    let fired = (master | FIRE) & !LINE;
+    assert_eq!(
+        TriggerState::from_register(fired),
+        TriggerState::new(Some(TriggerRole::Master), true, true)
+    );
+}
@@ -0,0 +1,8 @@
+use libbladerf_rs::bladerf1::TuningMode;
+
+#[test]
+fn tuning_mode_from_str() {
+    assert_eq!(TuningMode::try_from("host").unwrap(), TuningMode::Host);
+    assert_eq!(TuningMode::try_from("FPGA").unwrap(), TuningMode::Fpga);
+    assert!(TuningMode::try_from("auto").is_err());
+}
@@ -0,0 +1,35 @@
+use libbladerf_rs::bladerf1::TxGap;
+
+const SAMPLES_PER_MESSAGE: u64 = 508;
+
+#[test]
+fn contiguous_timestamp_continues_burst() {
+    assert_eq!(
+        TxGap::between(1_000, 1_000, SAMPLES_PER_MESSAGE),
+        TxGap::None
+    );
+}
+
+#[test]
+fn short_gap_is_zero_filled() {
+    assert_eq!(
+        TxGap::between(1_000, 1_001, SAMPLES_PER_MESSAGE),
+        TxGap::ZeroFill(1)
+    );
+    assert_eq!(
+        TxGap::between(1_000, 1_000 + SAMPLES_PER_MESSAGE - 1, SAMPLES_PER_MESSAGE),
+        TxGap::ZeroFill(SAMPLES_PER_MESSAGE - 1)
+    );
+}
+
+#[test]
+fn long_gap_restarts_burst() {
+    assert_eq!(
+        TxGap::between(1_000, 1_000 + SAMPLES_PER_MESSAGE, SAMPLES_PER_MESSAGE),
+        TxGap::Restart
+    );
+    assert_eq!(
+        TxGap::between(1_000, 1_000_000, SAMPLES_PER_MESSAGE),
+        TxGap::Restart
+    );
+}
@@ -0,0 +1,25 @@
+use libbladerf_rs::Error;
+use libbladerf_rs::bladerf1::VctcxoTamerMode;
+
+#[test]
+fn control_byte_per_mode() {
+    let cases = [
+        (VctcxoTamerMode::Disabled, 0u8),
+        (VctcxoTamerMode::Pps1, 1),
+        (VctcxoTamerMode::Mhz10, 2),
+    ];
+    for (mode, byte) in cases {
+        assert_eq!(u8::from(mode), byte, "{mode:?}");
+        assert_eq!(VctcxoTamerMode::try_from(byte).unwrap(), mode);
+    }
+}
+
+#[test]
+fn unknown_control_byte() {
+    for byte in [3u8, 0x7f, 0xff] {
+        assert!(matches!(
+            VctcxoTamerMode::try_from(byte),
+            Err(Error::Unsupported(_))
+        ));
+    }
+}
@@ -0,0 +1,56 @@
+use libbladerf_rs::{Error, SemanticVersion};
+
+#[test]
+fn parse_well_formed() {
+    let cases = [
+        ("2.4.0", (2, 4, 0)),
+        ("v1.7.1", (1, 7, 1)),
+        (" 2.3.2 ", (2, 3, 2)),
+        ("2.4.0-git-09e9d49c", (2, 4, 0)),
+        ("0.11.1+dirty", (0, 11, 1)),
+    ];
+    for (s, (major, minor, patch)) in cases {
+        let v: SemanticVersion = s.parse().unwrap();
+        assert_eq!(v, SemanticVersion::new(major, minor, patch), "{s:?}");
+    }
+}
+
+#[test]
+fn parse_malformed() {
+    for s in [
+        "",
+        "2",
+        "2.4",
+        "2.4.",
+        "a.b.c",
+        "2..0",
+        "2.4.x",
+        "2.4.0x",
+        "-1.0.0",
+        "1.2.99999",
+    ] {
+        assert!(
+            matches!(s.parse::<SemanticVersion>(), Err(Error::Argument(_))),
+            "{s:?} should not parse"
+        );
+    }
+}
+
+#[test]
+fn ordering_and_display() {
+    let old = SemanticVersion::new(1, 6, 1);
+    let new = SemanticVersion::new(1, 7, 0);
+    assert!(old < new);
+    assert!(SemanticVersion::new(1, 7, 0) < SemanticVersion::new(1, 7, 1));
+    assert!(SemanticVersion::new(2, 0, 0) > SemanticVersion::new(1, 99, 99));
+    assert_eq!(new.to_string(), "1.7.0");
+
+    let err = Error::NoApi {
+        device: old,
+        min: new,
+    };
+    assert_eq!(
+        err.to_string(),
+        "operation requires version 1.7.0 or later, device has 1.6.1"
+    );
+}
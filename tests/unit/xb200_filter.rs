@@ -0,0 +1,52 @@
+use libbladerf_rs::Channel;
+use libbladerf_rs::Error;
+use libbladerf_rs::bladerf1::Xb200Filter;
+
+const BANKS: [(Xb200Filter, u32); 4] = [
+    (Xb200Filter::_50M, 0),
+    (Xb200Filter::_144M, 1),
+    (Xb200Filter::_222M, 2),
+    (Xb200Filter::Custom, 3),
+];
+
+#[test]
+fn rx_filter_bits() {
+    for (filter, sel) in BANKS {
+        let gpio = filter.apply_to_gpio(Channel::Rx, 0).unwrap();
+        assert_eq!(gpio, sel << 28, "{filter:?}");
+        assert_eq!(Xb200Filter::from_gpio(Channel::Rx, gpio), filter);
+    }
+}
+
+#[test]
+fn tx_filter_bits() {
+    for (filter, sel) in BANKS {
+        let gpio = filter.apply_to_gpio(Channel::Tx, 0).unwrap();
+        assert_eq!(gpio, sel << 26, "{filter:?}");
+        assert_eq!(Xb200Filter::from_gpio(Channel::Tx, gpio), filter);
+    }
+}
+
+#[test]
+fn filter_bits_preserve_other_channel() {
+    let gpio = 0xffff_ffff;
+    let rx = Xb200Filter::_50M.apply_to_gpio(Channel::Rx, gpio).unwrap();
+    assert_eq!(rx, 0xcfff_ffff);
+    assert_eq!(Xb200Filter::from_gpio(Channel::Tx, rx), Xb200Filter::Custom);
+    let tx = Xb200Filter::_144M.apply_to_gpio(Channel::Tx, rx).unwrap();
+    assert_eq!(tx, 0xc7ff_ffff);
+    assert_eq!(Xb200Filter::from_gpio(Channel::Rx, tx), Xb200Filter::_50M);
+}
+
+#[test]
+fn auto_modes_are_not_banks() {
+    for filter in [Xb200Filter::Auto1db, Xb200Filter::Auto3db] {
+        assert!(filter.is_auto());
+        for ch in [Channel::Rx, Channel::Tx] {
+            assert!(matches!(
+                filter.apply_to_gpio(ch, 0),
+                Err(Error::Argument(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use libbladerf_rs::bladerf1::Xb300Amplifier;
+
+const AUX_EN: u32 = 0x000002;
+const TX_LED: u32 = 0x000010;
+const RX_LED: u32 = 0x000020;
+const PA_EN: u32 = 0x000200;
+const LNA_EN: u32 = 0x000400;
+
+const AMPLIFIERS: [Xb300Amplifier; 3] =
+    [Xb300Amplifier::Pa, Xb300Amplifier::Lna, Xb300Amplifier::Aux];
+
+#[test]
+fn pa_bits() {
+    assert_eq!(Xb300Amplifier::Pa.apply_to_gpio(true, 0), TX_LED | PA_EN);
+    assert_eq!(
+        Xb300Amplifier::Pa.apply_to_gpio(false, !0),
+        !(TX_LED | PA_EN)
+    );
+}
+
+#[test]
+fn lna_enable_is_active_low() {
+    assert_eq!(Xb300Amplifier::Lna.apply_to_gpio(true, LNA_EN), RX_LED);
+    assert_eq!(Xb300Amplifier::Lna.apply_to_gpio(false, RX_LED), LNA_EN);
+    assert!(Xb300Amplifier::Lna.is_enabled_in_gpio(0));
+    assert!(!Xb300Amplifier::Lna.is_enabled_in_gpio(LNA_EN));
+}
+
+#[test]
+fn aux_bits() {
+    assert_eq!(Xb300Amplifier::Aux.apply_to_gpio(true, 0), AUX_EN);
+    assert_eq!(Xb300Amplifier::Aux.apply_to_gpio(false, !0), !AUX_EN);
+}
+
+#[test]
+fn enable_round_trips() {
+    for amp in AMPLIFIERS {
+        for gpio in [0, !0, LNA_EN, PA_EN | AUX_EN] {
+            for enable in [true, false] {
+                let val = amp.apply_to_gpio(enable, gpio);
+                assert_eq!(amp.is_enabled_in_gpio(val), enable, "{amp:?} {gpio:#x}");
+            }
+        }
+    }
+}
+
+#[test]
+fn amplifiers_are_independent() {
+    for amp in AMPLIFIERS {
+        let val = amp.apply_to_gpio(true, Xb300Amplifier::Lna.apply_to_gpio(false, 0));
+        for other in AMPLIFIERS.into_iter().filter(|&o| o != amp) {
+            assert!(!other.is_enabled_in_gpio(val), "{amp:?} enabled {other:?}");
+        }
+    }
+}